@@ -1,6 +1,9 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 
+use isin::checksum::checksum_branchless;
 use isin::checksum::checksum_functional;
+#[cfg(feature = "simd")]
+use isin::checksum::checksum_simd;
 use isin::checksum::checksum_table;
 
 const PAYLOADS: [&str; 3] = [
@@ -18,6 +21,13 @@ fn bench_checksums(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::new("Table", p), p, |b, p| {
             b.iter(|| checksum_table(p.as_bytes()))
         });
+        group.bench_with_input(BenchmarkId::new("Branchless", p), p, |b, p| {
+            b.iter(|| checksum_branchless(p.as_bytes()))
+        });
+        #[cfg(feature = "simd")]
+        group.bench_with_input(BenchmarkId::new("Simd", p), p, |b, p| {
+            b.iter(|| checksum_simd(p.as_bytes()))
+        });
     }
 }
 
@@ -14,6 +14,43 @@ fn bench_parses(c: &mut Criterion) {
     }
 
     group.finish();
+
+    let mut group = c.benchmark_group("ParseLoose");
+
+    for p in ISIN_STRINGS.iter() {
+        let lowercase = format!(" {} ", p.to_ascii_lowercase());
+        group.bench_function(*p, |b| b.iter(|| isin::parse_loose(black_box(&lowercase))));
+    }
+
+    group.finish();
+
+    let mut group = c.benchmark_group("ParseBatch");
+
+    // Valid ISINs with the payloads of the inputs above, since batches are meant for mostly-valid
+    // data
+    let valid: Vec<String> = ISIN_STRINGS
+        .iter()
+        .map(|p| isin::build_from_payload(&p[..11]).unwrap().to_string())
+        .collect();
+    let values: Vec<&str> = valid
+        .iter()
+        .map(String::as_str)
+        .cycle()
+        .take(1024)
+        .collect();
+    group.bench_function("parse", |b| {
+        b.iter(|| {
+            black_box(&values)
+                .iter()
+                .map(|v| isin::parse(v))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("validate_batch", |b| {
+        b.iter(|| isin::validate_batch(black_box(&values)))
+    });
+
+    group.finish();
 }
 
 criterion_group!(benches, bench_parses);
@@ -0,0 +1,20 @@
+//! Compute the checksum of arbitrary bytes every way the crate can, checking they all agree with
+//! the direct translation of the formula.
+
+#![no_main]
+
+use isin::checksum::{
+    checksum_branchless, checksum_functional, checksum_table, ChecksumAccumulator,
+};
+use isin::mod10::BASE36;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let expected = checksum_functional(data);
+    assert_eq!(checksum_table(data), expected);
+    assert_eq!(checksum_branchless(data), expected);
+    assert_eq!(BASE36.checksum(data), expected);
+
+    let mut acc = ChecksumAccumulator::new();
+    assert_eq!(acc.push_bytes(data).map(|_| acc.finalize()), expected);
+});
@@ -0,0 +1,31 @@
+//! Parse arbitrary input strictly and loosely, checking the results agree with each other.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let from_bytes = isin::parse_bytes(data);
+    assert_eq!(from_bytes.is_ok(), isin::validate_bytes(data).is_ok());
+
+    if let Ok(isin) = from_bytes {
+        // A valid ISIN is exactly its input, so the unchecked UTF-8 view must match it.
+        assert_eq!(AsRef::<str>::as_ref(&isin).as_bytes(), data);
+        let displayed = isin.to_string();
+        assert_eq!(isin::parse(&displayed), Ok(isin));
+    }
+
+    if let Ok(s) = std::str::from_utf8(data) {
+        let strict = isin::parse(s);
+        assert_eq!(strict.ok(), from_bytes.ok());
+        assert_eq!(strict.is_ok(), isin::is_valid(s));
+
+        let loose = isin::parse_loose(s);
+        if let Ok(isin) = strict {
+            assert_eq!(loose, Ok(isin));
+        }
+        if let Ok(isin) = loose {
+            assert_eq!(isin.to_string(), s.trim().to_ascii_uppercase());
+        }
+    }
+});
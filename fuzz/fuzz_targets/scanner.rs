@@ -0,0 +1,27 @@
+//! Scan arbitrary text for ISINs, checking every hit is what parsing its range gives, and that
+//! scanning from a reader finds the same hits.
+
+#![no_main]
+
+use isin::scanner::{find_isins, find_isins_in_reader};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let hits: Vec<_> = find_isins(text).collect();
+    let mut last_end = 0;
+    for (range, result) in &hits {
+        assert!(range.start >= last_end);
+        assert_eq!(range.len(), 12);
+        let candidate = &text[range.clone()];
+        assert_eq!(*result, isin::parse(candidate).map_err(isin::Error::from));
+        last_end = range.end;
+    }
+
+    let from_reader: Vec<_> = find_isins_in_reader(text.as_bytes())
+        .map(|hit| {
+            let (range, result) = hit.unwrap();
+            (range.start as usize..range.end as usize, result)
+        })
+        .collect();
+    assert_eq!(from_reader, hits);
+});
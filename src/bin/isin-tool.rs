@@ -47,6 +47,8 @@
 //! Read 5273047 values; 5273047 were valid ISINs and 0 were not.
 //! ```
 //!
+//! Blank lines are skipped in both modes. Each invalid value is reported on standard error as it
+//! is found, and the summary is followed by the number of invalid values with each kind of error.
 //! If no bad values were found, the tool will exit with zero status, else non-zero.
 //!
 //! ## Fix mode
@@ -58,7 +60,19 @@
 use std::env;
 use std::io;
 use std::io::prelude::*;
-use std::str::from_utf8_unchecked;
+
+use isin::bulk::{LineFailure, Observer, Outcome};
+
+/// Prints each invalid line as it is found.
+struct PrintFailures;
+
+impl Observer for PrintFailures {
+    fn observe(&mut self, _: Outcome) {}
+
+    fn observe_failure(&mut self, failure: &LineFailure) {
+        eprintln!("Input: {}; Error: {}", failure.input, failure.error);
+    }
+}
 
 #[doc(hidden)]
 fn main() {
@@ -72,34 +86,47 @@ fn main() {
         std::process::exit(1);
     }
 
+    let stdin = io::stdin();
+
+    if !fix {
+        let report = match isin::bulk::validate_lines_observed(stdin.lock(), 0, &mut PrintFailures)
+        {
+            Ok(report) => report,
+            Err(err) => {
+                eprintln!("Error reading input: {err}");
+                std::process::exit(1);
+            }
+        };
+        eprintln!(
+            "Read {} values; {} were valid ISINs and {} were not.",
+            report.total, report.valid, report.invalid
+        );
+        for (kind, count) in &report.error_counts {
+            eprintln!("  {}: {}", kind.as_str(), count);
+        }
+
+        let result = (report.invalid == 0) as i32;
+        std::process::exit(result);
+    }
+
     let mut good = 0u64;
     let mut bad = 0u64;
     let mut fixed = 0u64;
 
-    let stdin = io::stdin();
     for line in stdin.lock().lines() {
         let line = line.unwrap();
-        match isin::parse(&line) {
-            Ok(isin) => {
+        if line.bytes().all(|b| b.is_ascii_whitespace()) {
+            continue;
+        }
+        match isin::parse_fixing_check_digit(&line) {
+            Ok((isin, false)) => {
                 good += 1;
-                if fix {
-                    println!("{isin}");
-                }
+                println!("{isin}");
             }
-            Err(isin::Error::IncorrectCheckDigit {
-                was: _,
-                expected: _,
-            }) => {
+            Ok((isin, true)) => {
                 bad += 1;
-                if fix {
-                    let payload = &line.as_bytes()[0..11]; // We know it was the right length
-                    let payload = unsafe { from_utf8_unchecked(payload) }; // We know it is ASCII
-
-                    // We know the Check Digit was the only problem, so we can safely unwrap()
-                    let isin = isin::build_from_payload(payload).unwrap();
-                    println!("{isin}");
-                    fixed += 1;
-                }
+                println!("{isin}");
+                fixed += 1;
             }
             Err(err) => {
                 eprintln!("Input: {line}; Error: {err}");
@@ -108,30 +135,18 @@ fn main() {
         }
     }
 
-    if fix {
-        eprintln!(
-            "Read {} values; {} were valid ISINs and {} were not. Fixed {}; Omitted {}.",
-            good + bad,
-            good,
-            bad,
-            fixed,
-            bad - fixed
-        );
+    eprintln!(
+        "Read {} values; {} were valid ISINs and {} were not. Fixed {}; Omitted {}.",
+        good + bad,
+        good,
+        bad,
+        fixed,
+        bad - fixed
+    );
 
-        if bad > fixed {
-            std::process::exit(1);
-        } else {
-            std::process::exit(0);
-        }
+    if bad > fixed {
+        std::process::exit(1);
     } else {
-        eprintln!(
-            "Read {} values; {} were valid ISINs and {} were not.",
-            good + bad,
-            good,
-            bad
-        );
-
-        let result = (bad == 0) as i32;
-        std::process::exit(result);
+        std::process::exit(0);
     }
 }
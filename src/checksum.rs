@@ -23,33 +23,78 @@
 //! effect each character has on the checksum accumulator at that point and how it effects
 //! whether the next character is in a doubling position or not.
 //!
-//! Benchmarking shows the table-driven implementation to be around 100 times faster
-//! than the functional style (on the test system, average run time decreases from around 2,015 ns
-//! with the functional style to around 19 ns with the table-driven style). Input-dependent
-//! variability in run time decreases also from about +/- 14% for the functional-style to about
-//! +/- 3% for the table-driven style.
+//! Benchmarking shows the table-driven implementation to be around 20 times faster than the
+//! functional style (on the test system, average run time for the _Payload_ `US037833100`
+//! decreases from around 320 ns with the functional style to around 17 ns with the table-driven
+//! style). Input-dependent variability in run time decreases also, from between about 290 and
+//! 430 ns for the functional style to between about 14 and 17 ns for the table-driven style.
+//!
+//! `checksum_branchless()` folds the three tables into one, so each character takes a single
+//! lookup, and replaces the branch on parity and the reduction in the loop with a shift and a
+//! sum reduced only at the end. It benchmarks only a little faster than the table-driven style
+//! (around 12 ns on the test system, for any input), because the parity for each character still
+//! depends on the characters after it, so the lookups cannot overlap. Breaking that dependency
+//! takes computing every character's parity at once, as `checksum_simd()` does with the `simd`
+//! feature (around 9 ns).
+//!
+//! To compute the _Check Digit_ for an ISIN _Payload_ assembled elsewhere, use
+//! `check_digit_of()`, which also validates the format of the _Payload_. To compute it while the
+//! characters arrive one at a time or in pieces, use a `ChecksumAccumulator`. For the same formula
+//! over an alphabet other than the ISIN one, see the [`mod10`](crate::mod10) module.
+//!
+//! For code that needs nothing but digit verification, such as a WASM filter, `checksum_digit()`
+//! and `has_valid_check_digit()` are `const fn`s that use no error type, formatting or allocation.
+//! Without the default `full` feature (`default-features = false`), they are all there is of this
+//! crate, with no formatting machinery, error types or allocation.
 
-/// The numeric value of a u8 ASCII character. Digit characters '0' through '9' map to values 0
-/// through 9, and letter characters 'A' through 'Z' map to values 10 through 35.
+#[cfg(feature = "full")]
+use crate::{Error, ParseError, Payload};
+
+/// Compute the _Check Digit_ for a _Payload_ of two uppercase ASCII letters and nine uppercase
+/// ASCII letters or digits, returning an error if `payload` is not in that format.
 ///
-/// # Panics
+/// ```
+/// use isin::checksum::check_digit_of;
 ///
-/// If anything other than an uppercase ASCII alphanumeric character is passed in, this function
-/// panics because it is only intended to be called from locations where the input has already been
-/// validated to match the character set requirements.
-fn char_value(c: &u8) -> u8 {
-    if c.is_ascii_digit() {
-        c - b'0'
-    } else if c.is_ascii_uppercase() {
-        c - b'A' + 10
-    } else {
-        panic!("Non-ASCII-alphanumeric characters should be impossible here!");
+/// assert_eq!(check_digit_of("US037833100"), Ok('5'));
+/// assert!(check_digit_of("US03783310").is_err());
+/// assert!(check_digit_of("us037833100").is_err());
+/// ```
+#[cfg(feature = "full")]
+pub fn check_digit_of(payload: &str) -> Result<char, Error> {
+    let payload = Payload::parse(payload)?;
+    Ok(payload.to_isin().check_digit())
+}
+
+/// The numeric value of a u8 ASCII character. Digit characters '0' through '9' map to values 0
+/// through 9, and letter characters 'A' through 'Z' map to values 10 through 35. Anything else has
+/// no value.
+#[cfg(feature = "full")]
+fn char_value(c: &u8) -> Option<u8> {
+    match VALUES[*c as usize] {
+        INVALID => None,
+        v => Some(v),
     }
 }
 
-/// A direct translation of the formula definition, in the functional style.
+/// The error for the first character of `s` that has no value. Used once we have found there is
+/// one, but perhaps not the first if we were working from the right. Falls back to `index`, the one
+/// we found, although it is always the first or one before it.
+#[cfg(feature = "full")]
+fn first_invalid_character(s: &[u8], index: usize) -> ParseError {
+    let index = s
+        .iter()
+        .position(|c| char_value(c).is_none())
+        .unwrap_or(index);
+    ParseError::invalid_character(index, s[index])
+}
+
+/// A direct translation of the formula definition, in the functional style. Returns an
+/// [`InvalidCharacter`](ParseError::InvalidCharacter) error for the first character that is not an
+/// uppercase ASCII alphanumeric.
+#[cfg(feature = "full")]
 #[allow(dead_code)]
-pub fn checksum_functional(s: &[u8]) -> u8 {
+pub fn checksum_functional(s: &[u8]) -> Result<u8, ParseError> {
     fn digits_of(x: u8) -> Vec<u8> {
         if x >= 10 {
             vec![x / 10, x % 10]
@@ -58,9 +103,14 @@ pub fn checksum_functional(s: &[u8]) -> u8 {
         }
     }
 
-    let sum: u32 = s
+    let values = s
         .iter()
-        .map(char_value)
+        .enumerate()
+        .map(|(index, c)| char_value(c).ok_or_else(|| first_invalid_character(s, index)))
+        .collect::<Result<Vec<u8>, ParseError>>()?;
+
+    let sum: u32 = values
+        .into_iter()
         .flat_map(digits_of)
         .rev()
         .enumerate()
@@ -78,9 +128,9 @@ pub fn checksum_functional(s: &[u8]) -> u8 {
 
     let diff = 10 - sum;
     if diff == 10 {
-        0
+        Ok(0)
     } else {
-        diff
+        Ok(diff)
     }
 }
 
@@ -128,18 +178,295 @@ const EVENS: [u8; 36] = [
 const MAX_ACCUM: u8 = u8::MAX - 9;
 
 /// Compute the _checksum_ for a u8 array. No attempt is made to ensure the input string is in
-/// the ISIN payload format or length.
+/// the ISIN payload format or length, but an [`InvalidCharacter`](ParseError::InvalidCharacter)
+/// error is returned for the first character that is not an ASCII digit or an ASCII uppercase
+/// letter. This is `checksum_digit()` with the error in place of `None`.
+#[cfg(feature = "full")]
+pub fn checksum_table(s: &[u8]) -> Result<u8, ParseError> {
+    checksum_digit(s).ok_or_else(|| first_invalid_character(s, 0))
+}
+
+/// Compute the _checksum_ as `checksum_table()` does, but for inputs of 8 to 16 bytes (such as an
+/// ISIN _Payload_) on `x86_64`, with SSE2 instructions working on all the characters at once. Other
+/// inputs and targets, and builds with the `forbid-unsafe` feature, use `checksum_table()`.
+///
+/// This function is only available with the `simd` feature enabled.
+#[cfg(feature = "simd")]
+pub fn checksum_simd(s: &[u8]) -> Result<u8, ParseError> {
+    #[cfg(all(target_arch = "x86_64", not(feature = "forbid-unsafe")))]
+    if (8..=16).contains(&s.len()) {
+        // SAFETY: SSE2 is part of the x86_64 baseline, so it is always available.
+        if let Some(sum) = unsafe { sse2::checksum_8_to_16(s) } {
+            return Ok(sum);
+        }
+    }
+    checksum_table(s)
+}
+
+/// The SSE2 implementation of `checksum_simd()`. SSE2 is part of the `x86_64` baseline, so there
+/// is no need to detect it at run time.
+#[cfg(all(
+    feature = "simd",
+    target_arch = "x86_64",
+    not(feature = "forbid-unsafe")
+))]
+mod sse2 {
+    use std::arch::x86_64::*;
+
+    /// Compute the checksum of `s`, which must be 8 to 16 bytes long, or `None` if any of its
+    /// bytes is not an uppercase ASCII alphanumeric.
+    ///
+    /// Each lane computes the net value of its character for both parities, as in the `EVENS` and
+    /// `ODDS` tables, and then picks one. The parity of a character is the parity of the sum of the
+    /// widths of the characters to its right, and since letters have width two, that is the parity
+    /// of the number of digits to its right.
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    pub(super) fn checksum_8_to_16(s: &[u8]) -> Option<u8> {
+        let len = s.len();
+        if !(8..=16).contains(&len) {
+            return None;
+        }
+
+        // Right-align the input in the register, with zeros on the left, from its first and last
+        // eight bytes. Where they overlap, they have the same bytes.
+        let first = u64::from_le_bytes(s[..8].try_into().ok()?) as u128;
+        let last = u64::from_le_bytes(s[len - 8..].try_into().ok()?) as u128;
+        let x = (first << ((16 - len) * 8)) | (last << 64);
+        let x = _mm_set_epi64x((x >> 64) as i64, x as i64);
+
+        let digit = _mm_and_si128(
+            _mm_cmpgt_epi8(x, _mm_set1_epi8(b'0' as i8 - 1)),
+            _mm_cmplt_epi8(x, _mm_set1_epi8(b'9' as i8 + 1)),
+        );
+        let letter = _mm_and_si128(
+            _mm_cmpgt_epi8(x, _mm_set1_epi8(b'A' as i8 - 1)),
+            _mm_cmplt_epi8(x, _mm_set1_epi8(b'Z' as i8 + 1)),
+        );
+        let valid = _mm_movemask_epi8(_mm_or_si128(digit, letter)) as u32;
+        if valid != (0xffff_u32 << (16 - len)) & 0xffff {
+            return None;
+        }
+
+        let one = _mm_set1_epi8(1);
+        let four = _mm_set1_epi8(4);
+        let nine = _mm_set1_epi8(9);
+        let ten = _mm_set1_epi8(10);
+        // Double a value from 0 to 9 and add the digits: 2v, less 9 if that is 10 or more.
+        let double_add = |v| {
+            _mm_sub_epi8(
+                _mm_add_epi8(v, v),
+                _mm_and_si128(_mm_cmpgt_epi8(v, four), nine),
+            )
+        };
+
+        // A digit has value d, and one digit.
+        let d = _mm_sub_epi8(x, _mm_set1_epi8(b'0' as i8));
+        let digit_even = double_add(d);
+        let digit_odd = d;
+
+        // A letter has value t + 10, with digits h and l.
+        let t = _mm_sub_epi8(x, _mm_set1_epi8(b'A' as i8));
+        let ge20 = _mm_cmpgt_epi8(t, nine);
+        let ge30 = _mm_cmpgt_epi8(t, _mm_set1_epi8(19));
+        let h = _mm_add_epi8(
+            one,
+            _mm_add_epi8(_mm_and_si128(ge20, one), _mm_and_si128(ge30, one)),
+        );
+        let l = _mm_sub_epi8(
+            t,
+            _mm_add_epi8(_mm_and_si128(ge20, ten), _mm_and_si128(ge30, ten)),
+        );
+        let letter_even = _mm_add_epi8(h, double_add(l));
+        let letter_odd = _mm_add_epi8(_mm_add_epi8(h, h), l);
+
+        // XOR the digit flags of all the lanes to the right of each lane. Shifting the register
+        // right moves higher lanes, which hold later characters, into lower ones.
+        let mut odd = digit;
+        odd = _mm_xor_si128(odd, _mm_srli_si128::<1>(odd));
+        odd = _mm_xor_si128(odd, _mm_srli_si128::<2>(odd));
+        odd = _mm_xor_si128(odd, _mm_srli_si128::<4>(odd));
+        odd = _mm_xor_si128(odd, _mm_srli_si128::<8>(odd));
+        let odd = _mm_xor_si128(odd, digit);
+
+        let select = |even, odd_value| {
+            _mm_or_si128(_mm_andnot_si128(odd, even), _mm_and_si128(odd, odd_value))
+        };
+        let net = _mm_or_si128(
+            _mm_and_si128(digit, select(digit_even, digit_odd)),
+            _mm_and_si128(letter, select(letter_even, letter_odd)),
+        );
+
+        // Each lane is at most 15, so the sum of 16 lanes fits in a byte.
+        let sums = _mm_sad_epu8(net, _mm_setzero_si128());
+        let sum = _mm_cvtsi128_si64(sums) + _mm_cvtsi128_si64(_mm_srli_si128::<8>(sums));
+        Some(((10 - sum % 10) % 10) as u8)
+    }
+}
+
+/// The value `checksum_slab()` gives for a record whose _Payload_ is not in the ISIN format.
+#[cfg(feature = "full")]
+pub const INVALID_PAYLOAD: u8 = u8::MAX;
+
+/// The number of records `checksum_slab()` works on together.
+#[cfg(feature = "full")]
+pub(crate) const SLAB_LANES: usize = 16;
+
+/// Compute the _checksum_ of the _Payload_ (the first eleven bytes) of each of a contiguous slab
+/// of 12-byte records, such as a column of ISINs. The result for a record whose _Payload_ is not
+/// two uppercase ASCII letters followed by nine uppercase ASCII alphanumerics is
+/// [`INVALID_PAYLOAD`]. The last byte of each record is ignored.
+///
+/// ```
+/// use isin::checksum::{checksum_slab, INVALID_PAYLOAD};
+///
+/// let records = [*b"US0378331005", *b"US594918104?", *b"us0378331005"];
+/// assert_eq!(checksum_slab(&records), vec![5, 5, INVALID_PAYLOAD]);
+/// ```
+///
+/// The records are processed in groups, each character position at a time across the group,
+/// computing the net value of each character arithmetically rather than by table lookup. This
+/// gives the compiler straight-line code over arrays that it can vectorize.
+#[cfg(feature = "full")]
+pub fn checksum_slab(records: &[[u8; 12]]) -> Vec<u8> {
+    let mut checksums = Vec::with_capacity(records.len());
+    for group in records.chunks(SLAB_LANES) {
+        let mut lanes = [[b'0'; 12]; SLAB_LANES];
+        lanes[..group.len()].copy_from_slice(group);
+        checksums.extend_from_slice(&checksum_lanes(&lanes)[..group.len()]);
+    }
+    checksums
+}
+
+/// Compute the checksums of the payloads of a group of records for `checksum_slab()`.
+#[cfg(feature = "full")]
+pub(crate) fn checksum_lanes(records: &[[u8; 12]; SLAB_LANES]) -> [u8; SLAB_LANES] {
+    let mut sum = [0u8; SLAB_LANES];
+    // 0xff where the character is an odd number of steps from the right, else 0
+    let mut odd = [0u8; SLAB_LANES];
+    // Nonzero where a character was not allowed at its position
+    let mut bad = [0u8; SLAB_LANES];
+
+    // Double a value from 0 to 9 and add the digits.
+    let double_add = |v: u8| v.wrapping_mul(2).wrapping_sub(9 * (v > 4) as u8);
+
+    for pos in (0..11).rev() {
+        for lane in 0..SLAB_LANES {
+            let c = records[lane][pos];
+            let d = c.wrapping_sub(b'0');
+            let t = c.wrapping_sub(b'A');
+            let is_digit = 0u8.wrapping_sub((d < 10) as u8);
+            let is_letter = 0u8.wrapping_sub((t < 26) as u8);
+            let allowed = if pos < 2 {
+                is_letter
+            } else {
+                is_digit | is_letter
+            };
+            bad[lane] |= !allowed;
+
+            // A digit has value d. A letter has value t + 10, with digits h and l.
+            let tens = (t > 9) as u8 + (t > 19) as u8;
+            let h = 1 + tens;
+            let l = t.wrapping_sub(10 * tens);
+            let even = (is_digit & double_add(d)) | (is_letter & h.wrapping_add(double_add(l)));
+            let odd_value = (is_digit & d) | (is_letter & (2 * h).wrapping_add(l));
+
+            sum[lane] = sum[lane].wrapping_add((even & !odd[lane]) | (odd_value & odd[lane]));
+            odd[lane] ^= is_digit;
+        }
+    }
+
+    let mut checksums = [0u8; SLAB_LANES];
+    for lane in 0..SLAB_LANES {
+        checksums[lane] = if bad[lane] != 0 {
+            INVALID_PAYLOAD
+        } else {
+            (10 - sum[lane] % 10) % 10
+        };
+    }
+    checksums
+}
+
+/// Set in `COMBINED` entries for digits, whose width of one flips the parity of the step count.
+#[cfg(feature = "full")]
+const FLIP: u16 = 1 << 8;
+/// Set in `COMBINED` entries for bytes that are not uppercase ASCII alphanumerics.
+#[cfg(feature = "full")]
+const NOT_ALPHANUMERIC: u16 = 1 << 9;
+
+/// The `EVENS`, `ODDS` and `WIDTHS` tables folded into one entry for every possible byte, along
+/// with its character class. The net value for an even step count is in bits 0 to 3, and for an
+/// odd one in bits 4 to 7. Only the parity of the width matters, so it is stored as `FLIP`, set
+/// for digits, whose width of one changes the parity of the step count for the characters to the
+/// left.
+#[cfg(feature = "full")]
+const COMBINED: [u16; 256] = {
+    let mut combined = [NOT_ALPHANUMERIC; 256];
+    let mut c = 0;
+    while c < 256 {
+        let v = VALUES[c];
+        if v != INVALID {
+            let v = v as usize;
+            combined[c] = EVENS[v] as u16 | (ODDS[v] as u16) << 4;
+            if WIDTHS[v] == 1 {
+                combined[c] |= FLIP;
+            }
+        }
+        c += 1;
+    }
+    combined
+};
+
+/// The most characters `checksum_branchless()` sums before reducing the sum mod 10, so it cannot
+/// overflow.
+#[cfg(feature = "full")]
+const BRANCHLESS_RUN: usize = 1 << 24;
+
+/// Compute the _checksum_ as `checksum_table()` does, with one lookup per character and no
+/// branches in the loop. The entry for each character holds its net value for both parities, and
+/// the parity selects one by a shift. Invalid characters are noted in the same entries and only
+/// checked for after the loop.
+#[cfg(feature = "full")]
+pub fn checksum_branchless(s: &[u8]) -> Result<u8, ParseError> {
+    let mut sum: u32 = 0;
+    let mut shift: u32 = 0;
+    let mut flags: u16 = 0;
+    for run in s.rchunks(BRANCHLESS_RUN) {
+        for &c in run.iter().rev() {
+            let e = COMBINED[c as usize];
+            sum += ((e >> shift) & 0xf) as u32;
+            shift ^= ((e & FLIP) >> 6) as u32;
+            flags |= e;
+        }
+        sum %= 10;
+    }
+    if flags & NOT_ALPHANUMERIC != 0 {
+        return Err(first_invalid_character(s, 0));
+    }
+    Ok(((10 - sum) % 10) as u8)
+}
+
+/// Compute the _checksum_ for a u8 array in the table-driven style, or `None` if any character is
+/// not an uppercase ASCII alphanumeric. As with `checksum_table()`, no attempt is made to ensure
+/// the input is in the ISIN payload format or length.
 ///
-/// # Panics
+/// ```
+/// use isin::checksum::checksum_digit;
 ///
-/// If an illegal character (not an ASCII digit and not an
-/// ASCII uppercase letter) is encountered, the char_value() function this calls will panic.
-pub fn checksum_table(s: &[u8]) -> u8 {
+/// const APPLE: Option<u8> = checksum_digit(b"US037833100");
+/// assert_eq!(APPLE, Some(5));
+/// assert_eq!(checksum_digit(b"us037833100"), None);
+/// ```
+pub const fn checksum_digit(s: &[u8]) -> Option<u8> {
     let mut sum: u8 = 0;
     let mut idx: usize = 0;
-    for c in s.iter().rev() {
-        let v = char_value(c);
-        let w = WIDTHS[v as usize];
+    let mut i = s.len();
+    while i > 0 {
+        i -= 1;
+        let v = VALUES[s[i] as usize];
+        if v == INVALID {
+            return None;
+        }
         // "idx & 0x1" Seems to save a few percent on run time vs. "idx % 2"
         let x = if (idx & 0x1) == 0 {
             EVENS[v as usize]
@@ -152,19 +479,219 @@ pub fn checksum_table(s: &[u8]) -> u8 {
             sum %= 10
         }
         sum += x;
-        idx += w as usize;
+        idx += WIDTHS[v as usize] as usize;
+    }
+    Some((10 - sum % 10) % 10)
+}
+
+/// Test whether or not the last byte of `s` is a decimal digit equal to the checksum of the bytes
+/// before it. This checks no more of the format of an ISIN than that those bytes are uppercase
+/// ASCII alphanumerics, so combine it with a length check if you need one.
+///
+/// ```
+/// use isin::checksum::has_valid_check_digit;
+///
+/// assert!(has_valid_check_digit(b"US0378331005"));
+/// assert!(!has_valid_check_digit(b"US0378331006"));
+/// ```
+pub const fn has_valid_check_digit(s: &[u8]) -> bool {
+    match s.split_last() {
+        Some((&last, rest)) if last.is_ascii_digit() => match checksum_digit(rest) {
+            Some(sum) => sum == last - b'0',
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Internal function testing in a `const` context whether or not `s` is a valid ISIN, as
+/// `parse()` would: twelve bytes, starting with two uppercase ASCII letters, with a valid _Check
+/// Digit_.
+#[cfg(feature = "full")]
+pub(crate) const fn is_valid_isin_bytes(s: &[u8]) -> bool {
+    s.len() == 12
+        && s[0].is_ascii_uppercase()
+        && s[1].is_ascii_uppercase()
+        && has_valid_check_digit(s)
+}
+
+/// A checksum computed incrementally, from left to right, as the characters arrive.
+///
+/// The formula works from the right, so which digits are doubled depends on how many follow them.
+/// The accumulator keeps the sum both ways, and swaps them as each digit is pushed.
+///
+/// ```
+/// use isin::checksum::ChecksumAccumulator;
+///
+/// let mut acc = ChecksumAccumulator::new();
+/// acc.push_str("US03783").unwrap();
+/// acc.push(b'3').unwrap();
+/// acc.push_str("100").unwrap();
+/// assert_eq!(acc.finalize(), 5);
+/// ```
+///
+/// As with `checksum_table()`, there is no check of the format or length of the characters pushed
+/// other than that they are uppercase ASCII alphanumerics.
+#[cfg(feature = "full")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ChecksumAccumulator {
+    /// The sum mod 10 if the digits an even number of steps from the right so far are doubled
+    even: u8,
+    /// The sum mod 10 if the digits an odd number of steps from the right so far are doubled
+    odd: u8,
+    /// The number of characters pushed so far
+    len: usize,
+}
+
+#[cfg(feature = "full")]
+impl ChecksumAccumulator {
+    /// An accumulator with no characters pushed.
+    pub const fn new() -> ChecksumAccumulator {
+        ChecksumAccumulator {
+            even: 0,
+            odd: 0,
+            len: 0,
+        }
+    }
+
+    /// The number of characters pushed so far.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Test whether or not any characters have been pushed.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Push the next character. Returns an [`InvalidCharacter`](ParseError::InvalidCharacter)
+    /// error, leaving the accumulator unchanged, if it is not an ASCII digit or an ASCII uppercase
+    /// letter. The error's index is the number of characters pushed before it.
+    pub fn push(&mut self, c: u8) -> Result<(), ParseError> {
+        let v = char_value(&c).ok_or(ParseError::invalid_character(self.len, c))?;
+        let (even, odd) = if WIDTHS[v as usize] == 1 {
+            // Every digit so far moves one step further from the right, so the sums swap.
+            (self.odd + EVENS[v as usize], self.even + ODDS[v as usize])
+        } else {
+            // Two steps, so the sums keep their roles.
+            (self.even + EVENS[v as usize], self.odd + ODDS[v as usize])
+        };
+        self.even = even % 10;
+        self.odd = odd % 10;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Push the next characters. If any of them is not an ASCII digit or an ASCII uppercase
+    /// letter, none of them are pushed and an [`InvalidCharacter`](ParseError::InvalidCharacter)
+    /// error is returned for the first, with its index counted from the start of everything
+    /// pushed.
+    pub fn push_str(&mut self, s: &str) -> Result<(), ParseError> {
+        self.push_bytes(s.as_bytes())
+    }
+
+    /// Push the next characters, as for [`push_str()`](Self::push_str).
+    pub fn push_bytes(&mut self, s: &[u8]) -> Result<(), ParseError> {
+        let mut next = *self;
+        for &c in s {
+            next.push(c)?;
+        }
+        *self = next;
+        Ok(())
+    }
+
+    /// The checksum of the characters pushed so far, from 0 to 9. More characters may be pushed
+    /// afterwards.
+    pub const fn finalize(&self) -> u8 {
+        (10 - self.even) % 10
+    }
+}
+
+/// Marks bytes that are not uppercase ASCII alphanumerics in `VALUES`.
+const INVALID: u8 = u8::MAX;
+
+/// The char value of every possible byte, or `INVALID`. Looking values up in this table lets
+/// `char_value()` and `checksum_payload_fused()` check the character class and find the value in
+/// one step.
+const VALUES: [u8; 256] = {
+    let mut values = [INVALID; 256];
+    let mut i = 0;
+    while i < 10 {
+        values[b'0' as usize + i] = i as u8;
+        i += 1;
+    }
+    let mut i = 0;
+    while i < 26 {
+        values[b'A' as usize + i] = 10 + i as u8;
+        i += 1;
+    }
+    values
+};
+
+/// The char value of every possible byte as in `VALUES`, but with lowercase ASCII letters having
+/// the same values as their uppercase forms.
+#[cfg(feature = "full")]
+const VALUES_IGNORE_CASE: [u8; 256] = {
+    let mut values = VALUES;
+    let mut i = 0;
+    while i < 26 {
+        values[b'a' as usize + i] = 10 + i as u8;
+        i += 1;
+    }
+    values
+};
+
+/// Compute the _checksum_ for an ISIN _Payload_ while checking its format in the same pass, as
+/// an alternative to validating the _Prefix_ and _Basic Code_ and then calling `checksum_table()`.
+/// Returns `None` at the first character that is not an uppercase ASCII letter (in the _Prefix_)
+/// or an uppercase ASCII alphanumeric (in the _Basic Code_), leaving it to the caller to work out
+/// the details of the problem.
+///
+/// Eleven characters add at most 99 to the accumulator, so unlike `checksum_table()` this never
+/// needs to reduce it before the end.
+#[cfg(feature = "full")]
+pub(crate) fn checksum_payload_fused(payload: &[u8]) -> Option<u8> {
+    checksum_payload_with(payload, &VALUES)
+}
+
+/// Compute the _checksum_ for an ISIN _Payload_ while checking its format, as
+/// `checksum_payload_fused()` does, but accepting lowercase letters as their uppercase forms, so
+/// lenient parsing need not convert the input before checking it.
+#[cfg(feature = "full")]
+pub(crate) fn checksum_payload_ignore_case(payload: &[u8]) -> Option<u8> {
+    checksum_payload_with(payload, &VALUES_IGNORE_CASE)
+}
+
+/// The implementation of `checksum_payload_fused()` with the given table of char values.
+#[cfg(feature = "full")]
+#[inline(always)]
+fn checksum_payload_with(payload: &[u8], values: &[u8; 256]) -> Option<u8> {
+    let mut sum: u8 = 0;
+    let mut idx: usize = 0;
+    for (pos, c) in payload.iter().enumerate().rev() {
+        let v = values[*c as usize];
+        if v == INVALID || (pos < 2 && v < 10) {
+            return None;
+        }
+        let x = if (idx & 0x1) == 0 {
+            EVENS[v as usize]
+        } else {
+            ODDS[v as usize]
+        };
+        sum += x;
+        idx += WIDTHS[v as usize] as usize;
     }
     sum %= 10;
 
     let diff = 10 - sum;
     if diff == 10 {
-        0
+        Some(0)
     } else {
-        diff
+        Some(diff)
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "full"))]
 mod tests {
     use super::*;
     use proptest::prelude::*;
@@ -177,8 +704,8 @@ mod tests {
         for c in ('0'..='9').chain('A'..='Z') {
             let s = c.to_string();
             let ss = s.as_bytes();
-            let a = checksum_functional(ss);
-            let b = checksum_table(ss);
+            let a = checksum_functional(ss).unwrap();
+            let b = checksum_table(ss).unwrap();
             assert_eq!(
                 a, b,
                 "checksum from library {} should equal that from functional style {} for \"{}\"",
@@ -195,8 +722,8 @@ mod tests {
         for c in ('0'..='9').chain('A'..='Z') {
             let s = format!("{}0", c);
             let ss = s.as_bytes();
-            let a = checksum_functional(ss);
-            let b = checksum_table(ss);
+            let a = checksum_functional(ss).unwrap();
+            let b = checksum_table(ss).unwrap();
             assert_eq!(
                 a, b,
                 "checksum from table style {} should equal that from functional style {} for \"{}\"",
@@ -212,8 +739,8 @@ mod tests {
         for c in ('0'..='9').chain('A'..='Z') {
             let s = format!("9{}", c);
             let ss = s.as_bytes();
-            let a = checksum_functional(ss);
-            let b = checksum_table(ss);
+            let a = checksum_functional(ss).unwrap();
+            let b = checksum_table(ss).unwrap();
             assert_eq!(
                 a, b,
                 "checksum from table style {} should equal that from functional style {} for \"{}\"",
@@ -222,17 +749,165 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ignore_case_accepts_lowercase() {
+        assert_eq!(checksum_payload_ignore_case(b"us037833100"), Some(5));
+        assert_eq!(checksum_payload_ignore_case(b"uS037833100"), Some(5));
+        assert_eq!(checksum_payload_ignore_case(b"u5037833100"), None);
+        assert_eq!(checksum_payload_ignore_case(b"us03783310-"), None);
+    }
+
+    #[test]
+    fn fused_rejects_bad_characters() {
+        assert_eq!(checksum_payload_fused(b"US037833100"), Some(5));
+        assert_eq!(checksum_payload_fused(b"U5037833100"), None);
+        assert_eq!(checksum_payload_fused(b"US03783310a"), None);
+        assert_eq!(checksum_payload_fused(b"US03783310\xff"), None);
+    }
+
     proptest! {
+        #[test]
+        fn ignore_case_matches_uppercased(s in proptest::collection::vec(any::<u8>(), 11)) {
+            prop_assert_eq!(
+                checksum_payload_ignore_case(&s),
+                checksum_payload_fused(&s.to_ascii_uppercase())
+            );
+        }
+
+        #[test]
+        fn fused_matches_table(s in "[A-Z]{2}[0-9A-Z]{9}") {
+            let ss = s.as_bytes();
+            prop_assert_eq!(checksum_payload_fused(ss), checksum_table(ss).ok());
+        }
+
         #[test]
         fn processes_all_valid_strings(s in "[A-Z]{2}[0-9A-Z]{9}") {
             let ss = s.as_bytes();
-            let a = checksum_functional(ss);
-            let b = checksum_table(ss);
+            let a = checksum_functional(ss).unwrap();
+            let b = checksum_table(ss).unwrap();
             assert_eq!(
                 a, b,
                 "checksum from table style {} should equal that from functional style {} for \"{}\"",
                 b, a, s
             );
         }
+
+        #[test]
+        fn accumulator_matches_table(s in "[0-9A-Z]{0,40}", split in any::<prop::sample::Index>()) {
+            let ss = s.as_bytes();
+            let (left, right) = ss.split_at(split.index(ss.len() + 1));
+            let mut acc = ChecksumAccumulator::new();
+            acc.push_bytes(left).unwrap();
+            for &c in right {
+                acc.push(c).unwrap();
+            }
+            prop_assert_eq!(acc.len(), ss.len());
+            prop_assert_eq!(acc.finalize(), checksum_table(ss).unwrap());
+        }
+
+        #[cfg(feature = "simd")]
+        #[test]
+        fn simd_matches_table(s in "[0-9A-Z]{0,20}") {
+            prop_assert_eq!(checksum_simd(s.as_bytes()), checksum_table(s.as_bytes()));
+        }
+
+        #[cfg(feature = "simd")]
+        #[test]
+        fn simd_matches_table_on_any_bytes(s in proptest::collection::vec(any::<u8>(), 0..20)) {
+            prop_assert_eq!(checksum_simd(&s), checksum_table(&s));
+        }
+
+        #[test]
+        fn slab_matches_fused(
+            records in proptest::collection::vec(
+                prop_oneof![
+                    "[A-Z]{2}[0-9A-Z]{10}".prop_map(|s| s.as_bytes().try_into().unwrap()),
+                    any::<[u8; 12]>(),
+                ],
+                0..40,
+            )
+        ) {
+            let expected: Vec<u8> = records
+                .iter()
+                .map(|r| checksum_payload_fused(&r[..11]).unwrap_or(INVALID_PAYLOAD))
+                .collect();
+            prop_assert_eq!(checksum_slab(&records), expected);
+        }
+
+        #[test]
+        fn branchless_matches_table(s in proptest::collection::vec(any::<u8>(), 0..40)) {
+            prop_assert_eq!(checksum_branchless(&s), checksum_table(&s));
+        }
+
+        #[test]
+        fn branchless_matches_table_on_valid(s in "[0-9A-Z]{0,40}") {
+            prop_assert_eq!(checksum_branchless(s.as_bytes()), checksum_table(s.as_bytes()));
+        }
+
+        #[test]
+        fn checksum_digit_matches_functional(s in proptest::collection::vec(any::<u8>(), 0..40)) {
+            prop_assert_eq!(checksum_digit(&s), checksum_functional(&s).ok());
+        }
+
+        #[test]
+        fn has_valid_check_digit_matches_validate(s in "[A-Z]{2}[0-9A-Z]{9}[0-9]") {
+            prop_assert_eq!(has_valid_check_digit(s.as_bytes()), crate::validate(&s).is_ok());
+        }
+
+        #[test]
+        fn never_panics(s in proptest::collection::vec(any::<u8>(), 0..40)) {
+            let a = checksum_functional(&s);
+            let b = checksum_table(&s);
+            prop_assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn accumulator_errors() {
+        let mut acc = ChecksumAccumulator::new();
+        acc.push_str("US").unwrap();
+        assert_eq!(
+            acc.push_str("03a"),
+            Err(ParseError::InvalidCharacter {
+                index: 4,
+                byte: b'a',
+                in_prefix: false
+            })
+        );
+        assert_eq!(acc.len(), 2);
+        acc.push_str("037833100").unwrap();
+        assert_eq!(acc.finalize(), 5);
+    }
+
+    #[test]
+    fn check_digit_of_payloads() {
+        use crate::BuildError;
+
+        assert_eq!(check_digit_of("US037833100"), Ok('5'));
+        assert_eq!(check_digit_of("US594918104"), Ok('5'));
+        assert_eq!(
+            check_digit_of("US0378331005"),
+            Err(Error::from(BuildError::InvalidPayloadStringLength {
+                was: 12
+            }))
+        );
+        assert!(matches!(
+            check_digit_of("U5037833100"),
+            Err(Error::InvalidPrefix { .. })
+        ));
+        assert!(check_digit_of("US03783310é").is_err());
+    }
+
+    #[test]
+    fn reject_invalid_characters() {
+        for s in [&b"US03783310a"[..], b"US0378-3310a", b"\xff"] {
+            let err = s
+                .iter()
+                .position(|c| !(c.is_ascii_digit() || c.is_ascii_uppercase()));
+            let err = err.map(|index| ParseError::invalid_character(index, s[index]));
+            assert_eq!(checksum_table(s).err(), err);
+            assert_eq!(checksum_functional(s).err(), err);
+        }
+        assert_eq!(checksum_table(b""), Ok(0));
     }
 }
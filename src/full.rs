@@ -0,0 +1,2227 @@
+//! # isin::full
+//!
+//! Everything in this crate but the `checksum` module, which is all there is without the `full`
+//! feature. The contents are re-exported from the crate root, so this module never appears in
+//! paths.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::ops::Deref;
+#[cfg(not(feature = "forbid-unsafe"))]
+use std::str::from_utf8_unchecked;
+use std::str::FromStr;
+
+#[cfg(feature = "avro")]
+pub mod avro;
+
+pub mod basic_code;
+pub use basic_code::BasicCode;
+
+pub mod builder;
+pub use builder::IsinBuilder;
+
+pub mod bulk;
+
+pub mod caseless;
+pub use caseless::CaselessKey;
+
+pub mod check_digit;
+pub use check_digit::CheckDigit;
+
+use crate::checksum::{self, checksum_payload_fused, checksum_payload_ignore_case, checksum_table};
+
+pub mod diff;
+pub use diff::IsinDiff;
+
+pub mod error;
+pub use error::{BuildError, Error, ErrorKind, ErrorWithInput, ParseError};
+
+pub mod field;
+
+pub mod fixtures;
+
+#[cfg(feature = "rand")]
+pub mod generator;
+
+#[cfg(feature = "fst")]
+pub mod fst;
+
+pub mod hash;
+
+pub mod interner;
+pub use interner::IsinInterner;
+
+#[cfg(not(feature = "forbid-unsafe"))]
+pub mod isin_str;
+#[cfg(not(feature = "forbid-unsafe"))]
+pub use isin_str::IsinStr;
+
+pub mod lowercase;
+pub use lowercase::IsinLowercase;
+
+#[cfg(feature = "i18n")]
+pub mod messages;
+
+pub mod mod10;
+
+#[cfg(feature = "nom")]
+pub mod nom;
+
+pub mod packed;
+pub use packed::PackedIsin;
+
+pub mod parse_options;
+pub use parse_options::{CheckDigitPolicy, ParseOptions};
+
+pub mod payload;
+pub use payload::Payload;
+
+#[cfg(feature = "phf")]
+pub mod phf;
+
+pub mod prefix;
+pub use prefix::Prefix;
+
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+pub mod range;
+pub use range::IsinRange;
+
+#[cfg(feature = "rayon")]
+pub mod rayon;
+
+pub mod repair;
+
+#[cfg(all(feature = "rkyv", not(feature = "forbid-unsafe")))]
+pub mod rkyv;
+
+pub mod scanner;
+pub use scanner::{ReadScanner, Scanner};
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
+pub mod slice;
+
+#[cfg(feature = "async")]
+pub mod stream;
+
+pub mod test_vectors;
+
+pub mod tokenizer;
+pub use tokenizer::IsinTokenizer;
+
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+pub mod validation_level;
+pub use validation_level::{ValidationFailure, ValidationLevel};
+
+#[cfg(feature = "winnow")]
+pub mod winnow;
+
+#[cfg(feature = "roaring")]
+pub mod roaring;
+
+/// Internal convenience function for viewing bytes already known to be ASCII as a `&str`.
+#[cfg(not(feature = "forbid-unsafe"))]
+pub(crate) const fn ascii_str(bytes: &[u8]) -> &str {
+    unsafe { from_utf8_unchecked(bytes) } // This is safe because we know it is ASCII
+}
+
+/// Internal convenience function for viewing bytes already known to be ASCII as a `&str`.
+#[cfg(feature = "forbid-unsafe")]
+pub(crate) const fn ascii_str(bytes: &[u8]) -> &str {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(_) => panic!("ISIN bytes are always ASCII"),
+    }
+}
+
+/// Internal convenience function for viewing the bytes `start..end` of an ISIN as a `&str` in a
+/// `const` context, where slice indexing is not available.
+const fn ascii_substr(bytes: &[u8], start: usize, end: usize) -> &str {
+    ascii_str(bytes.split_at(end).0.split_at(start).1)
+}
+
+/// Compute the _Check Digit_ for an array of u8. No attempt is made to ensure the input string
+/// is in the ISIN payload format or length. The caller is responsible for ensuring there are only
+/// ASCII digits and ASCII uppercase letters: anything else is a bug, which is caught by an
+/// assertion in debug builds but gives a _Check Digit_ of `0` rather than panicking in release
+/// builds.
+pub(crate) fn compute_check_digit(s: &[u8]) -> u8 {
+    match checksum_table(s) {
+        Ok(sum) => b'0' + sum,
+        Err(err) => {
+            debug_assert!(false, "checksum of unvalidated input: {err}");
+            b'0'
+        }
+    }
+}
+
+/// Test whether or not a byte is allowed at the given position of a _Payload_: an uppercase ASCII
+/// letter in the _Prefix_, or an uppercase ASCII alphanumeric in the _Basic Code_.
+pub(crate) fn is_payload_char(index: usize, b: u8) -> bool {
+    if index < 2 {
+        b.is_ascii_uppercase()
+    } else {
+        b.is_ascii_digit() || b.is_ascii_uppercase()
+    }
+}
+
+/// Test whether or not every character of the passed _Payload_ bytes is allowed at its position,
+/// reporting the first one that is not. The caller is responsible for ensuring there are at most
+/// eleven bytes.
+pub(crate) fn validate_payload_characters(payload: &[u8]) -> Result<(), ParseError> {
+    match payload
+        .iter()
+        .enumerate()
+        .find(|(index, b)| !is_payload_char(*index, **b))
+    {
+        Some((index, &byte)) => Err(ParseError::invalid_character(index, byte)),
+        None => Ok(()),
+    }
+}
+
+/// Test whether or not the passed bytes are a valid _Prefix_, returning the offset and value of
+/// the first invalid byte as the error if not. The caller is responsible for ensuring there are
+/// exactly two bytes.
+pub(crate) fn validate_prefix_format(prefix: &[u8]) -> Result<&[u8], (u8, u8)> {
+    match prefix.iter().position(|b| !b.is_ascii_uppercase()) {
+        Some(index) => Err((index as u8, prefix[index])),
+        None => Ok(prefix),
+    }
+}
+
+/// Test whether or not the passed bytes are a valid _Basic Code_, returning the offset and value
+/// of the first invalid byte as the error if not. The caller is responsible for ensuring there are
+/// exactly nine bytes.
+pub(crate) fn validate_basic_code_format(basic_code: &[u8]) -> Result<&[u8], (u8, u8)> {
+    match basic_code
+        .iter()
+        .position(|b| !(b.is_ascii_digit() || b.is_ascii_uppercase()))
+    {
+        Some(index) => Err((index as u8, basic_code[index])),
+        None => Ok(basic_code),
+    }
+}
+
+pub(crate) fn validate_check_digit_value(
+    payload: &[u8],
+    check_digit: u8,
+) -> Result<u8, ParseError> {
+    if !check_digit.is_ascii_digit() {
+        Err(ParseError::InvalidCheckDigit { was: check_digit })
+    } else {
+        let computed_check_digit = compute_check_digit(payload);
+        if check_digit != computed_check_digit {
+            Err(ParseError::IncorrectCheckDigit {
+                was: CheckDigit::from_ascii_valid(check_digit),
+                expected: CheckDigit::from_ascii_valid(computed_check_digit),
+            })
+        } else {
+            Ok(check_digit)
+        }
+    }
+}
+
+/// Parse a string to a valid ISIN or an error message, requiring the string to already be only
+/// uppercase alphanumerics with no leading or trailing whitespace in addition to being the
+/// right length and format.
+pub fn parse(value: &str) -> Result<ISIN, ParseError> {
+    // We make the preliminary assumption that the string is pure ASCII, so we work with the
+    // underlying bytes. If there is Unicode in the string, the bytes will be outside the
+    // allowed range and format validations will fail.
+
+    let bb: [u8; 12] = match value.as_bytes().try_into() {
+        Ok(bb) => bb,
+        Err(_) => {
+            return Err(ParseError::InvalidValueStringLength {
+                was: crate::error::error_len(value.len()),
+            })
+        }
+    };
+
+    validate_fields(&bb)?;
+
+    Ok(ISIN(bb))
+}
+
+/// Parse a string to a valid ISIN or an error, allowing the string to contain leading
+/// or trailing whitespace and/or lowercase letters as long as it is otherwise the right length
+/// and format.
+///
+/// See `ParseOptions` for finer control over which of these are allowed.
+pub fn parse_loose(value: &str) -> Result<ISIN, ParseError> {
+    // In the common case of a valid input, check it as it is and convert it to uppercase only
+    // once it is known to be valid. Otherwise, work out the error the slower way.
+    if let Ok(bb) = <[u8; 12]>::try_from(value.trim().as_bytes()) {
+        if checksum_payload_ignore_case(&bb[0..11]).is_some_and(|sum| bb[11] == b'0' + sum) {
+            return Ok(ISIN(bb.map(|b| b.to_ascii_uppercase())));
+        }
+    }
+    ParseOptions::loose().parse(value)
+}
+
+/// Parse a string to a valid ISIN or an error, first mapping Unicode compatibility forms such as
+/// full-width letters and digits (`ＵＳ０３７８３３１００５`) and non-breaking or ideographic
+/// spaces to their ASCII equivalents (Unicode normalization form NFKC). Otherwise the
+/// requirements are the same as `parse_loose()`, and errors refer to the normalized string.
+#[cfg(feature = "unicode")]
+pub fn parse_lenient_unicode(value: &str) -> Result<ISIN, ParseError> {
+    use unicode_normalization::UnicodeNormalization;
+
+    let normalized: String = value.nfkc().collect();
+    parse_loose(&normalized)
+}
+
+/// Parse a string to a valid ISIN or an error, first removing any of the characters in
+/// `separators` wherever they appear, so that for example `US-037833100-5` is accepted with
+/// `separators` of `"-"`. Otherwise the requirements are the same as `parse()`.
+///
+/// Invalid characters are reported at their position in `value` as given, before the separators
+/// are removed.
+pub fn parse_with_separators(value: &str, separators: &str) -> Result<ISIN, ParseError> {
+    ParseOptions::new().parse_with(Some(separators), value)
+}
+
+/// Parse a string to a valid ISIN or an error, with the same requirements as `parse()` except
+/// that an incorrect _Check Digit_ is replaced with the correct one. The returned flag is true if
+/// the _Check Digit_ was replaced.
+///
+/// A _Check Digit_ that is not a decimal digit at all is still an error, as is any problem with
+/// the _Prefix_ or _Basic Code_.
+pub fn parse_fixing_check_digit(value: &str) -> Result<(ISIN, bool), ParseError> {
+    let isin = ParseOptions::new()
+        .check_digit(CheckDigitPolicy::Fix)
+        .parse(value)?;
+    // The input was exactly the twelve ASCII bytes of the ISIN apart from the Check Digit
+    let fixed = isin.0[11] != value.as_bytes()[11];
+    Ok((isin, fixed))
+}
+
+/// Build an ISIN from a _Payload_ (an already-concatenated _Prefix_ and _Basic Code_). The
+/// _Check Digit_ is automatically computed.
+pub fn build_from_payload(payload: &str) -> Result<ISIN, BuildError> {
+    Payload::parse(payload).map(ISIN::from_payload)
+}
+
+/// Build an ISIN from its parts: an _Prefix_ and an _Basic Code_. The _Check Digit_ is
+/// automatically computed.
+pub fn build_from_parts(prefix: &str, basic_code: &str) -> Result<ISIN, BuildError> {
+    if prefix.len() != 2 {
+        return Err(BuildError::InvalidPrefixStringLength {
+            was: crate::error::error_len(prefix.len()),
+        });
+    }
+    let prefix: &[u8] = &prefix.as_bytes()[0..2];
+    validate_prefix_format(prefix)
+        .map_err(|(index, byte)| BuildError::InvalidPrefix { index, byte })?;
+
+    if basic_code.len() != 9 {
+        return Err(BuildError::InvalidBasicCodeStringLength {
+            was: crate::error::error_len(basic_code.len()),
+        });
+    }
+    let basic_code: &[u8] = &basic_code.as_bytes()[0..9];
+    validate_basic_code_format(basic_code)
+        .map_err(|(index, byte)| BuildError::InvalidBasicCode { index, byte })?;
+
+    let mut bb = [0u8; 12];
+
+    bb[0..2].copy_from_slice(prefix);
+    bb[2..11].copy_from_slice(basic_code);
+    bb[11] = compute_check_digit(&bb[0..11]);
+
+    Ok(ISIN(bb))
+}
+
+/// Build an ISIN from a _Payload_ given as a fixed-size byte array. The _Check Digit_ is
+/// automatically computed.
+pub fn build_from_payload_bytes(payload: &[u8; 11]) -> Result<ISIN, BuildError> {
+    validate_payload_format(payload)?;
+
+    let mut bb = [0u8; 12];
+
+    bb[0..11].copy_from_slice(payload);
+    bb[11] = compute_check_digit(payload);
+
+    Ok(ISIN(bb))
+}
+
+/// Build an ISIN from its parts given as fixed-size byte arrays: a _Prefix_ and a _Basic Code_.
+/// The _Check Digit_ is automatically computed.
+pub fn build_from_parts_bytes(prefix: &[u8; 2], basic_code: &[u8; 9]) -> Result<ISIN, BuildError> {
+    validate_prefix_format(prefix)
+        .map_err(|(index, byte)| BuildError::InvalidPrefix { index, byte })?;
+    validate_basic_code_format(basic_code)
+        .map_err(|(index, byte)| BuildError::InvalidBasicCode { index, byte })?;
+
+    let mut bb = [0u8; 12];
+
+    bb[0..2].copy_from_slice(prefix);
+    bb[2..11].copy_from_slice(basic_code);
+    bb[11] = compute_check_digit(&bb[0..11]);
+
+    Ok(ISIN(bb))
+}
+
+/// Test whether or not the passed string is in valid ISIN _Payload_ format.
+pub(crate) fn validate_payload_format(payload: &[u8]) -> Result<&[u8], BuildError> {
+    if payload.len() != 11 {
+        return Err(BuildError::InvalidPayloadArrayLength {
+            was: crate::error::error_len(payload.len()),
+        });
+    }
+
+    // We slice out the _Prefix_ and _Basic Code_ fields and validate their formats.
+
+    let prefix: &[u8] = &payload[0..2];
+    validate_prefix_format(prefix)
+        .map_err(|(index, byte)| BuildError::InvalidPrefix { index, byte })?;
+
+    let basic_code: &[u8] = &payload[2..11];
+    validate_basic_code_format(basic_code)
+        .map_err(|(index, byte)| BuildError::InvalidBasicCode { index, byte })?;
+
+    Ok(payload)
+}
+
+/// Test whether or not the passed string is a valid ISIN, with the same requirements as
+/// `parse()` but without producing an ISIN struct value. Use `parse()` instead if you need the
+/// validated value.
+pub fn validate(value: &str) -> Result<(), ParseError> {
+    parse(value).map(|_| ())
+}
+
+/// Validate the passed string as an ISIN to the given level of thoroughness, reporting the level
+/// whose checks failed along with the error.
+///
+/// ```
+/// use isin::{validate_at, ValidationLevel};
+///
+/// assert!(validate_at(ValidationLevel::FormatOnly, "US0378331006").is_ok());
+/// let failure = validate_at(ValidationLevel::Checksum, "US0378331006").unwrap_err();
+/// assert_eq!(failure.level(), ValidationLevel::Checksum);
+/// ```
+pub fn validate_at(level: ValidationLevel, value: &str) -> Result<(), ValidationFailure> {
+    let fail = |level, error: ParseError| ValidationFailure {
+        level,
+        error: error.into(),
+    };
+    validate_format(value).map_err(|err| fail(ValidationLevel::FormatOnly, err))?;
+    let b = value.as_bytes();
+    if level >= ValidationLevel::Checksum {
+        validate_fields(b).map_err(|err| fail(ValidationLevel::Checksum, err))?;
+    }
+    if level >= ValidationLevel::RegistryAllocated {
+        let prefix = [b[0], b[1]];
+        if !Prefix::from_bytes_valid(prefix).is_allocated() {
+            return Err(ValidationFailure {
+                level: ValidationLevel::RegistryAllocated,
+                error: Error::UnallocatedPrefix { was: prefix },
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Test whether or not the passed string is a valid ISIN like `validate()`, but report every
+/// problem found instead of stopping at the first. Each invalid character in the _Prefix_ and
+/// _Basic Code_ is reported, as is an invalid _Check Digit_ format; an incorrect _Check Digit_
+/// can only be detected if the _Prefix_ and _Basic Code_ are valid. If the length is wrong, that
+/// is the only problem reported, because the fields cannot be located.
+pub fn validate_all(value: &str) -> Result<(), Vec<ParseError>> {
+    if value.len() != 12 {
+        return Err(vec![ParseError::InvalidValueStringLength {
+            was: crate::error::error_len(value.len()),
+        }]);
+    }
+
+    let b = value.as_bytes();
+    let mut errors: Vec<ParseError> = b[0..11]
+        .iter()
+        .enumerate()
+        .filter(|(index, b)| !is_payload_char(*index, **b))
+        .map(|(index, &byte)| ParseError::invalid_character(index, byte))
+        .collect();
+
+    if errors.is_empty() {
+        if let Err(err) = validate_check_digit_value(&b[0..11], b[11]) {
+            errors.push(err);
+        }
+    } else if !b[11].is_ascii_digit() {
+        errors.push(ParseError::InvalidCheckDigit { was: b[11] });
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Return true if the passed string is a valid ISIN, with the same requirements as `parse()`.
+/// This stops at the first problem without constructing an error, for use as a filter predicate
+/// when the reason a value is invalid does not matter.
+pub fn is_valid(value: &str) -> bool {
+    let b = value.as_bytes();
+    b.len() == 12 && checksum_payload_fused(&b[0..11]).is_some_and(|sum| b[11] == b'0' + sum)
+}
+
+/// Test whether or not the passed string is in valid ISIN format: the right length, with a
+/// _Prefix_ of two uppercase letters, a _Basic Code_ of nine uppercase alphanumerics and a
+/// _Check Digit_ that is a decimal digit. Unlike `validate()`, the _Check Digit_ value is not
+/// computed, so this is a cheap pre-filter.
+///
+/// A string passing this but failing `validate()` is structurally an ISIN with an incorrect
+/// _Check Digit_.
+pub fn validate_format(value: &str) -> Result<(), ParseError> {
+    if value.len() != 12 {
+        return Err(ParseError::InvalidValueStringLength {
+            was: crate::error::error_len(value.len()),
+        });
+    }
+
+    let b = value.as_bytes();
+
+    validate_payload_characters(&b[0..11])?;
+    if !b[11].is_ascii_digit() {
+        return Err(ParseError::InvalidCheckDigit { was: b[11] });
+    }
+
+    Ok(())
+}
+
+/// Parse a byte slice to a valid ISIN or an error, with the same requirements as `parse()`. No
+/// UTF-8 validation is needed because any non-ASCII byte fails format validation anyway.
+pub fn parse_bytes(value: &[u8]) -> Result<ISIN, ParseError> {
+    let bb: [u8; 12] = match value.try_into() {
+        Ok(bb) => bb,
+        Err(_) => {
+            return Err(ParseError::InvalidValueArrayLength {
+                was: crate::error::error_len(value.len()),
+            })
+        }
+    };
+
+    validate_fields(&bb)?;
+
+    Ok(ISIN(bb))
+}
+
+/// Test whether or not the passed byte slice is a valid ISIN, with the same requirements as
+/// `parse_bytes()` but without producing an ISIN struct value.
+pub fn validate_bytes(value: &[u8]) -> Result<(), ParseError> {
+    parse_bytes(value).map(|_| ())
+}
+
+/// Test whether or not each of a contiguous slab of 12-byte records is a valid ISIN, with the same
+/// requirements as `validate_bytes()`. The checksums are computed for the whole slab at once by
+/// `checksum::checksum_slab()`, and only the invalid records are examined further to find their
+/// errors.
+///
+/// ```
+/// let records = [*b"US0378331005", *b"US0378331006"];
+/// let results = isin::validate_slab(&records);
+/// assert!(results[0].is_ok());
+/// assert_eq!(results[1].unwrap_err().kind(), isin::ErrorKind::IncorrectCheckDigit);
+/// ```
+pub fn validate_slab(records: &[[u8; 12]]) -> Vec<Result<(), Error>> {
+    checksum::checksum_slab(records)
+        .into_iter()
+        .zip(records)
+        .map(|(sum, record)| {
+            if sum != checksum::INVALID_PAYLOAD && record[11] == b'0' + sum {
+                Ok(())
+            } else {
+                validate_bytes(record).map_err(Error::from)
+            }
+        })
+        .collect()
+}
+
+/// Parse each of a batch of strings as an ISIN, with the same requirements as `parse()`.
+///
+/// This gives the same results as calling `parse()` on each string, but the checksums of groups of
+/// inputs are computed together as in `validate_slab()`, so it is faster when the values are
+/// already in memory and most of them are valid.
+///
+/// ```
+/// let results = isin::validate_batch(&["US0378331005", "US0378331006"]);
+/// assert_eq!(results[0].unwrap().to_string(), "US0378331005");
+/// assert_eq!(results[1].unwrap_err().kind(), isin::ErrorKind::IncorrectCheckDigit);
+/// ```
+pub fn validate_batch<S: AsRef<str>>(values: &[S]) -> Vec<Result<ISIN, Error>> {
+    let mut results = Vec::with_capacity(values.len());
+    validate_batch_with(values, |_, result| results.push(result));
+    results
+}
+
+/// Parse each of a batch of strings as `validate_batch()` does, writing the results to the
+/// corresponding elements of `results` instead of allocating. If the lengths differ, only as many
+/// values as there are elements of `results` are parsed. Returns the number of results written.
+///
+/// ```
+/// let mut results = [isin::parse("").map_err(isin::Error::from); 2];
+/// let written = isin::validate_batch_into(&["US0378331005", "US0378331006"], &mut results);
+/// assert_eq!(written, 2);
+/// assert!(results[0].is_ok() && results[1].is_err());
+/// ```
+pub fn validate_batch_into<S: AsRef<str>>(
+    values: &[S],
+    results: &mut [Result<ISIN, Error>],
+) -> usize {
+    let n = values.len().min(results.len());
+    validate_batch_with(&values[..n], |i, result| results[i] = result);
+    n
+}
+
+/// Parse each of `values` for `validate_batch()`, passing each index and result to `emit` in order.
+fn validate_batch_with<S: AsRef<str>>(
+    values: &[S],
+    mut emit: impl FnMut(usize, Result<ISIN, Error>),
+) {
+    for (g, group) in values.chunks(checksum::SLAB_LANES).enumerate() {
+        // Inputs of the wrong length stay as all zeros, and are rejected by the re-parse below
+        let mut lanes = [[b'0'; 12]; checksum::SLAB_LANES];
+        for (lane, value) in lanes.iter_mut().zip(group) {
+            if let Ok(bb) = value.as_ref().as_bytes().try_into() {
+                *lane = bb;
+            }
+        }
+        let sums = checksum::checksum_lanes(&lanes);
+
+        for (i, value) in group.iter().enumerate() {
+            let value = value.as_ref();
+            let sum = sums[i];
+            let result = if value.len() == 12
+                && sum != checksum::INVALID_PAYLOAD
+                && lanes[i][11] == b'0' + sum
+            {
+                Ok(ISIN(lanes[i]))
+            } else {
+                parse(value).map_err(Error::from)
+            };
+            emit(g * checksum::SLAB_LANES + i, result);
+        }
+    }
+}
+
+/// Validate the formats of the _Prefix_, _Basic Code_ and _Check Digit_, as well as the value of
+/// the _Check Digit_. The caller is responsible for ensuring there are exactly twelve bytes.
+pub(crate) fn validate_fields(b: &[u8]) -> Result<(), ParseError> {
+    // In the common case of a well-formed input, the character classes are checked and the
+    // checksum accumulated together in a single pass. Only if that finds a problem do we go on
+    // to work out which field is at fault.
+
+    if let Some(sum) = checksum_payload_fused(&b[0..11]) {
+        let check_digit = b[11];
+        let computed_check_digit = b'0' + sum;
+        if check_digit == computed_check_digit {
+            return Ok(());
+        }
+        if check_digit.is_ascii_digit() {
+            return Err(ParseError::IncorrectCheckDigit {
+                was: CheckDigit::from_ascii_valid(check_digit),
+                expected: CheckDigit::from_ascii_valid(computed_check_digit),
+            });
+        }
+        return Err(ParseError::InvalidCheckDigit { was: check_digit });
+    }
+
+    // The fused pass found a bad character, so we go back to find the first one.
+
+    let payload: &[u8] = &b[0..11];
+    validate_payload_characters(payload)?;
+    validate_check_digit_value(payload, b[11])?;
+
+    Ok(())
+}
+
+/// The number of distinct _Basic Code_ values (nine base-36 characters).
+pub(crate) const BASIC_CODE_COUNT: u64 = 36u64.pow(9);
+
+/// The number of distinct _Payload_ values (two base-26 letters followed by a _Basic Code_).
+pub(crate) const PAYLOAD_COUNT: u64 = 26 * 26 * BASIC_CODE_COUNT;
+
+/// The value of an uppercase ASCII alphanumeric character in base 36.
+pub(crate) fn alphanumeric_value(b: u8) -> u64 {
+    if b.is_ascii_digit() {
+        (b - b'0') as u64
+    } else {
+        (b - b'A') as u64 + 10
+    }
+}
+
+/// The uppercase ASCII alphanumeric character for a value in base 36.
+pub(crate) fn alphanumeric_char(v: u64) -> u8 {
+    if v < 10 {
+        b'0' + v as u8
+    } else {
+        b'A' + (v - 10) as u8
+    }
+}
+
+#[doc = include_str!("../README.md")]
+#[cfg(doctest)]
+pub struct ReadmeDoctests;
+
+/// An ISIN in confirmed valid format.
+///
+/// You cannot construct an ISIN value manually. This does not compile:
+///
+/// ```compile_fail
+/// use isin;
+/// let cannot_construct = isin::ISIN([0_u8; 12]);
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
+#[repr(transparent)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct ISIN(pub(crate) [u8; 12]);
+
+/// Hashes the same as the `str` form of the ISIN, so that borrowed string forms of an ISIN can be
+/// used to look up keys in hashed collections. The output depends only on the 12 bytes of the
+/// ISIN. See the [`hash`] module for a faster `Hasher` for ISIN keys.
+impl Hash for ISIN {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ascii_str(&self.0).hash(state)
+    }
+}
+
+impl AsRef<str> for ISIN {
+    fn as_ref(&self) -> &str {
+        ascii_str(&self.0[..])
+    }
+}
+
+/// Allows hashed and ordered collections keyed by `ISIN` to be queried with a plain `&str`,
+/// without parsing the query first. `Eq`, `Ord` and `Hash` all agree with those of `str`.
+impl Borrow<str> for ISIN {
+    fn borrow(&self) -> &str {
+        ascii_str(&self.0)
+    }
+}
+
+impl Deref for ISIN {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        ascii_str(&self.0)
+    }
+}
+
+impl PartialEq<str> for ISIN {
+    fn eq(&self, other: &str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<&str> for ISIN {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<String> for ISIN {
+    fn eq(&self, other: &String) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<ISIN> for str {
+    fn eq(&self, other: &ISIN) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<ISIN> for &str {
+    fn eq(&self, other: &ISIN) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<ISIN> for String {
+    fn eq(&self, other: &ISIN) -> bool {
+        other == self
+    }
+}
+
+/// Honors the width, fill and alignment flags of the formatter, so ISINs line up in fixed-width
+/// output such as `format!("{:>16}", isin)`.
+///
+/// The alternate form `{:#}` separates the parts with spaces, as in `US 037833100 5`.
+impl fmt::Display for ISIN {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            let mut bb = [b' '; 14];
+            bb[0..2].copy_from_slice(&self.0[0..2]);
+            bb[3..12].copy_from_slice(&self.0[2..11]);
+            bb[13] = self.0[11];
+            f.pad(ascii_str(&bb))
+        } else {
+            f.pad(ascii_str(self.as_bytes()))
+        }
+    }
+}
+
+impl fmt::Debug for ISIN {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let temp = ascii_str(self.as_bytes());
+        write!(f, "ISIN({temp})")
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl From<ISIN> for heapless::String<12> {
+    fn from(isin: ISIN) -> Self {
+        match heapless::String::try_from(isin.as_ref()) {
+            Ok(s) => s,
+            Err(_) => unreachable!("an ISIN is exactly 12 bytes"),
+        }
+    }
+}
+
+/// Requires the same strict format as `parse()`.
+#[cfg(feature = "heapless")]
+impl TryFrom<heapless::String<12>> for ISIN {
+    type Error = ParseError;
+
+    fn try_from(value: heapless::String<12>) -> Result<Self, Self::Error> {
+        parse(&value)
+    }
+}
+
+#[cfg(feature = "compact_str")]
+impl From<ISIN> for compact_str::CompactString {
+    fn from(isin: ISIN) -> Self {
+        compact_str::CompactString::new(AsRef::<str>::as_ref(&isin))
+    }
+}
+
+/// Requires the same strict format as `parse()`.
+#[cfg(feature = "compact_str")]
+impl TryFrom<compact_str::CompactString> for ISIN {
+    type Error = ParseError;
+
+    fn try_from(value: compact_str::CompactString) -> Result<Self, Self::Error> {
+        parse(&value)
+    }
+}
+
+#[cfg(feature = "smol_str")]
+impl From<ISIN> for smol_str::SmolStr {
+    fn from(isin: ISIN) -> Self {
+        smol_str::SmolStr::new_inline(isin.as_ref())
+    }
+}
+
+/// Requires the same strict format as `parse()`.
+#[cfg(feature = "smol_str")]
+impl TryFrom<smol_str::SmolStr> for ISIN {
+    type Error = ParseError;
+
+    fn try_from(value: smol_str::SmolStr) -> Result<Self, Self::Error> {
+        parse(&value)
+    }
+}
+
+/// An OpenAPI string schema with the length and format of an ISIN and an example value. The
+/// pattern cannot check the _Check Digit_.
+#[cfg(feature = "utoipa")]
+impl utoipa::PartialSchema for ISIN {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        use utoipa::openapi::schema::{ObjectBuilder, Type};
+
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .description(Some(
+                "An International Securities Identification Number (ISO 6166)",
+            ))
+            .min_length(Some(12))
+            .max_length(Some(12))
+            .pattern(Some("^[A-Z]{2}[0-9A-Z]{9}[0-9]$"))
+            .examples(["US0378331005"])
+            .into()
+    }
+}
+
+#[cfg(feature = "utoipa")]
+impl utoipa::ToSchema for ISIN {
+    fn name() -> std::borrow::Cow<'static, str> {
+        std::borrow::Cow::Borrowed("ISIN")
+    }
+}
+
+/// Encodes the twelve bytes of the ISIN, with no length prefix.
+#[cfg(feature = "bincode")]
+impl bincode::Encode for ISIN {
+    fn encode<E: bincode::enc::Encoder>(
+        &self,
+        encoder: &mut E,
+    ) -> Result<(), bincode::error::EncodeError> {
+        self.0.encode(encoder)
+    }
+}
+
+/// Decodes twelve bytes, which must be a valid ISIN as for `parse_bytes()`.
+#[cfg(feature = "bincode")]
+impl<Context> bincode::Decode<Context> for ISIN {
+    fn decode<D: bincode::de::Decoder<Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, bincode::error::DecodeError> {
+        let bytes = <[u8; 12]>::decode(decoder)?;
+        parse_bytes(&bytes).map_err(|err| bincode::error::DecodeError::OtherString(err.to_string()))
+    }
+}
+
+#[cfg(feature = "bincode")]
+bincode::impl_borrow_decode!(ISIN);
+
+/// Generates valid ISINs only, with every _Payload_ possible and the correct _Check Digit_.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for ISIN {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let rank = u.int_in_range(0..=PAYLOAD_COUNT - 1)?;
+        ISIN::from_payload_rank(rank).ok_or(arbitrary::Error::IncorrectFormat)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <u64 as arbitrary::Arbitrary>::size_hint(depth)
+    }
+}
+
+/// Generates valid ISINs only, with every _Payload_ possible and the correct _Check Digit_.
+/// Shrinking keeps the _Prefix_ and shrinks the value of the _Basic Code_ towards zero, so the
+/// simplest ISIN with the _Prefix_ has a _Basic Code_ of all zeros.
+#[cfg(feature = "quickcheck")]
+impl quickcheck::Arbitrary for ISIN {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let rank = u64::arbitrary(g) % PAYLOAD_COUNT;
+        ISIN::from_payload_rank(rank).expect("rank in range")
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let rank = self.payload_rank();
+        let first = rank - rank % BASIC_CODE_COUNT;
+        Box::new(
+            (rank % BASIC_CODE_COUNT)
+                .shrink()
+                .map(move |code| ISIN::from_payload_rank(first + code).expect("rank in range")),
+        )
+    }
+}
+
+/// Generates valid ISINs only, uniformly from every possible _Payload_, with the correct _Check
+/// Digit_.
+#[cfg(feature = "rand")]
+impl rand::distributions::Distribution<ISIN> for rand::distributions::Standard {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> ISIN {
+        ISIN::from_payload_rank(rng.gen_range(0..PAYLOAD_COUNT)).expect("rank in range")
+    }
+}
+
+#[cfg(feature = "rand")]
+impl ISIN {
+    /// Generate a random valid ISIN using `rng`, uniformly from every possible _Payload_. This is
+    /// the same as `rng.gen::<ISIN>()`.
+    ///
+    /// ```
+    /// use rand::{Rng, SeedableRng};
+    ///
+    /// let mut rng = rand::rngs::SmallRng::seed_from_u64(42);
+    /// let isin = isin::ISIN::random_with(&mut rng);
+    /// assert_eq!(isin::parse(&isin.to_string()), Ok(isin));
+    ///
+    /// let isins: Vec<isin::ISIN> = (&mut rng)
+    ///     .sample_iter(rand::distributions::Standard)
+    ///     .take(3)
+    ///     .collect();
+    /// assert_eq!(isins.len(), 3);
+    /// ```
+    pub fn random_with<R: rand::Rng + ?Sized>(rng: &mut R) -> ISIN {
+        rng.gen()
+    }
+}
+
+impl FromStr for ISIN {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_loose(s)
+    }
+}
+
+/// Requires the same strict format as `parse()`. Use `FromStr` or `parse_loose()` to accept
+/// surrounding whitespace and lowercase letters.
+impl TryFrom<&str> for ISIN {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse(value)
+    }
+}
+
+/// Requires the same strict format as `parse()`.
+impl TryFrom<String> for ISIN {
+    type Error = ParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse(&value)
+    }
+}
+
+impl From<ISIN> for String {
+    fn from(isin: ISIN) -> Self {
+        ascii_str(&isin.0).to_owned()
+    }
+}
+
+impl TryFrom<[u8; 12]> for ISIN {
+    type Error = ParseError;
+
+    fn try_from(value: [u8; 12]) -> Result<Self, Self::Error> {
+        validate_fields(&value)?;
+        Ok(ISIN(value))
+    }
+}
+
+impl ISIN {
+    /// The smallest valid ISIN in the usual ordering, `AA0000000006`.
+    pub const MIN: ISIN = ISIN(*b"AA0000000006");
+
+    /// The largest valid ISIN in the usual ordering, `ZZZZZZZZZZZ6`.
+    pub const MAX: ISIN = ISIN(*b"ZZZZZZZZZZZ6");
+
+    /// A regular expression matching the format of an ISIN: a two-letter _Prefix_, a nine-character
+    /// alphanumeric _Basic Code_ and a decimal _Check Digit_. The pattern does not (and cannot)
+    /// verify the _Check Digit_ value, so use `parse()` or `validate()` for that.
+    pub const PATTERN: &'static str = "^[A-Z]{2}[0-9A-Z]{9}[0-9]$";
+
+    /// Build an ISIN from its already-validated parts. This is the typed counterpart of
+    /// `build_from_parts()`, and cannot fail. The _Check Digit_ is automatically computed.
+    pub fn from_parts(prefix: Prefix, basic_code: BasicCode) -> ISIN {
+        let mut bb = [0u8; 12];
+
+        bb[0..2].copy_from_slice(prefix.as_bytes());
+        bb[2..11].copy_from_slice(basic_code.as_bytes());
+        bb[11] = compute_check_digit(&bb[0..11]);
+
+        ISIN(bb)
+    }
+
+    /// Build an ISIN from an already-validated _Payload_. This is the typed counterpart of
+    /// `build_from_payload()`, and cannot fail. The _Check Digit_ is automatically computed.
+    pub fn from_payload(payload: Payload) -> ISIN {
+        let mut bb = [0u8; 12];
+
+        bb[0..11].copy_from_slice(payload.as_bytes());
+        bb[11] = compute_check_digit(payload.as_bytes());
+
+        ISIN(bb)
+    }
+
+    /// Return the ISIN as a fixed-size array of its twelve ASCII bytes.
+    pub const fn as_bytes(&self) -> &[u8; 12] {
+        &self.0
+    }
+
+    /// Return the ISIN as a borrowed `IsinStr`.
+    ///
+    /// This function is not available with the `forbid-unsafe` feature enabled.
+    #[cfg(not(feature = "forbid-unsafe"))]
+    pub fn as_isin_str(&self) -> &IsinStr {
+        IsinStr::from_str_unchecked(ascii_str(&self.0))
+    }
+
+    /// Consume the ISIN, returning its twelve ASCII bytes.
+    pub const fn into_inner(self) -> [u8; 12] {
+        self.0
+    }
+
+    /// Construct an ISIN from its twelve bytes without any validation at all, for values that
+    /// come from a trusted source such as a store that only ever holds already-validated ISINs.
+    ///
+    /// This function is not available with the `forbid-unsafe` feature enabled.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must be a valid ISIN. In particular, they must all be ASCII, because the `&str`
+    /// views returned by the accessors are created without UTF-8 validation.
+    #[cfg(not(feature = "forbid-unsafe"))]
+    pub unsafe fn from_bytes_unchecked(bytes: [u8; 12]) -> ISIN {
+        ISIN(bytes)
+    }
+
+    /// Construct an ISIN from a string that is already known to have a correct _Check Digit_,
+    /// such as one read back from a store that only ever holds already-validated ISINs.
+    ///
+    /// The length and the formats of all the parts are still checked, so the result is always
+    /// safe to use, but the _Check Digit_ value is not computed. If it is wrong, the result is a
+    /// well-formed ISIN with an incorrect _Check Digit_.
+    pub fn from_prevalidated_str(value: &str) -> Result<ISIN, ParseError> {
+        validate_format(value)?;
+
+        let mut bb = [0u8; 12];
+        bb.copy_from_slice(value.as_bytes());
+
+        Ok(ISIN(bb))
+    }
+
+    /// Return true if the string is in valid ISIN format, without checking the _Check Digit_
+    /// value. See `validate_format()`.
+    pub fn is_plausible(value: &str) -> bool {
+        validate_format(value).is_ok()
+    }
+
+    /// Return just the _Prefix_ portion of the ISIN.
+    pub const fn prefix(&self) -> &str {
+        ascii_substr(&self.0, 0, 2)
+    }
+
+    /// Return just the _Prefix_ portion of the ISIN as a `Prefix`.
+    pub fn prefix_typed(&self) -> Prefix {
+        Prefix::from_bytes_valid(self.prefix_bytes())
+    }
+
+    /// Return just the _Prefix_ portion of the ISIN as a fixed-size array of its two ASCII bytes.
+    pub const fn prefix_bytes(&self) -> [u8; 2] {
+        [self.0[0], self.0[1]]
+    }
+
+    /// Return just the _Basic Code_ portion of the ISIN.
+    pub const fn basic_code(&self) -> &str {
+        ascii_substr(&self.0, 2, 11)
+    }
+
+    /// Return just the _Basic Code_ portion of the ISIN as a `BasicCode`.
+    pub fn basic_code_typed(&self) -> BasicCode {
+        BasicCode::from_bytes_valid(self.basic_code_bytes())
+    }
+
+    /// Return just the _Basic Code_ portion of the ISIN as a fixed-size array of its nine ASCII
+    /// bytes.
+    pub fn basic_code_bytes(&self) -> [u8; 9] {
+        let mut bb = [0u8; 9];
+        bb.copy_from_slice(&self.0[2..11]);
+        bb
+    }
+
+    /// Return the underlying national number: the _Basic Code_ with its left zero-padding
+    /// removed. See `BasicCode::nsin()`. For _Prefixes_ whose national numbers have a fixed width,
+    /// only the padding is removed, keeping any leading zeros of the number itself: the whole
+    /// CUSIP for `US` and `CA` (`037833100` for `US0378331005`), and the seven-character SEDOL for
+    /// `GB` and `IE`.
+    pub fn nsin(&self) -> &str {
+        let basic_code = &self.0[2..11];
+        let start = match self.prefix_typed().national_number_width() {
+            Some(width) => 9 - width,
+            None => basic_code.iter().position(|b| *b != b'0').unwrap_or(8),
+        };
+        ascii_str(&basic_code[start..])
+    }
+
+    /// Return the value of the _Basic Code_ interpreted as a base-36 number, with digits
+    /// `0`&ndash;`9` followed by letters `A`&ndash;`Z`. See `BasicCode::from_value()` for the
+    /// inverse.
+    pub fn basic_code_value(&self) -> u64 {
+        self.basic_code_typed().value()
+    }
+
+    /// Return the _Payload_ &mdash; everything except the _Check Digit_.
+    pub const fn payload(&self) -> &str {
+        ascii_substr(&self.0, 0, 11)
+    }
+
+    /// Return the _Payload_ as a `Payload`.
+    pub fn payload_typed(&self) -> Payload {
+        let mut bb = [0u8; 11];
+        bb.copy_from_slice(&self.0[0..11]);
+        Payload::from_bytes_valid(bb)
+    }
+
+    /// Return just the _Check Digit_ portion of the ISIN.
+    pub const fn check_digit(&self) -> char {
+        self.0[11] as char
+    }
+
+    /// Return the numeric value of the _Check Digit_, 0 through 9.
+    pub const fn check_digit_value(&self) -> u8 {
+        self.0[11] - b'0'
+    }
+
+    /// Internal function mapping the _Payload_ to a dense integer in `0..PAYLOAD_COUNT` that
+    /// preserves ordering. The _Check Digit_ is implied by the _Payload_, so it is not encoded.
+    pub(crate) fn payload_rank(&self) -> u64 {
+        let prefix = (self.0[0] - b'A') as u64 * 26 + (self.0[1] - b'A') as u64;
+        self.0[2..11]
+            .iter()
+            .fold(prefix, |acc, b| acc * 36 + alphanumeric_value(*b))
+    }
+
+    /// Internal function inverting `payload_rank()`, computing the _Check Digit_ on the way.
+    pub(crate) fn from_payload_rank(rank: u64) -> Option<ISIN> {
+        if rank >= PAYLOAD_COUNT {
+            return None;
+        }
+
+        let mut bb = [0u8; 12];
+        let mut rest = rank;
+        for b in bb[2..11].iter_mut().rev() {
+            *b = alphanumeric_char(rest % 36);
+            rest /= 36;
+        }
+        bb[1] = b'A' + (rest % 26) as u8;
+        bb[0] = b'A' + (rest / 26) as u8;
+        bb[11] = compute_check_digit(&bb[0..11]);
+
+        Some(ISIN(bb))
+    }
+
+    /// Return just the _Check Digit_ portion of the ISIN as a `CheckDigit`.
+    pub fn check_digit_typed(&self) -> CheckDigit {
+        CheckDigit::from_ascii_valid(self.0[11])
+    }
+
+    /// Return a new ISIN with the _Prefix_ replaced and the _Check Digit_ recomputed.
+    pub fn with_prefix(&self, prefix: &str) -> Result<ISIN, BuildError> {
+        build_from_parts(prefix, self.basic_code())
+    }
+
+    /// Return a new ISIN with the _Basic Code_ replaced and the _Check Digit_ recomputed.
+    pub fn with_basic_code(&self, basic_code: &str) -> Result<ISIN, BuildError> {
+        build_from_parts(self.prefix(), basic_code)
+    }
+
+    /// Format the ISIN with its _Prefix_, _Basic Code_ and _Check Digit_ separated by
+    /// `separator`. With a single space as the separator this is the same as `format!("{:#}", isin)`.
+    pub fn fmt_parts(&self, separator: &str) -> String {
+        format!(
+            "{}{separator}{}{separator}{}",
+            self.prefix(),
+            self.basic_code(),
+            self.check_digit()
+        )
+    }
+
+    /// Write the twelve ASCII bytes of the ISIN to `w`, without going through `Display` or
+    /// allocating a `String`.
+    pub fn write_to<W: io::Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(&self.0)
+    }
+
+    /// Copy the twelve ASCII bytes of the ISIN into a caller-supplied buffer.
+    pub fn copy_to(&self, buf: &mut [u8; 12]) {
+        *buf = self.0;
+    }
+
+    /// Return an adapter that displays the ISIN in lowercase without allocating.
+    pub fn display_lowercase(&self) -> IsinLowercase<'_> {
+        IsinLowercase::new(self)
+    }
+
+    /// Return true if the two ISINs have the same _Payload_, whatever their _Check Digits_.
+    ///
+    /// ISINs made by `parse()` and the builders always have the correct _Check Digit_, so for them
+    /// this is the same as `==`. It differs for ISINs made by `from_prevalidated_str()` from data
+    /// with a corrupted _Check Digit_, letting such records be grouped with their correct
+    /// counterparts.
+    pub fn eq_ignore_check_digit(&self, other: &ISIN) -> bool {
+        self.0[0..11] == other.0[0..11]
+    }
+
+    /// Return true if the string is this ISIN, ignoring ASCII case, for matching against data
+    /// sources that store identifiers in lowercase. See also [`CaselessKey`] for hash joins.
+    pub fn matches_ignore_case(&self, other: &str) -> bool {
+        ascii_str(&self.0).eq_ignore_ascii_case(other)
+    }
+
+    /// Compare two ISINs by _Payload_ only, ignoring their _Check Digits_. This is consistent with
+    /// `eq_ignore_check_digit()`, for sorting before deduplicating.
+    pub fn cmp_by_payload(&self, other: &ISIN) -> Ordering {
+        self.0[0..11].cmp(&other.0[0..11])
+    }
+
+    /// Return a compact key ordering ISINs country-major: by _Prefix_, then by the numeric
+    /// (base-36) value of the _Basic Code_. The _Basic Code_ is zero-padded to a fixed width, so
+    /// this agrees with the `Ord` implementation, but integer keys are cheaper to compare and
+    /// partition than strings.
+    pub fn sort_key(&self) -> u64 {
+        self.payload_rank()
+    }
+
+    /// Compare two ISINs by _Prefix_ only. Use with a stable sort to group by country while
+    /// keeping the existing order within each country.
+    pub fn cmp_by_prefix(&self, other: &ISIN) -> Ordering {
+        self.prefix_bytes().cmp(&other.prefix_bytes())
+    }
+
+    /// Compare two ISINs by the numeric value of the _Basic Code_ first, then by _Prefix_, so the
+    /// same national number issued in several countries sorts together.
+    pub fn cmp_by_basic_code(&self, other: &ISIN) -> Ordering {
+        self.basic_code_value()
+            .cmp(&other.basic_code_value())
+            .then_with(|| self.cmp_by_prefix(other))
+    }
+
+    /// Compare this ISIN to another one field by field, reporting which parts differ and at
+    /// which character positions.
+    pub fn diff(&self, other: &ISIN) -> IsinDiff {
+        IsinDiff::between(&self.0, &other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::proptest::prelude::*;
+
+    #[test]
+    fn parse_isin_for_apple_strict() {
+        match parse("US0378331005") {
+            Ok(isin) => {
+                assert_eq!(isin.to_string(), "US0378331005");
+                assert_eq!(isin.prefix(), "US");
+                assert_eq!(isin.basic_code(), "037833100");
+                assert_eq!(isin.check_digit(), '5');
+            }
+            Err(err) => panic!("Did not expect parsing to fail: {}", err),
+        }
+    }
+
+    #[test]
+    fn build_isin_for_apple_from_payload() {
+        match build_from_payload("US037833100") {
+            Ok(isin) => {
+                assert_eq!(isin.to_string(), "US0378331005");
+                assert_eq!(isin.prefix(), "US");
+                assert_eq!(isin.basic_code(), "037833100");
+                assert_eq!(isin.check_digit(), '5');
+            }
+            Err(err) => panic!("Did not expect building to fail: {}", err),
+        }
+    }
+
+    #[test]
+    fn build_isin_for_apple_from_parts() {
+        match build_from_parts("US", "037833100") {
+            Ok(isin) => {
+                assert_eq!(isin.to_string(), "US0378331005");
+                assert_eq!(isin.prefix(), "US");
+                assert_eq!(isin.basic_code(), "037833100");
+                assert_eq!(isin.check_digit(), '5');
+            }
+            Err(err) => panic!("Did not expect building to fail: {}", err),
+        }
+    }
+
+    #[test]
+    fn build_isin_for_apple_from_payload_bytes() {
+        match build_from_payload_bytes(b"US037833100") {
+            Ok(isin) => {
+                assert_eq!(isin.to_string(), "US0378331005");
+                assert_eq!(isin.check_digit(), '5');
+            }
+            Err(err) => panic!("Did not expect building to fail: {}", err),
+        }
+    }
+
+    #[test]
+    fn build_isin_for_apple_from_parts_bytes() {
+        match build_from_parts_bytes(b"US", b"037833100") {
+            Ok(isin) => {
+                assert_eq!(isin.to_string(), "US0378331005");
+                assert_eq!(isin.check_digit(), '5');
+            }
+            Err(err) => panic!("Did not expect building to fail: {}", err),
+        }
+    }
+
+    #[test]
+    fn reject_lowercase_prefix_bytes() {
+        match build_from_parts_bytes(b"us", b"037833100") {
+            Err(BuildError::InvalidPrefix { index, byte }) => assert_eq!((index, byte), (0, b'u')),
+            other => panic!(
+                "Expected Err(InvalidPrefix {{ ... }}), but got: {:?}",
+                other
+            ),
+        }
+    }
+
+    #[test]
+    fn byte_array_round_trip() {
+        let isin = parse("US0378331005").unwrap();
+        assert_eq!(isin.as_bytes(), b"US0378331005");
+        assert_eq!(ISIN::try_from(isin.into_inner()), Ok(isin));
+    }
+
+    #[test]
+    fn reject_byte_array_with_incorrect_check_digit() {
+        assert_eq!(
+            ISIN::try_from(*b"US0378331006"),
+            Err(ParseError::IncorrectCheckDigit {
+                was: CheckDigit::from_value(6).unwrap(),
+                expected: CheckDigit::from_value(5).unwrap()
+            })
+        );
+    }
+
+    #[test]
+    fn parse_bytes_for_apple() {
+        match parse_bytes(b"US0378331005") {
+            Ok(isin) => {
+                assert_eq!(isin.to_string(), "US0378331005");
+                assert_eq!(isin.check_digit(), '5');
+            }
+            Err(err) => panic!("Did not expect parsing to fail: {}", err),
+        }
+    }
+
+    #[test]
+    fn matches_ignore_case() {
+        let isin = parse("US0378331005").unwrap();
+        assert!(isin.matches_ignore_case("US0378331005"));
+        assert!(isin.matches_ignore_case("us0378331005"));
+        assert!(!isin.matches_ignore_case("us0378331006"));
+        assert!(!isin.matches_ignore_case(" us0378331005"));
+    }
+
+    #[test]
+    fn validate_reports_only_errors() {
+        assert_eq!(validate("US0378331005"), Ok(()));
+        assert_eq!(validate_bytes(b"US0378331005"), Ok(()));
+        assert_eq!(
+            validate("US037833100"),
+            Err(ParseError::InvalidValueStringLength { was: 11 })
+        );
+        assert_eq!(validate("US0378331006"), parse("US0378331006").map(|_| ()));
+    }
+
+    #[test]
+    fn validate_slab_matches_validate_bytes() {
+        let records = [
+            *b"US0378331005",
+            *b"US0378331006",
+            *b"US037833100X",
+            *b"U50378331005",
+            *b"US5949181045",
+            *b"us0378331005",
+        ];
+        let expected: Vec<_> = records
+            .iter()
+            .map(|r| validate_bytes(r).map_err(Error::from))
+            .collect();
+        assert_eq!(validate_slab(&records), expected);
+        assert!(validate_slab(&[]).is_empty());
+    }
+
+    #[test]
+    fn validate_batch_matches_parse() {
+        let mut values = vec![
+            "US0378331005",
+            "US0378331006",
+            "US037833100",
+            "us0378331005",
+            "",
+        ];
+        values.extend(["US5949181045"; 20]);
+        values.push("US037833100\u{e9}");
+        let expected: Vec<_> = values
+            .iter()
+            .map(|v| parse(v).map_err(Error::from))
+            .collect();
+        assert_eq!(validate_batch(&values), expected);
+
+        let mut results = vec![Err(Error::InvalidValueStringLength { was: 0 }); 3];
+        assert_eq!(validate_batch_into(&values, &mut results), 3);
+        assert_eq!(results, expected[..3]);
+        assert_eq!(validate_batch_into(&values[..1], &mut results), 1);
+        assert!(validate_batch::<&str>(&[]).is_empty());
+    }
+
+    #[test]
+    fn reject_bytes_of_wrong_length() {
+        assert_eq!(
+            validate_bytes(b"US037833100"),
+            Err(ParseError::InvalidValueArrayLength { was: 11 })
+        );
+    }
+
+    #[test]
+    fn reject_non_utf8_bytes() {
+        assert_eq!(
+            parse_bytes(b"US\xff378331005"),
+            Err(ParseError::InvalidCharacter {
+                index: 2,
+                byte: 0xff,
+                in_prefix: false
+            })
+        );
+    }
+
+    #[cfg(not(feature = "forbid-unsafe"))]
+    #[test]
+    fn from_bytes_unchecked_for_apple() {
+        let isin = unsafe { ISIN::from_bytes_unchecked(*b"US0378331005") };
+        assert_eq!(isin, parse("US0378331005").unwrap());
+    }
+
+    #[test]
+    fn from_prevalidated_str_skips_check_digit_value() {
+        assert_eq!(
+            ISIN::from_prevalidated_str("US0378331005"),
+            parse("US0378331005")
+        );
+        let unchecked = ISIN::from_prevalidated_str("US0378331006").unwrap();
+        assert_eq!(unchecked.check_digit(), '6');
+        assert_eq!(
+            ISIN::from_prevalidated_str("US037833100X"),
+            Err(ParseError::InvalidCheckDigit { was: b'X' })
+        );
+        assert_eq!(
+            ISIN::from_prevalidated_str("us0378331005"),
+            Err(ParseError::InvalidCharacter {
+                index: 0,
+                byte: b'u',
+                in_prefix: true
+            })
+        );
+    }
+
+    #[test]
+    fn replace_parts_of_apple() {
+        let isin = parse("US0378331005").unwrap();
+        assert_eq!(
+            isin.with_prefix("CA").unwrap(),
+            build_from_parts("CA", "037833100").unwrap()
+        );
+        assert_eq!(
+            isin.with_basic_code("037833101").unwrap(),
+            build_from_parts("US", "037833101").unwrap()
+        );
+        assert_eq!(
+            isin.with_prefix("C"),
+            Err(BuildError::InvalidPrefixStringLength { was: 1 })
+        );
+        assert_eq!(
+            isin.with_basic_code("03783310!"),
+            Err(BuildError::InvalidBasicCode {
+                index: 8,
+                byte: b'!'
+            })
+        );
+    }
+
+    #[test]
+    fn lookup_by_str_key() {
+        use std::collections::{BTreeMap, HashMap};
+
+        let isin = parse("US0378331005").unwrap();
+        let hashed: HashMap<ISIN, u32> = [(isin, 1)].into_iter().collect();
+        assert_eq!(hashed.get("US0378331005"), Some(&1));
+        assert_eq!(hashed.get("US0378331006"), None);
+
+        let ordered: BTreeMap<ISIN, u32> = [(isin, 1)].into_iter().collect();
+        assert_eq!(ordered.get("US0378331005"), Some(&1));
+
+        assert_eq!(isin.len(), 12);
+        assert!(isin.starts_with("US"));
+    }
+
+    #[test]
+    fn standard_conversions() {
+        let isin = ISIN::try_from("US0378331005").unwrap();
+        assert_eq!(ISIN::try_from(String::from("US0378331005")), Ok(isin));
+        assert_eq!(String::from(isin), "US0378331005");
+        let s: String = isin.into();
+        assert_eq!(s, "US0378331005");
+        assert_eq!(
+            ISIN::try_from(" US0378331005"),
+            Err(ParseError::InvalidValueStringLength { was: 13 })
+        );
+    }
+
+    #[test]
+    fn display_honors_padding() {
+        let isin = parse("US0378331005").unwrap();
+        assert_eq!(format!("{isin:>16}"), "    US0378331005");
+        assert_eq!(format!("{isin:<14}|"), "US0378331005  |");
+        assert_eq!(format!("{isin:*^16}"), "**US0378331005**");
+        assert_eq!(format!("{isin:8}"), "US0378331005");
+    }
+
+    #[test]
+    fn display_grouped_parts() {
+        let isin = parse("US0378331005").unwrap();
+        assert_eq!(format!("{isin:#}"), "US 037833100 5");
+        assert_eq!(format!("{isin:>#16}"), "  US 037833100 5");
+        assert_eq!(isin.fmt_parts(" "), format!("{isin:#}"));
+        assert_eq!(isin.fmt_parts("-"), "US-037833100-5");
+        assert_eq!(isin.fmt_parts(""), "US0378331005");
+    }
+
+    #[test]
+    fn write_without_allocating() {
+        let isin = parse("US0378331005").unwrap();
+
+        let mut message = Vec::with_capacity(32);
+        message.extend_from_slice(b"48=");
+        isin.write_to(&mut message).unwrap();
+        message.push(b'|');
+        assert_eq!(message, b"48=US0378331005|");
+
+        let mut buf = [0u8; 12];
+        isin.copy_to(&mut buf);
+        assert_eq!(&buf, b"US0378331005");
+
+        let mut short = [0u8; 8];
+        assert!(isin.write_to(&mut short[..]).is_err());
+    }
+
+    #[test]
+    fn raw_part_accessors() {
+        let isin = parse("US0378331005").unwrap();
+        assert_eq!(isin.prefix_bytes(), *b"US");
+        assert_eq!(isin.basic_code_bytes(), *b"037833100");
+        assert_eq!(isin.check_digit_value(), 5);
+        assert_eq!(isin.check_digit_value(), isin.check_digit_typed().as_u8());
+    }
+
+    #[test]
+    fn min_and_max_are_valid_extremes() {
+        assert_eq!(parse(ISIN::MIN.as_ref()), Ok(ISIN::MIN));
+        assert_eq!(parse(ISIN::MAX.as_ref()), Ok(ISIN::MAX));
+        assert_eq!(ISIN::from_payload_rank(0), Some(ISIN::MIN));
+        assert_eq!(ISIN::from_payload_rank(PAYLOAD_COUNT - 1), Some(ISIN::MAX));
+        let apple = parse("US0378331005").unwrap();
+        assert!(ISIN::MIN < apple && apple < ISIN::MAX);
+    }
+
+    #[test]
+    fn const_accessors() {
+        const PREFIX: &str = ISIN::MIN.prefix();
+        const BASIC_CODE: &str = ISIN::MIN.basic_code();
+        const PAYLOAD: &str = ISIN::MAX.payload();
+        const CHECK_DIGIT: char = ISIN::MAX.check_digit();
+        assert_eq!(PREFIX, "AA");
+        assert_eq!(BASIC_CODE, "000000000");
+        assert_eq!(PAYLOAD, "ZZZZZZZZZZZ");
+        assert_eq!(CHECK_DIGIT, '6');
+    }
+
+    #[test]
+    fn compare_ignoring_check_digit() {
+        let good = parse("US0378331005").unwrap();
+        let corrupted = ISIN::from_prevalidated_str("US0378331006").unwrap();
+        let other = parse("US5949181045").unwrap();
+        assert_ne!(good, corrupted);
+        assert!(good.eq_ignore_check_digit(&corrupted));
+        assert!(!good.eq_ignore_check_digit(&other));
+        assert_eq!(good.cmp_by_payload(&corrupted), Ordering::Equal);
+        assert_eq!(good.cmp_by_payload(&other), Ordering::Less);
+
+        let mut records = vec![corrupted, other, good];
+        records.sort_by(ISIN::cmp_by_payload);
+        records.dedup_by(|a, b| a.eq_ignore_check_digit(b));
+        assert_eq!(records.len(), 2);
+    }
+
+    #[test]
+    fn domain_sort_keys() {
+        let de = parse("DE000A0GNPZ3").unwrap();
+        let us_apple = parse("US0378331005").unwrap();
+        let us_msft = parse("US5949181045").unwrap();
+        assert!(de.sort_key() < us_apple.sort_key());
+        assert!(us_apple.sort_key() < us_msft.sort_key());
+
+        let mut isins = vec![us_msft, de, us_apple];
+        isins.sort_by_key(ISIN::sort_key);
+        assert_eq!(isins, vec![de, us_apple, us_msft]);
+
+        let gb_apple = us_apple.with_prefix("GB").unwrap();
+        let mut isins = vec![us_msft, us_apple, de, gb_apple];
+        isins.sort_by(ISIN::cmp_by_basic_code);
+        assert_eq!(isins, vec![de, gb_apple, us_apple, us_msft]);
+
+        let mut by_country = vec![us_msft, de, us_apple];
+        by_country.sort_by(ISIN::cmp_by_prefix);
+        assert_eq!(by_country, vec![de, us_msft, us_apple]);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn heapless_string_round_trip() {
+        let isin = parse("US0378331005").unwrap();
+        let s = heapless::String::<12>::from(isin);
+        assert_eq!(s, "US0378331005");
+        assert_eq!(ISIN::try_from(s), Ok(isin));
+    }
+
+    #[cfg(feature = "compact_str")]
+    #[test]
+    fn compact_string_round_trip() {
+        let isin = parse("US0378331005").unwrap();
+        let s = compact_str::CompactString::from(isin);
+        assert!(!s.is_heap_allocated());
+        assert_eq!(ISIN::try_from(s), Ok(isin));
+    }
+
+    #[cfg(feature = "smol_str")]
+    #[test]
+    fn smol_str_round_trip() {
+        let isin = parse("US0378331005").unwrap();
+        let s = smol_str::SmolStr::from(isin);
+        assert!(!s.is_heap_allocated());
+        assert_eq!(ISIN::try_from(s), Ok(isin));
+        assert_eq!(
+            ISIN::try_from(smol_str::SmolStr::new("US0378331006")).ok(),
+            None
+        );
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_round_trip() {
+        let config = bincode::config::standard();
+        let isins = vec![
+            parse("US0378331005").unwrap(),
+            parse("US5949181045").unwrap(),
+        ];
+
+        let mut buf = [0u8; 25];
+        let len = bincode::encode_into_slice(&isins, &mut buf, config).unwrap();
+        assert_eq!(len, 25);
+        assert_eq!(&buf[..13], b"\x02US0378331005");
+        let (back, read): (Vec<ISIN>, usize) = bincode::decode_from_slice(&buf, config).unwrap();
+        assert_eq!((back, read), (isins, 25));
+
+        buf[12] = b'6';
+        let err = bincode::decode_from_slice::<Vec<ISIN>, _>(&buf, config).unwrap_err();
+        assert!(err.to_string().contains("incorrect check digit"), "{err}");
+        let err = bincode::decode_from_slice::<ISIN, _>(&buf[1..6], config).unwrap_err();
+        assert!(matches!(
+            err,
+            bincode::error::DecodeError::UnexpectedEnd { .. }
+        ));
+    }
+
+    #[cfg(feature = "arbitrary")]
+    proptest! {
+        #[test]
+        fn arbitrary_isins_are_valid(data in ::proptest::collection::vec(any::<u8>(), 0..64)) {
+            use arbitrary::{Arbitrary, Unstructured};
+
+            let mut u = Unstructured::new(&data);
+            while let Ok(isin) = ISIN::arbitrary(&mut u) {
+                prop_assert_eq!(parse(AsRef::<str>::as_ref(&isin)), Ok(isin));
+                if u.is_empty() {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn quickcheck_arbitrary() {
+        use quickcheck::{Arbitrary, Gen, QuickCheck};
+
+        fn valid(isin: ISIN) -> bool {
+            parse(AsRef::<str>::as_ref(&isin)) == Ok(isin)
+        }
+        QuickCheck::new().quickcheck(valid as fn(ISIN) -> bool);
+
+        let mut g = Gen::new(100);
+        let isin = ISIN::arbitrary(&mut g);
+        for smaller in isin.shrink() {
+            assert_eq!(smaller.prefix(), isin.prefix());
+            assert!(smaller < isin);
+        }
+        let simplest = build_from_parts(isin.prefix(), "000000000").unwrap();
+        assert!(isin == simplest || isin.shrink().any(|s| s == simplest));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn rand_isins_are_valid() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(2348);
+        let mut prefixes = std::collections::HashSet::new();
+        for _ in 0..1000 {
+            let isin: ISIN = rng.gen();
+            assert_eq!(parse(AsRef::<str>::as_ref(&isin)), Ok(isin));
+            prefixes.insert(isin.prefix_typed());
+            let isin = ISIN::random_with(&mut rng);
+            assert_eq!(parse(AsRef::<str>::as_ref(&isin)), Ok(isin));
+        }
+        // 1000 uniform draws from 676 _Prefixes_ should cover most of them
+        assert!(prefixes.len() > 400, "{} prefixes", prefixes.len());
+    }
+
+    #[cfg(feature = "utoipa")]
+    #[test]
+    fn utoipa_schema() {
+        use utoipa::{PartialSchema, ToSchema};
+
+        assert_eq!(ISIN::name(), "ISIN");
+        let schema = serde_json::to_value(ISIN::schema()).unwrap();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["minLength"], 12);
+        assert_eq!(schema["maxLength"], 12);
+        assert_eq!(schema["examples"][0], "US0378331005");
+
+        let pattern = schema["pattern"].as_str().unwrap();
+        assert_eq!(pattern, "^[A-Z]{2}[0-9A-Z]{9}[0-9]$");
+    }
+
+    #[test]
+    fn fix_check_digit() {
+        let apple = parse("US0378331005").unwrap();
+        assert_eq!(parse_fixing_check_digit("US0378331005"), Ok((apple, false)));
+        assert_eq!(parse_fixing_check_digit("US0378331009"), Ok((apple, true)));
+        assert_eq!(
+            parse_fixing_check_digit("US037833100X"),
+            Err(ParseError::InvalidCheckDigit { was: b'X' })
+        );
+        assert_eq!(
+            parse_fixing_check_digit("us0378331009"),
+            Err(ParseError::InvalidCharacter {
+                index: 0,
+                byte: b'u',
+                in_prefix: true
+            })
+        );
+    }
+
+    #[test]
+    fn format_only_validation() {
+        assert!(validate_format("US0378331005").is_ok());
+        assert!(validate_format("US0378331006").is_ok());
+        assert!(validate("US0378331006").is_err());
+        assert_eq!(
+            validate_format("US037833100X"),
+            Err(ParseError::InvalidCheckDigit { was: b'X' })
+        );
+        assert_eq!(
+            validate_format("U50378331005"),
+            Err(ParseError::InvalidCharacter {
+                index: 1,
+                byte: b'5',
+                in_prefix: true
+            })
+        );
+        assert!(ISIN::is_plausible("US0378331006"));
+        assert!(!ISIN::is_plausible("US037833100"));
+    }
+
+    #[test]
+    fn boolean_validity() {
+        assert!(is_valid("US0378331005"));
+        assert!(!is_valid("US0378331006"));
+        assert!(!is_valid("US037833100X"));
+        assert!(!is_valid("us0378331005"));
+        assert!(!is_valid("US03783310-5"));
+        assert!(!is_valid("US037833100"));
+        assert!(!is_valid(""));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn parse_full_width_and_exotic_spaces() {
+        let apple = parse("US0378331005").unwrap();
+        assert_eq!(parse_lenient_unicode("ＵＳ０３７８３３１００５"), Ok(apple));
+        assert_eq!(
+            parse_lenient_unicode("\u{a0}us0378331005\u{3000}"),
+            Ok(apple)
+        );
+        assert_eq!(
+            parse_lenient_unicode("ＵＳ０３７８３３１００６"),
+            Err(ParseError::IncorrectCheckDigit {
+                was: CheckDigit::from_value(6).unwrap(),
+                expected: CheckDigit::from_value(5).unwrap()
+            })
+        );
+        assert!(parse_lenient_unicode("US037833100٥").is_err());
+    }
+
+    #[test]
+    fn report_all_problems() {
+        assert_eq!(validate_all("US0378331005"), Ok(()));
+        assert_eq!(
+            validate_all("US037833100"),
+            Err(vec![ParseError::InvalidValueStringLength { was: 11 }])
+        );
+        assert_eq!(
+            validate_all("US0378331006"),
+            Err(vec![ParseError::IncorrectCheckDigit {
+                was: CheckDigit::from_value(6).unwrap(),
+                expected: CheckDigit::from_value(5).unwrap()
+            }])
+        );
+        assert_eq!(
+            validate_all("u503783310-X"),
+            Err(vec![
+                ParseError::InvalidCharacter {
+                    index: 0,
+                    byte: b'u',
+                    in_prefix: true
+                },
+                ParseError::InvalidCharacter {
+                    index: 1,
+                    byte: b'5',
+                    in_prefix: true
+                },
+                ParseError::InvalidCharacter {
+                    index: 10,
+                    byte: b'-',
+                    in_prefix: false
+                },
+                ParseError::InvalidCheckDigit { was: b'X' },
+            ])
+        );
+        assert_eq!(
+            validate_all("US0378331-06"),
+            Err(vec![ParseError::InvalidCharacter {
+                index: 9,
+                byte: b'-',
+                in_prefix: false
+            }])
+        );
+    }
+
+    #[test]
+    fn compare_with_strings() {
+        let isin = parse("US0378331005").unwrap();
+        assert_eq!(isin, "US0378331005");
+        assert_eq!("US0378331005", isin);
+        assert_eq!(isin, *"US0378331005");
+        assert_eq!(*"US0378331005", isin);
+        assert_eq!(isin, String::from("US0378331005"));
+        assert_eq!(String::from("US0378331005"), isin);
+        assert_ne!(isin, "us0378331005");
+        assert_ne!(isin, "US037833100");
+    }
+
+    #[test]
+    fn parse_isin_for_apple_loose() {
+        match parse_loose("\tus0378331005    ") {
+            Ok(isin) => {
+                assert_eq!(isin.to_string(), "US0378331005");
+                assert_eq!(isin.prefix(), "US");
+                assert_eq!(isin.basic_code(), "037833100");
+                assert_eq!(isin.check_digit(), '5');
+            }
+            Err(err) => panic!("Did not expect parsing to fail: {}", err),
+        }
+    }
+
+    #[test]
+    fn validate_examples_from_standard_annex_c() {
+        assert!(validate("ES0SI0000005").is_ok()); // Example 1, page 10: "IBEX 35"
+        assert!(validate("JP3788600009").is_ok()); // Example 2, page 11: "Hitachi Ltd. Shares"
+        assert!(validate("DE000A0GNPZ3").is_ok()); // Example 3, page 11: "Allianz Finance II 5 3/8% without expiration date"
+    }
+
+    #[test]
+    fn validate_examples_from_standard_annex_e() {
+        assert!(validate("JP3788600009").is_ok()); // Page 13
+        assert!(validate("US9047847093").is_ok()); // Page 13
+        assert!(validate("IE00BFXC1P95").is_ok()); // Page 13
+        assert!(validate("DE000A0GNPZ3").is_ok()); // Page 13
+        assert!(validate("XS2021448886").is_ok()); // Page 13
+        assert!(validate("US36962GXZ26").is_ok()); // Page 13
+        assert!(validate("FR0000571077").is_ok()); // Page 13
+        assert!(validate("US277847UB38").is_ok()); // Page 13
+        assert!(validate("US65412AEW80").is_ok()); // Page 13
+        assert!(validate("GB00BF0FCW58").is_ok()); // Page 13
+        assert!(validate("FR0000312928").is_ok()); // Page 13
+        assert!(validate("DE000DL3T7M1").is_ok()); // Page 13
+
+        assert!(validate("ES0A02234250").is_ok()); // Page 14
+        assert!(validate("EZR9HY1361L7").is_ok()); // Page 14
+        assert!(validate("CH0107166065").is_ok()); // Page 14
+        assert!(validate("XS0313614355").is_ok()); // Page 14
+        assert!(validate("DE000A0AE077").is_ok()); // Page 14
+        assert!(validate("CH0002813860").is_ok()); // Page 14
+        assert!(validate("TRLTCMB00045").is_ok()); // Page 14
+        assert!(validate("ES0SI0000005").is_ok()); // Page 14
+        assert!(validate("GB00B56Z6W79").is_ok()); // Page 14
+        assert!(validate("AU000000SKI7").is_ok()); // Page 14
+        assert!(validate("EU000A1RRN98").is_ok()); // Page 14
+        assert!(validate("LI0024807526").is_ok()); // Page 14
+    }
+
+    #[test]
+    fn reject_empty_string() {
+        let res = parse("");
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn reject_lowercase_prefix_if_strict() {
+        match parse("us0378331005") {
+            Err(ParseError::InvalidCharacter {
+                index: 0,
+                byte: b'u',
+                in_prefix: true,
+            }) => {} // Ok
+            Err(err) => {
+                panic!(
+                    "Expected Err(InvalidCharacter {{ ... }}), but got: Err({:?})",
+                    err
+                )
+            }
+            Ok(isin) => {
+                panic!(
+                    "Expected Err(InvalidCharacter {{ ... }}), but got: Ok({:?})",
+                    isin
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn reject_lowercase_basic_code_if_strict() {
+        match parse("US09739d1000") {
+            Err(ParseError::InvalidCharacter {
+                index: 7,
+                byte: b'd',
+                in_prefix: false,
+            }) => {} // Ok
+            Err(err) => {
+                panic!(
+                    "Expected Err(InvalidCharacter {{ ... }}), but got: Err({:?})",
+                    err
+                )
+            }
+            Ok(isin) => {
+                panic!(
+                    "Expected Err(InvalidCharacter {{ ... }}), but got: Ok({:?})",
+                    isin
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn reject_short_payload() {
+        assert_eq!(
+            build_from_payload("US03783310"),
+            Err(BuildError::InvalidPayloadStringLength { was: 10 })
+        );
+    }
+
+    #[test]
+    fn parse_isin_with_0_check_digit() {
+        parse("US09739D1000").unwrap(); // BCC aka Boise Cascade
+    }
+
+    #[test]
+    fn parse_isin_with_1_check_digit() {
+        parse("US4581401001").unwrap(); // INTC aka Intel
+    }
+
+    #[test]
+    fn parse_isin_with_2_check_digit() {
+        parse("US98421M1062").unwrap(); // XRX aka Xerox
+    }
+
+    #[test]
+    fn parse_isin_with_3_check_digit() {
+        parse("US02376R1023").unwrap(); // AAL aka American Airlines
+    }
+
+    #[test]
+    fn parse_isin_with_4_check_digit() {
+        parse("US9216591084").unwrap(); // VNDA aka Vanda Pharmaceuticals
+    }
+
+    #[test]
+    fn parse_isin_with_5_check_digit() {
+        parse("US0207721095").unwrap(); // APT aka AlphaProTec
+    }
+
+    #[test]
+    fn parse_isin_with_6_check_digit() {
+        parse("US71363P1066").unwrap(); // PRDO aka Perdoceo Education
+    }
+
+    #[test]
+    fn parse_isin_with_7_check_digit() {
+        parse("US5915202007").unwrap(); // MEI aka Methode Electronics
+    }
+
+    #[test]
+    fn parse_isin_with_8_check_digit() {
+        parse("US4570301048").unwrap(); // IMKTA aka Ingles Markets
+    }
+
+    #[test]
+    fn parse_isin_with_9_check_digit() {
+        parse("US8684591089").unwrap(); // SUPN aka Supernus Pharmaceuticals
+    }
+
+    #[test]
+    fn payload_rank_round_trip() {
+        for s in ["AA000000000", "US037833100", "DE000A0GNPZ", "ZZZZZZZZZZZ"] {
+            let isin = build_from_payload(s).unwrap();
+            assert_eq!(ISIN::from_payload_rank(isin.payload_rank()), Some(isin));
+        }
+        assert_eq!(build_from_payload("AA000000000").unwrap().payload_rank(), 0);
+        assert_eq!(
+            build_from_payload("ZZZZZZZZZZZ").unwrap().payload_rank(),
+            PAYLOAD_COUNT - 1
+        );
+        assert_eq!(ISIN::from_payload_rank(PAYLOAD_COUNT), None);
+    }
+
+    #[test]
+    fn test_unicode_gibberish() {
+        assert!(parse("𑴈𐎟 0 A").is_err());
+    }
+
+    proptest! {
+        #[test]
+        #[allow(unused_must_use)]
+        fn doesnt_crash(s in "\\PC*") {
+            parse(&s);
+        }
+
+        #[test]
+        fn validate_batch_matches_parse_each(
+            values in ::proptest::collection::vec("[A-Z]{2}[0-9A-Z]{9}[0-9]|[0-9A-Za-z]{0,13}", 0..40)
+        ) {
+            let expected: Vec<_> = values.iter().map(|v| parse(v).map_err(Error::from)).collect();
+            prop_assert_eq!(validate_batch(&values), expected);
+        }
+
+        #[test]
+        fn parse_loose_fast_path_matches_options(s in "[ \t]?[a-zA-Z]{2}[0-9a-zA-Z]{9}[0-9][ \t]?") {
+            prop_assert_eq!(parse_loose(&s), ParseOptions::loose().parse(&s));
+        }
+
+        #[test]
+        #[allow(unused_must_use)]
+        fn doesnt_crash_on_any_bytes(b in ::proptest::collection::vec(any::<u8>(), 0..16)) {
+            parse_bytes(&b);
+            validate_bytes(&b);
+            checksum::checksum_table(&b);
+            checksum::checksum_functional(&b);
+            field::parse_fixed(&b, 12);
+            field::parse_fixed(&b, 16);
+            if let Ok(s) = std::str::from_utf8(&b) {
+                parse_loose(s);
+                parse_fixing_check_digit(s);
+                validate_all(s);
+                validate_format(s);
+                build_from_payload(s);
+                if let Err(err) = parse(s) {
+                    err.suggestion(s);
+                }
+                repair::suggestions(s);
+            }
+        }
+
+        #[test]
+        fn parse_bytes_matches_parse(s in "\\PC*") {
+            prop_assert_eq!(parse(&s).ok(), parse_bytes(s.as_bytes()).ok());
+        }
+
+        #[test]
+        fn is_valid_matches_validate(s in "[A-Z]{2}[0-9A-Z]{9}[0-9]|\\PC*") {
+            prop_assert_eq!(is_valid(&s), validate(&s).is_ok());
+        }
+
+        #[test]
+        fn payload_rank_preserves_order(a in "[A-Z]{2}[0-9A-Z]{9}", b in "[A-Z]{2}[0-9A-Z]{9}") {
+            let a = build_from_payload(&a).unwrap();
+            let b = build_from_payload(&b).unwrap();
+            prop_assert_eq!(a.cmp(&b), a.payload_rank().cmp(&b.payload_rank()));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde {
+        use crate::ISIN;
+
+        use proptest::{prop_assert, prop_assert_eq, proptest};
+        use serde::de::value::{self, StrDeserializer};
+        use serde::Deserialize as _;
+
+        #[test]
+        fn deserialize_apple() {
+            let isin = ISIN::deserialize(StrDeserializer::<value::Error>::new("US0378331005"))
+                .expect("successful deserialization");
+            assert_eq!(isin.to_string(), "US0378331005");
+            assert_eq!(isin.prefix(), "US");
+            assert_eq!(isin.basic_code(), "037833100");
+            assert_eq!(isin.check_digit(), '5');
+        }
+
+        #[test]
+        fn deserialize_bytes() {
+            use serde::de::value::{BytesDeserializer, SeqDeserializer};
+
+            let apple = crate::parse("US0378331005").unwrap();
+            let isin = ISIN::deserialize(BytesDeserializer::<value::Error>::new(b"US0378331005"))
+                .expect("successful deserialization");
+            assert_eq!(isin, apple);
+            let _ = ISIN::deserialize(BytesDeserializer::<value::Error>::new(b"US0378331006"))
+                .expect_err("unsuccessful deserialization");
+            let _ = ISIN::deserialize(BytesDeserializer::<value::Error>::new(b"US03783310"))
+                .expect_err("unsuccessful deserialization");
+
+            let seq = |bytes: &'static [u8]| {
+                SeqDeserializer::<_, value::Error>::new(bytes.iter().copied())
+            };
+            assert_eq!(ISIN::deserialize(seq(b"US0378331005")).unwrap(), apple);
+            let err = ISIN::deserialize(seq(b"US037833100")).unwrap_err();
+            assert_eq!(err.to_string(), "invalid length 11, expected an ISIN");
+            let err = ISIN::deserialize(seq(b"US03783310050")).unwrap_err();
+            assert_eq!(err.to_string(), "invalid length 13, expected an ISIN");
+        }
+
+        #[test]
+        fn reject_empty_string() {
+            let _ = ISIN::deserialize(StrDeserializer::<value::Error>::new(""))
+                .expect_err("unsuccessful deserialization");
+        }
+
+        #[test]
+        fn reject_lowercase_prefix_if_strict() {
+            let _ = ISIN::deserialize(StrDeserializer::<value::Error>::new("us0378331005"))
+                .expect_err("unsuccessful deserialization");
+        }
+
+        #[test]
+        fn reject_lowercase_basic_code_if_strict() {
+            let _ = ISIN::deserialize(StrDeserializer::<value::Error>::new("US09739d1000"))
+                .expect_err("unsuccessful deserialization");
+        }
+
+        proptest! {
+            #[test]
+            fn doesnt_crash(s in "\\PC*") {
+                let _ = ISIN::deserialize(StrDeserializer::<value::Error>::new(&s));
+            }
+
+            #[test]
+            fn matches_parse(s in "\\PC*") {
+                let parse_result = crate::parse(&s);
+                let deserialize_result = ISIN::deserialize(StrDeserializer::<value::Error>::new(&s));
+
+                match (parse_result, deserialize_result)
+                {
+                    (Ok(parsed_isin), Ok(deserialized_isin)) => prop_assert_eq!(parsed_isin, deserialized_isin),
+                    (Ok(_), Err(_)) | (Err(_), Ok(_)) => prop_assert!(false),
+                    (Err(_), Err(_)) => {}
+                 }
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+//! # isin::avro
+//!
+//! The Avro representation of ISINs, for use with [`apache_avro`](https://docs.rs/apache-avro).
+//! An ISIN field can be either of two canonical schemas:
+//!
+//! * [`FIXED_SCHEMA_JSON`]: a `fixed` of twelve bytes named `ISIN`, the most compact form.
+//! * [`STRING_SCHEMA_JSON`]: a `string` with the logical type `isin`, readable by tools that do
+//!   not know the schema, which treat it as a plain string.
+//!
+//! ```
+//! use apache_avro::types::Value;
+//! use apache_avro::Schema;
+//!
+//! let schema = Schema::parse_str(&format!(
+//!     r#"{{ "type": "record", "name": "Trade", "fields": [
+//!         {{ "name": "isin", "type": {} }},
+//!         {{ "name": "benchmark", "type": {} }}
+//!     ] }}"#,
+//!     isin::avro::FIXED_SCHEMA_JSON,
+//!     isin::avro::STRING_SCHEMA_JSON,
+//! ))
+//! .unwrap();
+//!
+//! let apple = isin::parse("US0378331005").unwrap();
+//! let record = Value::Record(vec![
+//!     ("isin".to_string(), isin::avro::to_fixed_value(&apple)),
+//!     ("benchmark".to_string(), isin::avro::to_string_value(&apple)),
+//! ]);
+//! assert!(record.validate(&schema));
+//!
+//! let Value::Record(fields) = record else { unreachable!() };
+//! assert_eq!(isin::avro::from_value(&fields[0].1), Ok(apple));
+//! assert_eq!(isin::avro::from_value(&fields[1].1), Ok(apple));
+//! ```
+//!
+//! A named type such as the `fixed` can only be defined once in a schema, so further fields of
+//! the same record schema refer to it by its name, `"ISIN"`.
+//!
+//! This module is only available with the `avro` feature enabled.
+
+use std::fmt;
+
+use apache_avro::types::Value;
+use apache_avro::Schema;
+
+use crate::{parse, parse_bytes, ParseError, ISIN};
+
+/// The Avro schema of an ISIN as a `fixed` of its twelve bytes.
+pub const FIXED_SCHEMA_JSON: &str = r#"{"type": "fixed", "name": "ISIN", "size": 12}"#;
+
+/// The Avro schema of an ISIN as a `string` with the logical type `isin`.
+pub const STRING_SCHEMA_JSON: &str = r#"{"type": "string", "logicalType": "isin"}"#;
+
+/// Return the schema of [`FIXED_SCHEMA_JSON`].
+pub fn fixed_schema() -> Schema {
+    Schema::parse_str(FIXED_SCHEMA_JSON).expect("valid schema")
+}
+
+/// Return the Avro value of an ISIN for the [`FIXED_SCHEMA_JSON`] schema.
+pub fn to_fixed_value(isin: &ISIN) -> Value {
+    Value::Fixed(12, isin.as_bytes().to_vec())
+}
+
+/// Return the Avro value of an ISIN for the [`STRING_SCHEMA_JSON`] schema.
+pub fn to_string_value(isin: &ISIN) -> Value {
+    Value::String(isin.to_string())
+}
+
+/// The error returned by [`from_value()`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum FromValueError {
+    /// The value is not a string, bytes or a fixed of twelve bytes
+    UnexpectedType,
+    /// The value has a suitable type, but is not a valid ISIN
+    Invalid(ParseError),
+}
+
+impl fmt::Display for FromValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromValueError::UnexpectedType => {
+                write!(f, "Avro value is not a string, bytes or a fixed of size 12")
+            }
+            FromValueError::Invalid(err) => write!(f, "Avro value is not a valid ISIN: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for FromValueError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FromValueError::UnexpectedType => None,
+            FromValueError::Invalid(err) => Some(err),
+        }
+    }
+}
+
+/// Return the ISIN of an Avro value of either schema, or bytes. The value must be valid as for
+/// `parse()`. A union (such as a nullable field) is unwrapped to the value in it.
+pub fn from_value(value: &Value) -> Result<ISIN, FromValueError> {
+    match value {
+        Value::String(s) => parse(s).map_err(FromValueError::Invalid),
+        Value::Fixed(12, bytes) | Value::Bytes(bytes) => {
+            parse_bytes(bytes).map_err(FromValueError::Invalid)
+        }
+        Value::Union(_, value) => from_value(value),
+        _ => Err(FromValueError::UnexpectedType),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn schemas() {
+        let Schema::Fixed(fixed) = fixed_schema() else {
+            panic!("not a fixed schema");
+        };
+        assert_eq!(fixed.size, 12);
+        assert_eq!(fixed.name.name, "ISIN");
+
+        let apple = parse("US0378331005").unwrap();
+        assert!(to_fixed_value(&apple).validate(&fixed_schema()));
+        let string_schema = Schema::parse_str(STRING_SCHEMA_JSON).unwrap();
+        assert!(to_string_value(&apple).validate(&string_schema));
+        assert!(!to_string_value(&apple).validate(&fixed_schema()));
+    }
+
+    #[test]
+    fn reject_other_values() {
+        assert_eq!(
+            from_value(&Value::Int(1)),
+            Err(FromValueError::UnexpectedType)
+        );
+        assert_eq!(
+            from_value(&Value::Union(0, Box::new(Value::Null))),
+            Err(FromValueError::UnexpectedType)
+        );
+        assert_eq!(
+            from_value(&Value::Fixed(11, b"US037833100".to_vec())),
+            Err(FromValueError::UnexpectedType)
+        );
+        let err = from_value(&Value::String("US0378331006".into())).unwrap_err();
+        assert!(matches!(err, FromValueError::Invalid(_)));
+        assert!(err
+            .to_string()
+            .starts_with("Avro value is not a valid ISIN"));
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(payload in "[A-Z]{2}[0-9A-Z]{9}") {
+            let isin = crate::build_from_payload(&payload).unwrap();
+            prop_assert_eq!(from_value(&to_fixed_value(&isin)), Ok(isin));
+            prop_assert_eq!(from_value(&to_string_value(&isin)), Ok(isin));
+            let union = Value::Union(1, Box::new(to_fixed_value(&isin)));
+            prop_assert_eq!(from_value(&union), Ok(isin));
+            prop_assert_eq!(from_value(&Value::Bytes(isin.as_bytes().to_vec())), Ok(isin));
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! # isin::basic_code
+//!
+//! The _Basic Code_ of an ISIN as a type of its own, validated independently of any full ISIN.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{
+    alphanumeric_char, alphanumeric_value, ascii_str, validate_basic_code_format, BuildError,
+    BASIC_CODE_COUNT,
+};
+
+/// An ISIN _Basic Code_ in confirmed valid format: nine uppercase ASCII alphanumeric characters.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash)]
+#[repr(transparent)]
+pub struct BasicCode([u8; 9]);
+
+impl BasicCode {
+    /// Internal constructor for bytes already known to be a valid _Basic Code_.
+    pub(crate) fn from_bytes_valid(bytes: [u8; 9]) -> BasicCode {
+        BasicCode(bytes)
+    }
+
+    /// Parse a string to a valid _Basic Code_ or an error, requiring the string to already be
+    /// nine uppercase ASCII alphanumeric characters with no leading or trailing whitespace.
+    pub fn parse(value: &str) -> Result<BasicCode, BuildError> {
+        if value.len() != 9 {
+            return Err(BuildError::InvalidBasicCodeStringLength {
+                was: crate::error::error_len(value.len()),
+            });
+        }
+        let b = value.as_bytes();
+        validate_basic_code_format(b)
+            .map_err(|(index, byte)| BuildError::InvalidBasicCode { index, byte })?;
+
+        let mut bb = [0u8; 9];
+        bb.copy_from_slice(b);
+
+        Ok(BasicCode(bb))
+    }
+
+    /// Make a _Basic Code_ from its value as a base-36 number, with digits `0`&ndash;`9` followed
+    /// by letters `A`&ndash;`Z`, zero-padded on the left to nine characters. Returns `None` if the
+    /// value needs more than nine base-36 digits.
+    pub fn from_value(value: u64) -> Option<BasicCode> {
+        if value >= BASIC_CODE_COUNT {
+            return None;
+        }
+        let mut bb = [b'0'; 9];
+        let mut rest = value;
+        for b in bb.iter_mut().rev() {
+            *b = alphanumeric_char(rest % 36);
+            rest /= 36;
+        }
+        Some(BasicCode(bb))
+    }
+
+    /// Return the value of the _Basic Code_ interpreted as a base-36 number, with digits
+    /// `0`&ndash;`9` followed by letters `A`&ndash;`Z`.
+    pub fn value(&self) -> u64 {
+        self.0
+            .iter()
+            .fold(0, |acc, b| acc * 36 + alphanumeric_value(*b))
+    }
+
+    /// Return the underlying national number: the _Basic Code_ with its left zero-padding
+    /// removed, as used by National Numbering Agencies and many vendor files. A _Basic Code_ of
+    /// all zeros gives `"0"`.
+    ///
+    /// Without the _Prefix_ there is no telling padding from the leading zeros of a fixed-width
+    /// national number, so every leading zero is removed: the CUSIP `037833100` gives `"37833100"`.
+    /// `ISIN::nsin()` knows the _Prefix_, and keeps those zeros.
+    pub fn nsin(&self) -> &str {
+        let start = self.0.iter().position(|b| *b != b'0').unwrap_or(8);
+        ascii_str(&self.0[start..])
+    }
+
+    /// Return the _Basic Code_ as a string slice.
+    pub fn as_str(&self) -> &str {
+        ascii_str(&self.0)
+    }
+
+    /// Return the _Basic Code_ as a fixed-size array of its nine ASCII bytes.
+    pub fn as_bytes(&self) -> &[u8; 9] {
+        &self.0
+    }
+}
+
+impl AsRef<str> for BasicCode {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for BasicCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Debug for BasicCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BasicCode({})", self.as_str())
+    }
+}
+
+impl FromStr for BasicCode {
+    type Err = BuildError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        BasicCode::parse(s)
+    }
+}
+
+impl TryFrom<[u8; 9]> for BasicCode {
+    type Error = BuildError;
+
+    fn try_from(value: [u8; 9]) -> Result<Self, Self::Error> {
+        validate_basic_code_format(&value)
+            .map_err(|(index, byte)| BuildError::InvalidBasicCode { index, byte })?;
+        Ok(BasicCode(value))
+    }
+}
+
+impl PartialEq<str> for BasicCode {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for BasicCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Prefix, ISIN};
+
+    #[test]
+    fn parse_apple() {
+        let basic_code: BasicCode = "037833100".parse().unwrap();
+        assert_eq!(basic_code.as_str(), "037833100");
+        assert_eq!(basic_code.as_bytes(), b"037833100");
+        assert_eq!(basic_code.to_string(), "037833100");
+        assert_eq!(format!("{:?}", basic_code), "BasicCode(037833100)");
+        assert_eq!(basic_code, "037833100");
+    }
+
+    #[test]
+    fn reject_invalid() {
+        assert_eq!(
+            BasicCode::parse("03783310"),
+            Err(BuildError::InvalidBasicCodeStringLength { was: 8 })
+        );
+        assert_eq!(
+            BasicCode::parse("03783310a"),
+            Err(BuildError::InvalidBasicCode {
+                index: 8,
+                byte: b'a'
+            })
+        );
+    }
+
+    #[test]
+    fn numeric_value() {
+        let basic_code = BasicCode::parse("000A0GNPZ").unwrap();
+        assert_eq!(BasicCode::from_value(basic_code.value()), Some(basic_code));
+        assert_eq!(BasicCode::from_value(0).unwrap(), "000000000");
+        assert_eq!(BasicCode::from_value(35).unwrap(), "00000000Z");
+        assert_eq!(BasicCode::from_value(36).unwrap(), "000000010");
+        assert_eq!(
+            BasicCode::from_value(BASIC_CODE_COUNT - 1).unwrap(),
+            "ZZZZZZZZZ"
+        );
+        assert_eq!(BasicCode::from_value(BASIC_CODE_COUNT), None);
+        let next = BasicCode::from_value(basic_code.value() + 1).unwrap();
+        assert_eq!(next, "000A0GNQ0");
+    }
+
+    #[test]
+    fn strip_padding_for_nsin() {
+        assert_eq!(BasicCode::parse("000A0GNPZ").unwrap().nsin(), "A0GNPZ");
+        assert_eq!(BasicCode::parse("037833100").unwrap().nsin(), "37833100");
+        assert_eq!(BasicCode::parse("B0YBKJ7G1").unwrap().nsin(), "B0YBKJ7G1");
+        assert_eq!(BasicCode::parse("000000000").unwrap().nsin(), "0");
+    }
+
+    #[test]
+    fn keep_fixed_width_zeros_for_isin_nsin() {
+        let nsin = |s: &str| crate::parse(s).unwrap().nsin().to_owned();
+        assert_eq!(nsin("US0378331005"), "037833100");
+        assert_eq!(nsin("CA0679011084"), "067901108");
+        assert_eq!(nsin("GB0002634946"), "0263494");
+        assert_eq!(nsin("IE00BFXC1P95"), "BFXC1P9");
+        assert_eq!(nsin("DE000A0GNPZ3"), "A0GNPZ");
+        assert_eq!(nsin("CH0002813860"), "281386");
+    }
+
+    #[test]
+    fn build_and_access() {
+        let isin = ISIN::from_parts(Prefix::parse("US").unwrap(), "037833100".parse().unwrap());
+        assert_eq!(isin, crate::parse("US0378331005").unwrap());
+        assert_eq!(isin.basic_code_typed(), "037833100");
+        assert_eq!(isin.basic_code_value(), isin.basic_code_typed().value());
+        assert_eq!(isin.nsin(), "037833100");
+    }
+}
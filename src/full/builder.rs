@@ -0,0 +1,161 @@
+//! # isin::builder
+//!
+//! A fluent builder for assembling an ISIN from separately-supplied parts, as an alternative to the
+//! positional `build_from_parts()`.
+
+use crate::{BasicCode, BuildError, Prefix, ISIN};
+
+/// A builder for an ISIN, with each part validated as it is set.
+///
+/// Setting a part with an invalid value records the error, which is then available from
+/// `error()` and returned from `build()`. Only the first error is kept.
+///
+/// ```
+/// use isin::IsinBuilder;
+///
+/// let isin = IsinBuilder::new()
+///     .prefix("US")
+///     .basic_code("037833100")
+///     .build()
+///     .unwrap();
+/// assert_eq!(isin.to_string(), "US0378331005");
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct IsinBuilder {
+    prefix: Option<Prefix>,
+    basic_code: Option<BasicCode>,
+    error: Option<BuildError>,
+}
+
+impl IsinBuilder {
+    /// Create a builder with no parts set.
+    pub fn new() -> IsinBuilder {
+        IsinBuilder::default()
+    }
+
+    /// Record an error, unless one has already been recorded.
+    fn fail(mut self, error: BuildError) -> IsinBuilder {
+        if self.error.is_none() {
+            self.error = Some(error);
+        }
+        self
+    }
+
+    /// Set the _Prefix_ from a string of two uppercase ASCII letters.
+    pub fn prefix(self, prefix: &str) -> IsinBuilder {
+        match Prefix::parse(prefix) {
+            Ok(prefix) => self.prefix_typed(prefix),
+            Err(err) => self.fail(err),
+        }
+    }
+
+    /// Set the _Prefix_ from an already-validated `Prefix`.
+    pub fn prefix_typed(mut self, prefix: Prefix) -> IsinBuilder {
+        self.prefix = Some(prefix);
+        self
+    }
+
+    /// Set the _Basic Code_ from a string of nine uppercase ASCII alphanumeric characters.
+    pub fn basic_code(self, basic_code: &str) -> IsinBuilder {
+        match BasicCode::parse(basic_code) {
+            Ok(basic_code) => self.basic_code_typed(basic_code),
+            Err(err) => self.fail(err),
+        }
+    }
+
+    /// Set the _Basic Code_ from a string of up to nine uppercase ASCII alphanumeric characters,
+    /// zero-padding it on the left to nine characters as The Standard requires.
+    pub fn basic_code_padded(self, basic_code: &str) -> IsinBuilder {
+        if basic_code.is_empty() || basic_code.len() > 9 {
+            return self.fail(BuildError::InvalidBasicCodeStringLength {
+                was: crate::error::error_len(basic_code.len()),
+            });
+        }
+        let padded = format!("{basic_code:0>9}");
+        self.basic_code(&padded)
+    }
+
+    /// Set the _Basic Code_ from an already-validated `BasicCode`.
+    pub fn basic_code_typed(mut self, basic_code: BasicCode) -> IsinBuilder {
+        self.basic_code = Some(basic_code);
+        self
+    }
+
+    /// Return the first error recorded while setting parts, if any.
+    pub fn error(&self) -> Option<&BuildError> {
+        self.error.as_ref()
+    }
+
+    /// Build the ISIN, computing the _Check Digit_. Fails with the first error recorded while
+    /// setting parts, or if a part was never set.
+    pub fn build(&self) -> Result<ISIN, BuildError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        let prefix = self.prefix.ok_or(BuildError::MissingPrefix)?;
+        let basic_code = self.basic_code.ok_or(BuildError::MissingBasicCode)?;
+        Ok(ISIN::from_parts(prefix, basic_code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_apple() {
+        let isin = IsinBuilder::new()
+            .prefix("US")
+            .basic_code("037833100")
+            .build();
+        assert_eq!(isin.ok(), crate::parse("US0378331005").ok());
+    }
+
+    #[test]
+    fn build_with_padding() {
+        let isin = IsinBuilder::new()
+            .prefix("DE")
+            .basic_code_padded("A0GNPZ")
+            .build();
+        assert_eq!(isin.ok(), crate::parse("DE000A0GNPZ3").ok());
+    }
+
+    #[test]
+    fn report_first_error_as_set() {
+        let builder = IsinBuilder::new().prefix("us");
+        assert_eq!(
+            builder.error(),
+            Some(&BuildError::InvalidPrefix {
+                index: 0,
+                byte: b'u'
+            })
+        );
+        let builder = builder.basic_code("TOO LONG CODE");
+        assert_eq!(
+            builder.build(),
+            Err(BuildError::InvalidPrefix {
+                index: 0,
+                byte: b'u'
+            })
+        );
+    }
+
+    #[test]
+    fn reject_missing_parts() {
+        assert_eq!(
+            IsinBuilder::new().basic_code("037833100").build(),
+            Err(BuildError::MissingPrefix)
+        );
+        assert_eq!(
+            IsinBuilder::new().prefix("US").build(),
+            Err(BuildError::MissingBasicCode)
+        );
+        assert_eq!(
+            IsinBuilder::new()
+                .prefix("US")
+                .basic_code_padded("")
+                .build(),
+            Err(BuildError::InvalidBasicCodeStringLength { was: 0 })
+        );
+    }
+}
@@ -0,0 +1,981 @@
+//! # isin::bulk
+//!
+//! Functions for processing many potential ISINs in one pass, such as the contents of a file with
+//! one value per line.
+//!
+//! The functions ending in `_observed` also pass the [`Outcome`] for each value to an
+//! [`Observer`] as it is validated, such as a closure incrementing metrics counters:
+//!
+//! ```
+//! use isin::bulk::{validate_lines_observed, Outcome};
+//!
+//! let mut invalid = 0;
+//! let data = "US0378331005\nUS0378331006\n";
+//! validate_lines_observed(data.as_bytes(), 10, &mut |outcome| {
+//!     if let Outcome::Invalid(_) = outcome {
+//!         invalid += 1;
+//!     }
+//! })
+//! .unwrap();
+//! assert_eq!(invalid, 1);
+//! ```
+//!
+//! With the `tracing` feature enabled, each function runs in a span named after it, and emits an
+//! `INFO` event with the counts and throughput when it finishes, `DEBUG` events with the count of
+//! each kind of error and the progress of long inputs, and a `TRACE` event for each invalid value.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
+use std::time::{Duration, Instant};
+
+use crate::checksum::{checksum_lanes, INVALID_PAYLOAD, SLAB_LANES};
+use crate::{
+    parse, parse_bytes, parse_fixing_check_digit, validate_bytes, Error, ErrorKind, Prefix, ISIN,
+};
+
+/// An input that was not a valid ISIN as given, but could be repaired.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Fixed {
+    /// The zero-based position of the input in the sequence passed to [`fix_all()`]
+    pub index: usize,
+    /// The input exactly as it was passed in
+    pub input: String,
+    /// The repaired ISIN
+    pub isin: ISIN,
+    /// Whether the _Check Digit_ had to be replaced (as opposed to only normalizing whitespace
+    /// and case)
+    pub check_digit_corrected: bool,
+}
+
+/// An input that could not be repaired.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Unfixable {
+    /// The zero-based position of the input in the sequence passed to [`fix_all()`]
+    pub index: usize,
+    /// The input exactly as it was passed in
+    pub input: String,
+    /// The reason the (normalized) input could not be repaired
+    pub error: Error,
+}
+
+/// The outcome of running [`fix_all()`] over a sequence of inputs.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct FixReport {
+    /// The inputs that were already valid ISINs, in input order
+    pub valid: Vec<ISIN>,
+    /// The inputs that were repaired, in input order
+    pub fixed: Vec<Fixed>,
+    /// The inputs that could not be repaired, in input order
+    pub unfixable: Vec<Unfixable>,
+}
+
+impl FixReport {
+    /// The total number of inputs processed.
+    pub fn total(&self) -> usize {
+        self.valid.len() + self.fixed.len() + self.unfixable.len()
+    }
+}
+
+/// Attempt to parse every input, repairing those that can be repaired.
+///
+/// Each input is first parsed strictly. If that fails, it is normalized by trimming leading and
+/// trailing whitespace and converting to uppercase, and parsed again. If the only remaining
+/// problem is an incorrect _Check Digit_, a correct one is computed from the _Payload_. Anything
+/// else is reported as unfixable along with the error from the normalized input.
+pub fn fix_all<I, S>(inputs: I) -> FixReport
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    fix_all_observed(inputs, &mut |_| {})
+}
+
+/// Attempt to parse every input as [`fix_all()`] does, passing the outcome for each input to
+/// `observer`: [`Outcome::Valid`], [`Outcome::Fixed`] or [`Outcome::Invalid`] for one that could
+/// not be repaired.
+pub fn fix_all_observed<I, S, O>(inputs: I, observer: &mut O) -> FixReport
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+    O: Observer + ?Sized,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("fix_all").entered();
+
+    let mut report = FixReport::default();
+    for (index, input) in inputs.into_iter().enumerate() {
+        let outcome = fix_one(index, input.as_ref());
+        observer.observe(match &outcome {
+            FixOutcome::Valid(isin) => Outcome::Valid(*isin),
+            FixOutcome::Fixed(fixed) => Outcome::Fixed(fixed.isin),
+            FixOutcome::Unfixable(unfixable) => Outcome::Invalid(unfixable.error.kind()),
+        });
+        report.push(outcome);
+    }
+    #[cfg(feature = "tracing")]
+    report.trace();
+    report
+}
+
+/// The outcome of validating one value, as passed to an [`Observer`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    /// The value is a valid ISIN
+    Valid(ISIN),
+    /// The value is not a valid ISIN, for the given kind of error
+    Invalid(ErrorKind),
+    /// The value was repaired by [`fix_all()`] to the given ISIN, or could be. When validating
+    /// without repairing, this follows the `Invalid` outcome for the same value, as the value is
+    /// counted in both [`Report::invalid`] and [`Report::fixed`].
+    Fixed(ISIN),
+}
+
+/// Receives the [`Outcome`] for each value as it is validated by the functions ending in
+/// `_observed`. Blank lines and records are skipped without an outcome.
+///
+/// This is implemented for closures taking an `Outcome`.
+pub trait Observer {
+    /// Handle the outcome for the next value.
+    fn observe(&mut self, outcome: Outcome);
+
+    /// Handle a line that is not a valid ISIN, just before its `Invalid` outcome. This is called
+    /// by the line-based functions for every such line, kept in the [`Report`] or not, so each
+    /// failure can be reported as it is found without keeping them all. Does nothing by default.
+    fn observe_failure(&mut self, failure: &LineFailure) {
+        let _ = failure;
+    }
+}
+
+impl<F: FnMut(Outcome)> Observer for F {
+    fn observe(&mut self, outcome: Outcome) {
+        self(outcome)
+    }
+}
+
+/// The outcome of [`fix_one()`] for one input.
+pub(crate) enum FixOutcome {
+    Valid(ISIN),
+    Fixed(Fixed),
+    Unfixable(Unfixable),
+}
+
+impl FixReport {
+    /// Emit the counts as an event.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn trace(&self) {
+        tracing::info!(
+            total = self.total(),
+            valid = self.valid.len(),
+            fixed = self.fixed.len(),
+            unfixable = self.unfixable.len(),
+            "fixed ISINs"
+        );
+    }
+
+    /// Add the outcome for the next input.
+    pub(crate) fn push(&mut self, outcome: FixOutcome) {
+        match outcome {
+            FixOutcome::Valid(isin) => self.valid.push(isin),
+            FixOutcome::Fixed(fixed) => self.fixed.push(fixed),
+            FixOutcome::Unfixable(unfixable) => self.unfixable.push(unfixable),
+        }
+    }
+}
+
+/// Parse one input for [`fix_all()`], repairing it if possible.
+pub(crate) fn fix_one(index: usize, input: &str) -> FixOutcome {
+    if let Ok(isin) = parse(input) {
+        return FixOutcome::Valid(isin);
+    }
+
+    let normalized = input.trim().to_ascii_uppercase();
+    match parse_fixing_check_digit(&normalized) {
+        Ok((isin, check_digit_corrected)) => FixOutcome::Fixed(Fixed {
+            index,
+            input: input.to_string(),
+            isin,
+            check_digit_corrected,
+        }),
+        Err(error) => FixOutcome::Unfixable(Unfixable {
+            index,
+            input: input.to_string(),
+            error: error.into(),
+        }),
+    }
+}
+
+/// The number of failures kept in a [`Report`] by [`validate_lines()`].
+pub const DEFAULT_KEPT_FAILURES: usize = 100;
+
+/// A line that was not a valid ISIN.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LineFailure {
+    /// The one-based line number
+    pub line: usize,
+    /// The line without its line ending, with any invalid UTF-8 replaced
+    pub input: String,
+    /// The reason the line is not a valid ISIN
+    pub error: Error,
+}
+
+/// The outcome of running [`validate_lines()`] over a source of lines.
+///
+/// With the `serde` feature, this implements `Serialize`, with the prefixes and error kinds as
+/// the keys of maps, so it can be stored as a summary of a data-quality check:
+///
+/// ```
+/// # #[cfg(feature = "serde")] {
+/// let report = isin::bulk::validate_lines(&b"US0378331005\nUS0378331006\n"[..]).unwrap();
+/// let json = serde_json::to_value(&report).unwrap();
+/// assert_eq!(json["prefix_counts"]["US"], 1);
+/// assert_eq!(json["error_counts"]["IncorrectCheckDigit"], 1);
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Report {
+    /// The number of lines validated, not counting blank ones
+    pub total: usize,
+    /// The number of lines that were valid ISINs
+    pub valid: usize,
+    /// The number of lines that were not valid ISINs
+    pub invalid: usize,
+    /// The number of invalid lines that [`fix_all()`] would repair
+    pub fixed: usize,
+    /// The number of valid lines with each _Prefix_
+    pub prefix_counts: BTreeMap<Prefix, usize>,
+    /// The number of invalid lines with each kind of error
+    pub error_counts: BTreeMap<ErrorKind, usize>,
+    /// The first invalid lines, in order, up to the number asked for
+    pub failures: Vec<LineFailure>,
+    /// How long the validation took, including reading
+    pub duration: Duration,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for LineFailure {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut st = serializer.serialize_struct("LineFailure", 3)?;
+        st.serialize_field("line", &self.line)?;
+        st.serialize_field("input", &self.input)?;
+        st.serialize_field("error", &self.error)?;
+        st.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Report {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        struct Counts<'a, K>(&'a BTreeMap<K, usize>, fn(&K) -> &str);
+
+        impl<K> serde::Serialize for Counts<'_, K> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.collect_map(self.0.iter().map(|(k, v)| ((self.1)(k), v)))
+            }
+        }
+
+        let mut st = serializer.serialize_struct("Report", 8)?;
+        st.serialize_field("total", &self.total)?;
+        st.serialize_field("valid", &self.valid)?;
+        st.serialize_field("invalid", &self.invalid)?;
+        st.serialize_field("fixed", &self.fixed)?;
+        st.serialize_field(
+            "prefix_counts",
+            &Counts(&self.prefix_counts, Prefix::as_str),
+        )?;
+        st.serialize_field("error_counts", &Counts(&self.error_counts, |k| k.as_str()))?;
+        st.serialize_field("failures", &self.failures)?;
+        st.serialize_field("duration", &self.duration)?;
+        st.end()
+    }
+}
+
+/// Validate every line read from `reader` as an ISIN, with the same requirements as `parse()`,
+/// counting the results and keeping the first [`DEFAULT_KEPT_FAILURES`] failures.
+///
+/// ```
+/// let data = "US0378331005\nUS0378331006\n\nUS5949181045\n";
+/// let report = isin::bulk::validate_lines(data.as_bytes()).unwrap();
+/// assert_eq!((report.total, report.valid, report.invalid), (3, 2, 1));
+/// assert_eq!(report.failures[0].line, 2);
+/// ```
+pub fn validate_lines<R: BufRead>(reader: R) -> io::Result<Report> {
+    validate_lines_keeping(reader, DEFAULT_KEPT_FAILURES)
+}
+
+/// Validate every line read from `reader` as [`validate_lines()`] does, keeping the first
+/// `keep_failures` failures.
+///
+/// Lines end at `\n`, and a `\r` before it is removed too. Blank lines (empty, or only ASCII
+/// whitespace) are skipped, but still counted for line numbers. A line that is not valid UTF-8 is
+/// invalid, rather than an error. Only errors reading from `reader` are returned as errors.
+pub fn validate_lines_keeping<R: BufRead>(reader: R, keep_failures: usize) -> io::Result<Report> {
+    validate_lines_observed(reader, keep_failures, &mut |_| {})
+}
+
+/// Validate every line read from `reader` as [`validate_lines_keeping()`] does, passing the
+/// outcome for each line that is not blank to `observer`.
+pub fn validate_lines_observed<R, O>(
+    mut reader: R,
+    keep_failures: usize,
+    observer: &mut O,
+) -> io::Result<Report>
+where
+    R: BufRead,
+    O: Observer + ?Sized,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("validate_lines", keep_failures).entered();
+
+    let start = Instant::now();
+    let mut report = Report::default();
+    let mut buf = Vec::new();
+    let mut line = 0;
+
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        line += 1;
+
+        report.record(line, strip_line_ending(&buf), keep_failures, observer);
+    }
+
+    report.finish(start);
+    Ok(report)
+}
+
+/// How values are laid out in the data passed to [`validate_buffer()`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Layout {
+    /// One value per line, as for [`validate_lines()`]
+    Lines,
+    /// Records of the given number of bytes, each holding a value followed by any ASCII whitespace
+    /// (such as space padding or a line ending), which is ignored. A width of zero is treated as
+    /// one. If the data is not a whole number of records, the last one is shorter.
+    FixedWidth(usize),
+}
+
+/// Validate every value in `data` as an ISIN, with the same requirements as `parse()`, counting
+/// the results and keeping the first [`DEFAULT_KEPT_FAILURES`] failures. Blank lines or records are
+/// skipped, as for [`validate_lines()`], and the `line` of a failure is the number of its line or
+/// record.
+///
+/// The values are validated where they are in `data`, without copying them.
+///
+/// ```
+/// use isin::bulk::{validate_buffer, Layout};
+///
+/// let report = validate_buffer(b"US0378331005\r\nUS0378331006\n", Layout::Lines);
+/// assert_eq!((report.total, report.valid, report.invalid), (2, 1, 1));
+///
+/// let report = validate_buffer(b"US0378331005  US5949181045  ", Layout::FixedWidth(14));
+/// assert_eq!((report.total, report.valid), (2, 2));
+/// ```
+pub fn validate_buffer(data: &[u8], layout: Layout) -> Report {
+    validate_buffer_observed(data, layout, &mut |_| {})
+}
+
+/// Validate every value in `data` as [`validate_buffer()`] does, passing the outcome for each
+/// value to `observer`.
+pub fn validate_buffer_observed<O>(data: &[u8], layout: Layout, observer: &mut O) -> Report
+where
+    O: Observer + ?Sized,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("validate_buffer", bytes = data.len(), ?layout).entered();
+
+    let start = Instant::now();
+    let mut report = Report::default();
+
+    match layout {
+        Layout::Lines => {
+            for (i, line) in data.split(|&b| b == b'\n').enumerate() {
+                let value = line.strip_suffix(b"\r").unwrap_or(line);
+                report.record(i + 1, value, DEFAULT_KEPT_FAILURES, observer);
+            }
+        }
+        Layout::FixedWidth(width) => {
+            for (i, record) in data.chunks(width.max(1)).enumerate() {
+                let value = record.trim_ascii_end();
+                report.record(i + 1, value, DEFAULT_KEPT_FAILURES, observer);
+            }
+        }
+    }
+
+    report.finish(start);
+    report
+}
+
+/// Validate every value in a file, as [`validate_buffer()`] does, by mapping the file into memory
+/// instead of reading it.
+///
+/// The file must not be changed (by this or any other process) while it is being validated.
+///
+/// This function is only available with the `mmap` feature enabled, and not with the
+/// `forbid-unsafe` feature.
+#[cfg(all(feature = "mmap", not(feature = "forbid-unsafe")))]
+pub fn validate_mmap<P: AsRef<std::path::Path>>(path: P, layout: Layout) -> io::Result<Report> {
+    let file = std::fs::File::open(path)?;
+    // SAFETY: The map is only read, and the caller is required not to change the file while it is
+    // mapped.
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+    Ok(validate_buffer(&map, layout))
+}
+
+/// The outcome of [`validate_column()`]: which rows hold valid ISINs, and the errors of those that
+/// do not.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ColumnValidity {
+    /// The number of rows
+    rows: usize,
+    /// Bit `i % 64` of word `i / 64` is set if row `i` is valid
+    bits: Vec<u64>,
+    /// The row number and error of each invalid row, in row order
+    errors: Vec<(usize, Error)>,
+}
+
+impl ColumnValidity {
+    /// Return the number of rows validated.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Return true if the row holds a valid ISIN. Rows past the end are not valid.
+    pub fn is_valid(&self, row: usize) -> bool {
+        row < self.rows && self.bits[row / 64] & (1 << (row % 64)) != 0
+    }
+
+    /// Return the number of valid rows.
+    pub fn valid_count(&self) -> usize {
+        self.rows - self.errors.len()
+    }
+
+    /// Return the validity of the rows as a bitmask: bit `i % 64` of word `i / 64` is set if row
+    /// `i` is valid. This is the layout of an Arrow validity bitmap, when the words are stored in
+    /// little-endian order. Bits past the last row are clear.
+    pub fn bitmask(&self) -> &[u64] {
+        &self.bits
+    }
+
+    /// Return the row number and error of each invalid row, in row order.
+    pub fn errors(&self) -> &[(usize, Error)] {
+        &self.errors
+    }
+}
+
+/// Validate ISINs stored at a fixed position in each of a run of fixed-length records, such as a
+/// mainframe extract or an Arrow fixed-size binary column, without copying the records out.
+///
+/// The ISIN of row `i` is the twelve bytes starting at `offset + i * stride` in `buf`, and must
+/// meet the same requirements as for `validate_bytes()`. A row whose bytes extend past the end of
+/// `buf` is invalid with an [`InvalidValueArrayLength`](Error::InvalidValueArrayLength) error
+/// giving the number of bytes there were.
+///
+/// ```
+/// use isin::bulk::validate_column;
+///
+/// // Records of an 8-byte account code, an ISIN, and a 4-byte quantity
+/// let buf = b"ACCT0001US0378331005 100ACCT0002US0378331006  50";
+/// let validity = validate_column(buf, 24, 8, 2);
+/// assert!(validity.is_valid(0));
+/// assert!(!validity.is_valid(1));
+/// assert_eq!(validity.bitmask(), &[0b01]);
+/// assert_eq!(validity.errors()[0].0, 1);
+/// ```
+///
+/// The checksums of groups of rows are computed together, as for `validate_slab()`.
+pub fn validate_column(buf: &[u8], stride: usize, offset: usize, rows: usize) -> ColumnValidity {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("validate_column", stride, offset, rows).entered();
+
+    let mut validity = ColumnValidity {
+        rows,
+        bits: vec![0; rows.div_ceil(64)],
+        errors: Vec::new(),
+    };
+
+    // The bytes of a row, or as many of them as there are
+    let row_bytes = |row: usize| -> &[u8] {
+        let start = row
+            .checked_mul(stride)
+            .and_then(|pos| pos.checked_add(offset))
+            .unwrap_or(usize::MAX);
+        buf.get(start..)
+            .map_or(&[], |rest| &rest[..rest.len().min(12)])
+    };
+
+    let mut lanes = [[b'0'; 12]; SLAB_LANES];
+    for first in (0..rows).step_by(SLAB_LANES) {
+        let group = first..rows.min(first + SLAB_LANES);
+        for (lane, row) in lanes.iter_mut().zip(group.clone()) {
+            // Rows that are too short are rejected by the check below
+            *lane = row_bytes(row).try_into().unwrap_or([b'0'; 12]);
+        }
+        let sums = checksum_lanes(&lanes);
+
+        for (i, row) in group.enumerate() {
+            let bytes = row_bytes(row);
+            let sum = sums[i];
+            if bytes.len() == 12 && sum != INVALID_PAYLOAD && bytes[11] == b'0' + sum {
+                validity.bits[row / 64] |= 1 << (row % 64);
+            } else if let Err(error) = validate_bytes(bytes) {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(row, %error, "invalid ISIN");
+                validity.errors.push((row, error.into()));
+            }
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        rows,
+        valid = validity.valid_count(),
+        invalid = validity.errors.len(),
+        "validated ISIN column"
+    );
+    validity
+}
+
+/// The number of lines between progress events when validating with the `tracing` feature.
+#[cfg(feature = "tracing")]
+const TRACE_PROGRESS_LINES: usize = 1 << 20;
+
+/// Remove a trailing `\n` or `\r\n` from a line.
+pub(crate) fn strip_line_ending(line: &[u8]) -> &[u8] {
+    match line.strip_suffix(b"\n") {
+        Some(rest) => rest.strip_suffix(b"\r").unwrap_or(rest),
+        None => line,
+    }
+}
+
+impl Report {
+    /// Count one line, without its line ending, keeping it as a failure if it is invalid and
+    /// fewer than `keep_failures` have been kept, and pass its outcome to `observer`. Blank lines
+    /// are ignored.
+    pub(crate) fn record<O>(
+        &mut self,
+        line: usize,
+        value: &[u8],
+        keep_failures: usize,
+        observer: &mut O,
+    ) where
+        O: Observer + ?Sized,
+    {
+        #[cfg(feature = "tracing")]
+        if line.is_multiple_of(TRACE_PROGRESS_LINES) {
+            tracing::debug!(lines = line, "validating ISINs");
+        }
+        if value.iter().all(u8::is_ascii_whitespace) {
+            return;
+        }
+
+        self.total += 1;
+        match parse_bytes(value) {
+            Ok(isin) => {
+                self.valid += 1;
+                *self.prefix_counts.entry(isin.prefix_typed()).or_default() += 1;
+                observer.observe(Outcome::Valid(isin));
+            }
+            Err(error) => {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(line, %error, "invalid ISIN");
+                let kind = error.kind();
+                self.invalid += 1;
+                *self.error_counts.entry(kind).or_default() += 1;
+                let failure = LineFailure {
+                    line,
+                    input: String::from_utf8_lossy(value).into_owned(),
+                    error: error.into(),
+                };
+                observer.observe_failure(&failure);
+                observer.observe(Outcome::Invalid(kind));
+                if let Some(isin) = fixable(value) {
+                    self.fixed += 1;
+                    observer.observe(Outcome::Fixed(isin));
+                }
+                if self.failures.len() < keep_failures {
+                    self.failures.push(failure);
+                }
+            }
+        }
+    }
+
+    /// Set the duration from the time validation started, and emit the counts as events.
+    pub(crate) fn finish(&mut self, start: Instant) {
+        self.duration = start.elapsed();
+        #[cfg(feature = "tracing")]
+        self.trace();
+    }
+
+    #[cfg(feature = "tracing")]
+    fn trace(&self) {
+        let secs = self.duration.as_secs_f64();
+        let per_second = if secs > 0.0 {
+            self.total as f64 / secs
+        } else {
+            0.0
+        };
+        tracing::info!(
+            total = self.total,
+            valid = self.valid,
+            invalid = self.invalid,
+            fixed = self.fixed,
+            duration_secs = secs,
+            per_second,
+            "validated ISINs"
+        );
+        for (kind, count) in &self.error_counts {
+            tracing::debug!(kind = kind.as_str(), count, "invalid ISINs");
+        }
+    }
+
+    /// Add the counts of `other`, a report on lines that all come after the ones in this one,
+    /// keeping the first `keep_failures` failures of both.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn merge(&mut self, other: Report, keep_failures: usize) {
+        self.total += other.total;
+        self.valid += other.valid;
+        self.invalid += other.invalid;
+        self.fixed += other.fixed;
+        for (prefix, count) in other.prefix_counts {
+            *self.prefix_counts.entry(prefix).or_default() += count;
+        }
+        for (kind, count) in other.error_counts {
+            *self.error_counts.entry(kind).or_default() += count;
+        }
+        let room = keep_failures.saturating_sub(self.failures.len());
+        self.failures.extend(other.failures.into_iter().take(room));
+    }
+}
+
+/// Return the ISIN [`fix_all()`] would repair an input to, if it would.
+fn fixable(value: &[u8]) -> Option<ISIN> {
+    let value = std::str::from_utf8(value).ok()?;
+    let (isin, _) = parse_fixing_check_digit(&value.trim().to_ascii_uppercase()).ok()?;
+    Some(isin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fix_mixed_inputs() {
+        let report = fix_all([
+            "US0378331005",
+            " us0378331005\t",
+            "US0378331009",
+            "US03783310",
+        ]);
+
+        assert_eq!(report.total(), 4);
+        assert_eq!(report.valid, vec![parse("US0378331005").unwrap()]);
+
+        assert_eq!(report.fixed.len(), 2);
+        assert_eq!(report.fixed[0].index, 1);
+        assert_eq!(report.fixed[0].isin.to_string(), "US0378331005");
+        assert!(!report.fixed[0].check_digit_corrected);
+        assert_eq!(report.fixed[1].index, 2);
+        assert_eq!(report.fixed[1].isin.to_string(), "US0378331005");
+        assert!(report.fixed[1].check_digit_corrected);
+
+        assert_eq!(report.unfixable.len(), 1);
+        assert_eq!(report.unfixable[0].index, 3);
+        assert_eq!(report.unfixable[0].input, "US03783310");
+        assert_eq!(
+            report.unfixable[0].error,
+            Error::InvalidValueStringLength { was: 10 }
+        );
+    }
+
+    #[test]
+    fn validate_lines_counts_and_keeps_failures() {
+        let data = b"US0378331005\r\nUS0378331006\n  \nUS03783310\nUS0378331005\xff\nUS5949181045";
+        let report = validate_lines_keeping(&data[..], 2).unwrap();
+        assert_eq!(report.total, 5);
+        assert_eq!(report.valid, 2);
+        assert_eq!(report.invalid, 3);
+        assert_eq!(
+            report.error_counts,
+            BTreeMap::from([
+                (ErrorKind::InvalidValueArrayLength, 2),
+                (ErrorKind::IncorrectCheckDigit, 1),
+            ])
+        );
+        assert_eq!(report.fixed, 1);
+        assert_eq!(report.prefix_counts.len(), 1);
+        assert_eq!(
+            report.prefix_counts.get(&Prefix::parse("US").unwrap()),
+            Some(&2)
+        );
+        assert_eq!(report.failures.len(), 2);
+        assert_eq!(report.failures[0].line, 2);
+        assert_eq!(report.failures[0].input, "US0378331006");
+        assert_eq!(report.failures[1].line, 4);
+        assert_eq!(
+            report.failures[1].error,
+            Error::InvalidValueArrayLength { was: 10 }
+        );
+    }
+
+    #[test]
+    fn validate_lines_reports_read_errors() {
+        struct Failing;
+
+        impl io::Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("failed"))
+            }
+        }
+
+        assert!(validate_lines(io::BufReader::new(Failing)).is_err());
+        let report = validate_lines(&b""[..]).unwrap();
+        assert_eq!(report.total, 0);
+        assert!(report.error_counts.is_empty());
+    }
+
+    #[test]
+    fn observers_see_each_outcome() {
+        let apple = parse("US0378331005").unwrap();
+
+        let mut outcomes = Vec::new();
+        let data = b"US0378331005\n\nus0378331005\nUS03783310\n";
+        let report = validate_lines_observed(&data[..], 0, &mut |o| outcomes.push(o)).unwrap();
+        assert_eq!(
+            outcomes,
+            [
+                Outcome::Valid(apple),
+                Outcome::Invalid(ErrorKind::InvalidCharacter),
+                Outcome::Fixed(apple),
+                Outcome::Invalid(ErrorKind::InvalidValueArrayLength),
+            ]
+        );
+        assert_eq!((report.valid, report.invalid, report.fixed), (1, 2, 1));
+
+        outcomes.clear();
+        validate_buffer_observed(data, Layout::Lines, &mut |o| outcomes.push(o));
+        assert_eq!(outcomes.len(), 4);
+
+        outcomes.clear();
+        fix_all_observed(["US0378331005", "US0378331009", "US03783310"], &mut |o| {
+            outcomes.push(o)
+        });
+        assert_eq!(
+            outcomes,
+            [
+                Outcome::Valid(apple),
+                Outcome::Fixed(apple),
+                Outcome::Invalid(ErrorKind::InvalidValueStringLength),
+            ]
+        );
+    }
+
+    #[test]
+    fn observers_see_every_failure() {
+        struct Failures(Vec<(usize, String)>);
+
+        impl Observer for Failures {
+            fn observe(&mut self, _: Outcome) {}
+
+            fn observe_failure(&mut self, failure: &LineFailure) {
+                self.0.push((failure.line, failure.input.clone()));
+            }
+        }
+
+        let mut failures = Failures(Vec::new());
+        let data = b"US0378331005\n\nus0378331005\nUS03783310\n";
+        let report = validate_lines_observed(&data[..], 1, &mut failures).unwrap();
+        assert_eq!(
+            failures.0,
+            [
+                (3, "us0378331005".to_string()),
+                (4, "US03783310".to_string())
+            ]
+        );
+        assert_eq!(report.failures.len(), 1);
+    }
+
+    #[test]
+    fn empty_input() {
+        let report = fix_all(Vec::<String>::new());
+        assert_eq!(report, FixReport::default());
+    }
+
+    #[test]
+    fn buffer_layouts_match_lines() {
+        let data = b"US0378331005\nUS0378331006\r\n\nus0378331005\nUS5949181045";
+        let mut expected = validate_lines(&data[..]).unwrap();
+        let mut report = validate_buffer(data, Layout::Lines);
+        expected.duration = Duration::ZERO;
+        report.duration = Duration::ZERO;
+        assert_eq!(report, expected);
+
+        let data = b"US0378331005 \nUS0378331006 \n             \nus03783310";
+        let report = validate_buffer(data, Layout::FixedWidth(14));
+        assert_eq!((report.total, report.valid, report.invalid), (3, 1, 2));
+        assert_eq!(report.failures[1].line, 4);
+        assert_eq!(report.failures[1].input, "us03783310");
+
+        assert_eq!(validate_buffer(b"", Layout::Lines).total, 0);
+        assert_eq!(validate_buffer(b"US", Layout::FixedWidth(0)).invalid, 2);
+    }
+
+    #[test]
+    fn column_matches_validate_bytes() {
+        let values: Vec<&[u8]> = vec![
+            b"US0378331005",
+            b"US0378331006",
+            b"us0378331005",
+            b"US5949181045",
+        ];
+        let mut buf = Vec::new();
+        for i in 0..70 {
+            buf.extend_from_slice(b"--");
+            buf.extend_from_slice(values[i % values.len()]);
+            buf.push(b'|');
+        }
+
+        let validity = validate_column(&buf, 15, 2, 70);
+        assert_eq!(validity.rows(), 70);
+        assert_eq!(validity.bitmask().len(), 2);
+        for row in 0..70 {
+            let expected = validate_bytes(values[row % values.len()]);
+            assert_eq!(validity.is_valid(row), expected.is_ok(), "{row}");
+        }
+        assert!(!validity.is_valid(70));
+        assert_eq!(validity.valid_count(), 35);
+        let error = validate_bytes(values[1]).unwrap_err();
+        assert_eq!(validity.errors()[0], (1, error.into()));
+    }
+
+    #[test]
+    fn column_past_end_of_buffer() {
+        let validity = validate_column(b"xxUS0378331005US03783310", 12, 2, 3);
+        assert!(validity.is_valid(0));
+        assert_eq!(
+            validity.errors(),
+            &[
+                (1, Error::InvalidValueArrayLength { was: 10 }),
+                (2, Error::InvalidValueArrayLength { was: 0 })
+            ]
+        );
+        assert_eq!(
+            validate_column(b"", usize::MAX, usize::MAX, 2).valid_count(),
+            0
+        );
+    }
+
+    #[cfg(all(feature = "mmap", not(feature = "forbid-unsafe")))]
+    #[test]
+    fn mmap_file() {
+        let path = std::env::temp_dir().join(format!("isin-bulk-{}.txt", std::process::id()));
+        std::fs::write(&path, "US0378331005\nUS0378331006\n").unwrap();
+        let report = validate_mmap(&path, Layout::Lines);
+        std::fs::remove_file(&path).unwrap();
+        let report = report.unwrap();
+        assert_eq!((report.total, report.valid, report.invalid), (2, 1, 1));
+
+        std::fs::write(&path, "").unwrap();
+        let report = validate_mmap(&path, Layout::Lines);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(report.unwrap().total, 0);
+
+        assert!(validate_mmap(&path, Layout::Lines).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_report() {
+        let mut report = validate_lines(&b"US0378331005\nus0378331005\n"[..]).unwrap();
+        report.duration = Duration::from_millis(1500);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "total": 2,
+                "valid": 1,
+                "invalid": 1,
+                "fixed": 1,
+                "prefix_counts": { "US": 1 },
+                "error_counts": { "InvalidCharacter": 1 },
+                "failures": [{
+                    "line": 2,
+                    "input": "us0378331005",
+                    "error": { "InvalidCharacter": { "index": 0, "byte": "u", "in_prefix": true } },
+                }],
+                "duration": { "secs": 1, "nanos": 500000000 },
+            })
+        );
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn trace_events() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        /// Collect the level and message of every event.
+        #[derive(Clone, Default)]
+        struct Collector(Arc<Mutex<Vec<(tracing::Level, String)>>>);
+
+        struct Message(String);
+
+        impl Visit for Message {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    self.0 = format!("{value:?}");
+                }
+            }
+        }
+
+        impl Subscriber for Collector {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                let mut message = Message(String::new());
+                event.record(&mut message);
+                let level = *event.metadata().level();
+                self.0.lock().unwrap().push((level, message.0));
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let collector = Collector::default();
+        tracing::subscriber::with_default(collector.clone(), || {
+            validate_buffer(b"US0378331005\nUS0378331006\n", Layout::Lines);
+        });
+
+        let events = collector.0.lock().unwrap();
+        assert_eq!(
+            *events,
+            [
+                (tracing::Level::TRACE, "invalid ISIN".to_string()),
+                (tracing::Level::INFO, "validated ISINs".to_string()),
+                (tracing::Level::DEBUG, "invalid ISINs".to_string()),
+            ]
+        );
+    }
+}
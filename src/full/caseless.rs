@@ -0,0 +1,136 @@
+//! # isin::caseless
+//!
+//! A key wrapper that compares and hashes ignoring ASCII case, for joining ISINs against data
+//! sources that store identifiers in lowercase without normalizing every value first.
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use isin::CaselessKey;
+//!
+//! // Rows from a source that stores identifiers in lowercase.
+//! let rows = [("us0378331005", "Apple"), ("us5949181045", "Microsoft")];
+//! let names: HashMap<CaselessKey<&str>, &str> =
+//!     rows.iter().map(|(id, name)| (CaselessKey(*id), *name)).collect();
+//!
+//! let isin = isin::parse("US0378331005").unwrap();
+//! assert_eq!(names.get(&CaselessKey(isin.as_ref())), Some(&"Apple"));
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A string-like key whose `Eq`, `Ord` and `Hash` ignore ASCII case. Two keys are equal if their
+/// strings are equal after converting ASCII letters to uppercase, and equal keys hash the same.
+#[derive(Clone, Copy, Default)]
+pub struct CaselessKey<T>(pub T);
+
+impl<T> CaselessKey<T> {
+    /// Unwrap the key.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: AsRef<str>> CaselessKey<T> {
+    /// The uppercased bytes of the key, which is what `Eq`, `Ord` and `Hash` use.
+    fn folded(&self) -> impl Iterator<Item = u8> + '_ {
+        self.0.as_ref().bytes().map(|b| b.to_ascii_uppercase())
+    }
+}
+
+impl<T: AsRef<str>> PartialEq for CaselessKey<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_ref().eq_ignore_ascii_case(other.0.as_ref())
+    }
+}
+
+impl<T: AsRef<str>> Eq for CaselessKey<T> {}
+
+impl<T: AsRef<str>> PartialOrd for CaselessKey<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: AsRef<str>> Ord for CaselessKey<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.folded().cmp(other.folded())
+    }
+}
+
+impl<T: AsRef<str>> Hash for CaselessKey<T> {
+    /// Feed the hasher the uppercased bytes in fixed-size chunks, so equal keys (which always have
+    /// equal lengths) feed it exactly the same calls. An ISIN is a single chunk.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for chunk in self.0.as_ref().as_bytes().chunks(16) {
+            let mut buf = [0u8; 16];
+            let buf = &mut buf[..chunk.len()];
+            buf.copy_from_slice(chunk);
+            buf.make_ascii_uppercase();
+            state.write(buf);
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for CaselessKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CaselessKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CaselessKey({:?})", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use std::collections::{BTreeSet, HashSet};
+    use std::hash::{BuildHasher, RandomState};
+
+    #[test]
+    fn equal_ignoring_case() {
+        let isin = parse("US0378331005").unwrap();
+        assert_eq!(CaselessKey(isin.as_ref()), CaselessKey("us0378331005"));
+        assert_eq!(CaselessKey(isin), CaselessKey(isin));
+        assert_ne!(CaselessKey("us0378331005"), CaselessKey("us0378331006"));
+        assert_eq!(
+            CaselessKey("us0378331005").cmp(&CaselessKey("US0378331006")),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn same_hash_ignoring_case() {
+        let s = RandomState::new();
+        assert_eq!(
+            s.hash_one(CaselessKey("US0378331005")),
+            s.hash_one(CaselessKey("us0378331005".to_string()))
+        );
+        let long = "a long key that spans several chunks of sixteen bytes";
+        assert_eq!(
+            s.hash_one(CaselessKey(long)),
+            s.hash_one(CaselessKey(long.to_uppercase()))
+        );
+    }
+
+    #[test]
+    fn collections() {
+        let keys = [
+            "us0378331005",
+            "US0378331005",
+            "Us0378331005",
+            "US5949181045",
+        ];
+        let hashed: HashSet<_> = keys.iter().map(CaselessKey).collect();
+        assert_eq!(hashed.len(), 2);
+        let ordered: BTreeSet<_> = keys.iter().map(CaselessKey).collect();
+        assert_eq!(ordered.len(), 2);
+        assert_eq!(format!("{:?}", CaselessKey("us")), "CaselessKey(\"us\")");
+    }
+}
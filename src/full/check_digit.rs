@@ -0,0 +1,114 @@
+//! # isin::check_digit
+//!
+//! The _Check Digit_ of an ISIN as a type of its own, so the character and numeric forms of the
+//! digit are never confused.
+
+use std::fmt;
+
+/// An ISIN _Check Digit_: a single ASCII decimal digit.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash)]
+#[repr(transparent)]
+pub struct CheckDigit(u8);
+
+impl CheckDigit {
+    /// Internal constructor for an ASCII byte already known to be a decimal digit.
+    pub(crate) fn from_ascii_valid(b: u8) -> CheckDigit {
+        CheckDigit(b)
+    }
+
+    /// Make a _Check Digit_ from its numeric value, which must be 0 through 9.
+    pub fn from_value(value: u8) -> Option<CheckDigit> {
+        if value < 10 {
+            Some(CheckDigit(b'0' + value))
+        } else {
+            None
+        }
+    }
+
+    /// Make a _Check Digit_ from its ASCII character code, which must be `b'0'` through `b'9'`.
+    pub fn from_ascii(b: u8) -> Option<CheckDigit> {
+        if b.is_ascii_digit() {
+            Some(CheckDigit(b))
+        } else {
+            None
+        }
+    }
+
+    /// Make a _Check Digit_ from a character, which must be `'0'` through `'9'`.
+    pub fn from_char(c: char) -> Option<CheckDigit> {
+        if c.is_ascii_digit() {
+            Some(CheckDigit(c as u8))
+        } else {
+            None
+        }
+    }
+
+    /// Return the _Check Digit_ as a character `'0'` through `'9'`.
+    pub fn as_char(&self) -> char {
+        self.0 as char
+    }
+
+    /// Return the numeric value of the _Check Digit_, 0 through 9.
+    pub fn as_u8(&self) -> u8 {
+        self.0 - b'0'
+    }
+
+    /// Return the ASCII character code of the _Check Digit_, `b'0'` through `b'9'`.
+    pub fn as_ascii(&self) -> u8 {
+        self.0
+    }
+}
+
+impl From<CheckDigit> for char {
+    fn from(check_digit: CheckDigit) -> Self {
+        check_digit.as_char()
+    }
+}
+
+impl fmt::Display for CheckDigit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
+impl fmt::Debug for CheckDigit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CheckDigit({:?})", self.as_char())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        let five = CheckDigit::from_value(5).unwrap();
+        assert_eq!(five.as_char(), '5');
+        assert_eq!(five.as_u8(), 5);
+        assert_eq!(five.as_ascii(), b'5');
+        assert_eq!(CheckDigit::from_ascii(b'5'), Some(five));
+        assert_eq!(CheckDigit::from_char('5'), Some(five));
+        assert_eq!(char::from(five), '5');
+        assert_eq!(five.to_string(), "5");
+        assert_eq!(format!("{:?}", five), "CheckDigit('5')");
+    }
+
+    #[test]
+    fn reject_non_digits() {
+        assert_eq!(CheckDigit::from_value(10), None);
+        assert_eq!(CheckDigit::from_ascii(b'A'), None);
+        assert_eq!(CheckDigit::from_char('٥'), None);
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(CheckDigit::from_value(0) < CheckDigit::from_value(9));
+    }
+
+    #[test]
+    fn typed_accessor() {
+        let isin = crate::parse("US0378331005").unwrap();
+        assert_eq!(isin.check_digit_typed(), CheckDigit::from_value(5).unwrap());
+    }
+}
@@ -0,0 +1,141 @@
+//! # isin::diff
+//!
+//! Field-level comparison of two ISINs, for reconciliation reports that need to explain _how_ two
+//! values disagree rather than just _that_ they do.
+
+use std::fmt;
+
+/// The positions of the _Prefix_ within an ISIN.
+const PREFIX_MASK: u16 = 0b0000_0000_0011;
+
+/// The positions of the _Basic Code_ within an ISIN.
+const BASIC_CODE_MASK: u16 = 0b0111_1111_1100;
+
+/// The position of the _Check Digit_ within an ISIN.
+const CHECK_DIGIT_MASK: u16 = 0b1000_0000_0000;
+
+/// The result of comparing two ISINs character by character, as produced by
+/// [`ISIN::diff()`](crate::ISIN::diff).
+///
+/// Bit `i` is set if the characters at position `i` differ, with position 0 being the first
+/// character of the _Prefix_ and position 11 being the _Check Digit_.
+#[derive(Eq, PartialEq, Clone, Copy, Hash, Debug)]
+pub struct IsinDiff(u16);
+
+impl IsinDiff {
+    /// Compare two 12-byte ISIN values position by position.
+    pub(crate) fn between(a: &[u8; 12], b: &[u8; 12]) -> IsinDiff {
+        let mut mask = 0u16;
+        for (i, (x, y)) in a.iter().zip(b.iter()).enumerate() {
+            if x != y {
+                mask |= 1 << i;
+            }
+        }
+        IsinDiff(mask)
+    }
+
+    /// Return true if the two ISINs are identical.
+    pub fn is_same(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Return true if the _Prefix_ portions of the two ISINs differ.
+    pub fn prefix_differs(&self) -> bool {
+        self.0 & PREFIX_MASK != 0
+    }
+
+    /// Return true if the _Basic Code_ portions of the two ISINs differ.
+    pub fn basic_code_differs(&self) -> bool {
+        self.0 & BASIC_CODE_MASK != 0
+    }
+
+    /// Return true if the _Check Digit_ portions of the two ISINs differ.
+    pub fn check_digit_differs(&self) -> bool {
+        self.0 & CHECK_DIGIT_MASK != 0
+    }
+
+    /// Return the number of character positions at which the two ISINs differ.
+    pub fn count(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Iterate over the (zero-based) character positions at which the two ISINs differ, in
+    /// ascending order.
+    pub fn positions(&self) -> impl Iterator<Item = usize> {
+        let mask = self.0;
+        (0..12).filter(move |i| mask & (1 << i) != 0)
+    }
+}
+
+impl fmt::Display for IsinDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_same() {
+            return write!(f, "identical");
+        }
+
+        let fields = [
+            (self.prefix_differs(), "Prefix"),
+            (self.basic_code_differs(), "Basic Code"),
+            (self.check_digit_differs(), "Check Digit"),
+        ];
+        let mut first = true;
+        for (_, name) in fields.iter().filter(|(differs, _)| *differs) {
+            if !first {
+                write!(f, ", ")?;
+            }
+            write!(f, "{name}")?;
+            first = false;
+        }
+
+        write!(f, " differ at positions ")?;
+        let mut first = true;
+        for i in self.positions() {
+            if !first {
+                write!(f, ", ")?;
+            }
+            write!(f, "{i}")?;
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse;
+
+    #[test]
+    fn identical() {
+        let a = parse("US0378331005").unwrap();
+        let diff = a.diff(&a);
+        assert!(diff.is_same());
+        assert_eq!(diff.count(), 0);
+        assert_eq!(diff.to_string(), "identical");
+    }
+
+    #[test]
+    fn prefix_and_check_digit() {
+        let a = parse("US0378331005").unwrap();
+        let b = crate::build_from_parts("CA", "037833100").unwrap();
+        let diff = a.diff(&b);
+        assert!(diff.prefix_differs());
+        assert!(!diff.basic_code_differs());
+        assert!(diff.check_digit_differs());
+        assert_eq!(diff.positions().collect::<Vec<_>>(), vec![0, 1, 11]);
+        assert_eq!(
+            diff.to_string(),
+            "Prefix, Check Digit differ at positions 0, 1, 11"
+        );
+    }
+
+    #[test]
+    fn transposed_basic_code() {
+        let a = parse("US0378331005").unwrap();
+        let b = crate::build_from_parts("US", "037383100").unwrap();
+        let diff = a.diff(&b);
+        assert!(!diff.prefix_differs());
+        assert!(diff.basic_code_differs());
+        assert_eq!(diff.count(), 3);
+        assert_eq!(diff.positions().collect::<Vec<_>>(), vec![5, 6, 11]);
+    }
+}
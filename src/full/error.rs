@@ -0,0 +1,1418 @@
+#![warn(missing_docs)]
+//! # isin::error
+//!
+//! Error types for ISIN parsing and building.
+//!
+//! Parsing functions return a [`ParseError`] and building functions return a [`BuildError`], each
+//! with only the variants that function can actually produce. Both convert into the combined
+//! [`Error`] type for callers that handle failures from either kind of function in one place.
+
+use std::fmt::Formatter;
+use std::fmt::{Debug, Display};
+
+use crate::{repair, CheckDigit, ISIN};
+
+/// All the ways parsing or building could fail. This is the union of [`ParseError`] and
+/// [`BuildError`].
+///
+/// Like them, it is `Copy` and only eight bytes: lengths are stored as a `u8` (saturating at 255),
+/// and an invalid _Prefix_ or _Basic Code_ as the offset and value of its first invalid byte
+/// rather than a copy of it, so even large collections of errors are cheap to keep.
+#[non_exhaustive]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The value string length is not exactly 12 characters.
+    InvalidValueStringLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The value byte array length is not exactly 12 bytes.
+    InvalidValueArrayLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The _Payload_ string length is not exactly 11 characters.
+    InvalidPayloadStringLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The _Payload_ byte array length is not exactly 11 bytes.
+    InvalidPayloadArrayLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The _Prefix_ string length is not exactly 2 characters.
+    InvalidPrefixStringLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The _Prefix_ byte array length is not exactly 2 bytes.
+    InvalidPrefixArrayLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The _Basic Code_ string length is not exactly 9 characters.
+    InvalidBasicCodeStringLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The _Basic Code_ byte array length is not exactly 9 bytes.
+    InvalidBasicCodeArrayLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The input _Prefix_ is not two uppercase ASCII alphabetic characters.
+    InvalidPrefix {
+        /// The zero-based offset of the first invalid byte in the _Prefix_
+        index: u8,
+        /// The byte we found
+        byte: u8,
+    },
+    /// The input _Basic Code_ is not nine uppercase ASCII alphanumeric characters.
+    InvalidBasicCode {
+        /// The zero-based offset of the first invalid byte in the _Basic Code_
+        index: u8,
+        /// The byte we found
+        byte: u8,
+    },
+    /// The input has a character that is not allowed at its position: anything but an uppercase
+    /// ASCII letter in the _Prefix_, or an uppercase ASCII alphanumeric in the _Basic Code_.
+    InvalidCharacter {
+        /// The zero-based byte offset of the character in the input
+        index: u32,
+        /// The byte we found (the first byte of its UTF-8 encoding if not ASCII)
+        byte: u8,
+        /// Whether the character is in the _Prefix_, rather than the _Basic Code_
+        in_prefix: bool,
+    },
+    /// The input _Check Digit_ is not a single ASCII decimal digit character.
+    InvalidCheckDigit {
+        /// The _Check Digit_ we found
+        was: u8,
+    },
+    /// The input _Check Digit_ is in a valid format, but has an incorrect value.
+    IncorrectCheckDigit {
+        /// The _Check Digit_ we found
+        was: CheckDigit,
+        /// The _Check Digit_ we expected
+        expected: CheckDigit,
+    },
+    /// No _Prefix_ was given when building an ISIN from separately-supplied parts.
+    MissingPrefix,
+    /// No _Basic Code_ was given when building an ISIN from separately-supplied parts.
+    MissingBasicCode,
+    /// The _Prefix_ is in valid format, but is not one allocated for use in ISINs. See
+    /// [`Prefix::is_allocated()`](crate::Prefix::is_allocated).
+    UnallocatedPrefix {
+        /// The _Prefix_ we found
+        was: [u8; 2],
+    },
+}
+
+/// The kind of an [`Error`], [`ParseError`] or [`BuildError`], without the details. There is one
+/// kind for each variant of `Error`, so it is cheap to copy, compare and match on, and can be used
+/// to categorize failures (such as for metrics labels) without destructuring.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum ErrorKind {
+    /// See [`Error::InvalidValueStringLength`].
+    InvalidValueStringLength,
+    /// See [`Error::InvalidValueArrayLength`].
+    InvalidValueArrayLength,
+    /// See [`Error::InvalidPayloadStringLength`].
+    InvalidPayloadStringLength,
+    /// See [`Error::InvalidPayloadArrayLength`].
+    InvalidPayloadArrayLength,
+    /// See [`Error::InvalidPrefixStringLength`].
+    InvalidPrefixStringLength,
+    /// See [`Error::InvalidPrefixArrayLength`].
+    InvalidPrefixArrayLength,
+    /// See [`Error::InvalidBasicCodeStringLength`].
+    InvalidBasicCodeStringLength,
+    /// See [`Error::InvalidBasicCodeArrayLength`].
+    InvalidBasicCodeArrayLength,
+    /// See [`Error::InvalidPrefix`].
+    InvalidPrefix,
+    /// See [`Error::InvalidBasicCode`].
+    InvalidBasicCode,
+    /// See [`Error::InvalidCharacter`].
+    InvalidCharacter,
+    /// See [`Error::InvalidCheckDigit`].
+    InvalidCheckDigit,
+    /// See [`Error::IncorrectCheckDigit`].
+    IncorrectCheckDigit,
+    /// See [`Error::MissingPrefix`].
+    MissingPrefix,
+    /// See [`Error::MissingBasicCode`].
+    MissingBasicCode,
+    /// See [`Error::UnallocatedPrefix`].
+    UnallocatedPrefix,
+}
+
+impl ErrorKind {
+    /// Return the name of the kind, which is the same as the name of the variant.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::InvalidValueStringLength => "InvalidValueStringLength",
+            ErrorKind::InvalidValueArrayLength => "InvalidValueArrayLength",
+            ErrorKind::InvalidPayloadStringLength => "InvalidPayloadStringLength",
+            ErrorKind::InvalidPayloadArrayLength => "InvalidPayloadArrayLength",
+            ErrorKind::InvalidPrefixStringLength => "InvalidPrefixStringLength",
+            ErrorKind::InvalidPrefixArrayLength => "InvalidPrefixArrayLength",
+            ErrorKind::InvalidBasicCodeStringLength => "InvalidBasicCodeStringLength",
+            ErrorKind::InvalidBasicCodeArrayLength => "InvalidBasicCodeArrayLength",
+            ErrorKind::InvalidPrefix => "InvalidPrefix",
+            ErrorKind::InvalidBasicCode => "InvalidBasicCode",
+            ErrorKind::InvalidCharacter => "InvalidCharacter",
+            ErrorKind::InvalidCheckDigit => "InvalidCheckDigit",
+            ErrorKind::IncorrectCheckDigit => "IncorrectCheckDigit",
+            ErrorKind::MissingPrefix => "MissingPrefix",
+            ErrorKind::MissingBasicCode => "MissingBasicCode",
+            ErrorKind::UnallocatedPrefix => "UnallocatedPrefix",
+        }
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Error {
+    /// Return the kind of this error, without the details.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::InvalidValueStringLength { .. } => ErrorKind::InvalidValueStringLength,
+            Error::InvalidValueArrayLength { .. } => ErrorKind::InvalidValueArrayLength,
+            Error::InvalidPayloadStringLength { .. } => ErrorKind::InvalidPayloadStringLength,
+            Error::InvalidPayloadArrayLength { .. } => ErrorKind::InvalidPayloadArrayLength,
+            Error::InvalidPrefixStringLength { .. } => ErrorKind::InvalidPrefixStringLength,
+            Error::InvalidPrefixArrayLength { .. } => ErrorKind::InvalidPrefixArrayLength,
+            Error::InvalidBasicCodeStringLength { .. } => ErrorKind::InvalidBasicCodeStringLength,
+            Error::InvalidBasicCodeArrayLength { .. } => ErrorKind::InvalidBasicCodeArrayLength,
+            Error::InvalidPrefix { .. } => ErrorKind::InvalidPrefix,
+            Error::InvalidBasicCode { .. } => ErrorKind::InvalidBasicCode,
+            Error::InvalidCharacter { .. } => ErrorKind::InvalidCharacter,
+            Error::InvalidCheckDigit { .. } => ErrorKind::InvalidCheckDigit,
+            Error::IncorrectCheckDigit { .. } => ErrorKind::IncorrectCheckDigit,
+            Error::MissingPrefix => ErrorKind::MissingPrefix,
+            Error::MissingBasicCode => ErrorKind::MissingBasicCode,
+            Error::UnallocatedPrefix { .. } => ErrorKind::UnallocatedPrefix,
+        }
+    }
+}
+
+/// Serialize in serde's usual externally tagged form for enums: unit variants as their name, and
+/// the others as a map from the name to the details. A byte from the input is serialized as a
+/// string of that character if it is ASCII, or of `0x` and two hexadecimal digits if not, as in
+/// the messages, and _Check Digits_ as single characters.
+///
+/// ```
+/// let err = isin::parse("US0378331006").unwrap_err();
+/// assert_eq!(
+///     serde_json::to_string(&isin::Error::from(err)).unwrap(),
+///     r#"{"IncorrectCheckDigit":{"was":"6","expected":"5"}}"#
+/// );
+/// ```
+#[cfg(feature = "serde")]
+impl serde::Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStructVariant;
+
+        let index = self.kind() as u32;
+        let name = self.kind().as_str();
+        match self {
+            Error::InvalidValueStringLength { was }
+            | Error::InvalidValueArrayLength { was }
+            | Error::InvalidPayloadStringLength { was }
+            | Error::InvalidPayloadArrayLength { was }
+            | Error::InvalidPrefixStringLength { was }
+            | Error::InvalidPrefixArrayLength { was }
+            | Error::InvalidBasicCodeStringLength { was }
+            | Error::InvalidBasicCodeArrayLength { was } => {
+                let mut sv = serializer.serialize_struct_variant("Error", index, name, 1)?;
+                sv.serialize_field("was", was)?;
+                sv.end()
+            }
+            Error::UnallocatedPrefix { was } => {
+                let mut sv = serializer.serialize_struct_variant("Error", index, name, 1)?;
+                sv.serialize_field("was", &String::from_utf8_lossy(was))?;
+                sv.end()
+            }
+            Error::InvalidPrefix { index: at, byte }
+            | Error::InvalidBasicCode { index: at, byte } => {
+                let mut sv = serializer.serialize_struct_variant("Error", index, name, 2)?;
+                sv.serialize_field("index", at)?;
+                sv.serialize_field("byte", &ErrorByte(*byte))?;
+                sv.end()
+            }
+            Error::InvalidCharacter {
+                index: at,
+                byte,
+                in_prefix,
+            } => {
+                let mut sv = serializer.serialize_struct_variant("Error", index, name, 3)?;
+                sv.serialize_field("index", at)?;
+                sv.serialize_field("byte", &ErrorByte(*byte))?;
+                sv.serialize_field("in_prefix", in_prefix)?;
+                sv.end()
+            }
+            Error::InvalidCheckDigit { was } => {
+                let mut sv = serializer.serialize_struct_variant("Error", index, name, 1)?;
+                sv.serialize_field("was", &ErrorByte(*was))?;
+                sv.end()
+            }
+            Error::IncorrectCheckDigit { was, expected } => {
+                let mut sv = serializer.serialize_struct_variant("Error", index, name, 2)?;
+                sv.serialize_field("was", &was.as_char())?;
+                sv.serialize_field("expected", &expected.as_char())?;
+                sv.end()
+            }
+            Error::MissingPrefix | Error::MissingBasicCode => {
+                serializer.serialize_unit_variant("Error", index, name)
+            }
+        }
+    }
+}
+
+impl Error {
+    /// Suggest the ISIN that was probably meant by the `input` that produced this error, for a
+    /// "did you mean" prompt. Only errors from a strict parse of a 12-character input can be
+    /// repaired, and only if there is a single most likely repair:
+    ///
+    /// * For an [`Error::IncorrectCheckDigit`], swapping two adjacent characters if exactly one
+    ///   swap gives a valid ISIN, otherwise correcting the _Check Digit_ if no swap does.
+    /// * For an [`Error::InvalidCharacter`] or [`Error::InvalidCheckDigit`], uppercasing the bad
+    ///   character if it is a lowercase letter, otherwise swapping it with a neighbour if exactly
+    ///   one such swap gives a valid ISIN.
+    ///
+    /// ```
+    /// let input = "US0738331005";
+    /// let err = isin::Error::from(isin::parse(input).unwrap_err());
+    /// assert_eq!(err.suggestion(input), Some(isin::parse("US0378331005").unwrap()));
+    /// ```
+    ///
+    /// See the [`repair`](crate::repair) module for all the candidates.
+    pub fn suggestion(&self, input: &str) -> Option<ISIN> {
+        let bb: [u8; 12] = input.as_bytes().try_into().ok()?;
+        let index = match self {
+            Error::IncorrectCheckDigit { .. } => {
+                let mut swaps = (0..11).filter_map(|index| repair::transposed(&bb, index));
+                return match (swaps.next(), swaps.next()) {
+                    (Some(isin), None) => Some(isin),
+                    // The error may not have come from this input, so rather than trusting its
+                    // expected digit, build the ISIN from the payload, which validates it.
+                    (None, _) => {
+                        let mut payload = [0u8; 11];
+                        payload.copy_from_slice(&bb[0..11]);
+                        crate::build_from_payload_bytes(&payload).ok()
+                    }
+                    _ => None,
+                };
+            }
+            Error::InvalidCharacter { index, .. } => *index as usize,
+            Error::InvalidCheckDigit { .. } => 11,
+            _ => return None,
+        };
+        if index >= 12 {
+            return None;
+        }
+
+        if bb[index].is_ascii_lowercase() {
+            let mut upper = bb;
+            upper[index] = bb[index].to_ascii_uppercase();
+            if let Ok(isin) = crate::parse_bytes(&upper) {
+                return Some(isin);
+            }
+        }
+
+        let before = index
+            .checked_sub(1)
+            .and_then(|i| repair::transposed(&bb, i));
+        let after = repair::transposed(&bb, index);
+        match (before, after) {
+            (Some(isin), None) | (None, Some(isin)) => Some(isin),
+            _ => None,
+        }
+    }
+
+    /// Display this error with a message from the given catalog, such as one in another
+    /// language. See the [`messages`](crate::messages) module.
+    #[cfg(feature = "i18n")]
+    pub fn localized<'a>(
+        &'a self,
+        catalog: &'a dyn crate::messages::MessageCatalog,
+    ) -> crate::messages::Localized<'a> {
+        crate::messages::Localized {
+            error: self,
+            catalog,
+        }
+    }
+
+    /// Attach a copy of the input that caused this error, for reporting which of many inputs
+    /// failed once the error has been passed up away from where the input is known.
+    pub fn with_input(self, input: &str) -> ErrorWithInput {
+        ErrorWithInput {
+            input: input.into(),
+            error: self,
+        }
+    }
+}
+
+impl Debug for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidValueStringLength { was } => {
+                write!(f, "InvalidValueStringLength {{ was: {was:?} }}")
+            }
+            Error::InvalidValueArrayLength { was } => {
+                write!(f, "InvalidValueArrayLength {{ was: {was:?} }}")
+            }
+            Error::InvalidPayloadStringLength { was } => {
+                write!(f, "InvalidPayloadStringLength {{ was: {was:?} }}")
+            }
+            Error::InvalidPayloadArrayLength { was } => {
+                write!(f, "InvalidPayloadArrayLength {{ was: {was:?} }}")
+            }
+            Error::InvalidPrefixStringLength { was } => {
+                write!(f, "InvalidPrefixStringLength {{ was: {was:?} }}")
+            }
+            Error::InvalidPrefixArrayLength { was } => {
+                write!(f, "InvalidPrefixArrayLength {{ was: {was:?} }}")
+            }
+            Error::InvalidBasicCodeStringLength { was } => {
+                write!(f, "InvalidBasicCodeStringLength {{ was: {was:?} }}")
+            }
+            Error::InvalidBasicCodeArrayLength { was } => {
+                write!(f, "InvalidBasicCodeArrayLength {{ was: {was:?} }}")
+            }
+            Error::InvalidPrefix { index, byte } => {
+                write!(
+                    f,
+                    "InvalidPrefix {{ index: {index:?}, byte: {:?} }}",
+                    ErrorByte(*byte)
+                )
+            }
+            Error::InvalidBasicCode { index, byte } => {
+                write!(
+                    f,
+                    "InvalidBasicCode {{ index: {index:?}, byte: {:?} }}",
+                    ErrorByte(*byte)
+                )
+            }
+            Error::InvalidCharacter {
+                index,
+                byte,
+                in_prefix,
+            } => {
+                write!(
+                    f,
+                    "InvalidCharacter {{ index: {index:?}, byte: {:?}, in_prefix: {in_prefix:?} }}",
+                    ErrorByte(*byte)
+                )
+            }
+            Error::InvalidCheckDigit { was } => {
+                write!(f, "InvalidCheckDigit {{ was: {:?} }}", ErrorByte(*was))
+            }
+            Error::IncorrectCheckDigit { was, expected } => {
+                write!(
+                    f,
+                    "IncorrectCheckDigit {{ was: {:?}, expected: {:?} }}",
+                    was.as_char(),
+                    expected.as_char()
+                )
+            }
+            Error::MissingPrefix => write!(f, "MissingPrefix"),
+            Error::MissingBasicCode => write!(f, "MissingBasicCode"),
+            Error::UnallocatedPrefix { was } => {
+                write!(
+                    f,
+                    "UnallocatedPrefix {{ was: {:?} }}",
+                    String::from_utf8_lossy(was)
+                )
+            }
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidValueStringLength { was } => {
+                write!(
+                    f,
+                    "invalid value string length {was} characters when expecting 12"
+                )
+            }
+            Error::InvalidValueArrayLength { was } => {
+                write!(
+                    f,
+                    "invalid value array length {was} bytes when expecting 12"
+                )
+            }
+            Error::InvalidPayloadStringLength { was } => {
+                write!(
+                    f,
+                    "invalid Payload string length {was} characters when expecting 11"
+                )
+            }
+            Error::InvalidPayloadArrayLength { was } => {
+                write!(
+                    f,
+                    "invalid Payload array length {was} bytes when expecting 11"
+                )
+            }
+            Error::InvalidPrefixArrayLength { was } => {
+                write!(
+                    f,
+                    "invalid Prefix array length {was} bytes when expecting 2"
+                )
+            }
+            Error::InvalidPrefixStringLength { was } => {
+                write!(
+                    f,
+                    "invalid Prefix string length {was} characters when expecting 2"
+                )
+            }
+            Error::InvalidBasicCodeStringLength { was } => {
+                write!(
+                    f,
+                    "invalid Basic Code string length {was} characters when expecting 9"
+                )
+            }
+            Error::InvalidBasicCodeArrayLength { was } => {
+                write!(
+                    f,
+                    "invalid Basic Code array length {was} bytes when expecting 9"
+                )
+            }
+            Error::InvalidPrefix { index, byte } => {
+                write!(
+                    f,
+                    "invalid character {:?} at index {index} of the prefix, which must be two uppercase ASCII alphabetic characters",
+                    ErrorByte(*byte)
+                )
+            }
+            Error::InvalidBasicCode { index, byte } => {
+                write!(
+                    f,
+                    "invalid character {:?} at index {index} of the basic code, which must be nine uppercase ASCII alphanumeric characters",
+                    ErrorByte(*byte)
+                )
+            }
+            Error::InvalidCharacter { index, byte, .. } => {
+                write!(
+                    f,
+                    "invalid character {:?} at index {index}",
+                    ErrorByte(*byte)
+                )
+            }
+            Error::InvalidCheckDigit { was } => {
+                write!(
+                    f,
+                    "check digit {:?} is not one ASCII decimal digit",
+                    ErrorByte(*was)
+                )
+            }
+            Error::IncorrectCheckDigit { was, expected } => {
+                write!(
+                    f,
+                    "incorrect check digit {:?} when expecting {:?}",
+                    was.as_char(),
+                    expected.as_char()
+                )
+            }
+            Error::MissingPrefix => write!(f, "missing Prefix"),
+            Error::MissingBasicCode => write!(f, "missing Basic Code"),
+            Error::UnallocatedPrefix { was } => {
+                write!(
+                    f,
+                    "prefix {:?} is not an allocated country code or special prefix",
+                    String::from_utf8_lossy(was)
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Every error has a code naming its kind, such as `isin::IncorrectCheckDigit`, and some have help.
+/// An [`Error::InvalidCharacter`] is labeled at its position, so attaching the input as source code
+/// shows exactly where the problem is:
+///
+/// ```
+/// let input = "US03783310-5";
+/// let err = isin::Error::from(isin::parse(input).unwrap_err());
+/// let report = miette::Report::new(err).with_source_code(input);
+/// # let _ = report;
+/// ```
+///
+/// The other errors have no position in the input, so have no labels.
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(format!("isin::{}", self.kind())))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let help = match self {
+            Error::InvalidValueStringLength { .. } | Error::InvalidValueArrayLength { .. } => {
+                "an ISIN is exactly 12 characters long".to_string()
+            }
+            Error::InvalidCharacter {
+                in_prefix: true, ..
+            } => {
+                "the Prefix (the first 2 characters) must be uppercase letters".to_string()
+            }
+            Error::InvalidCharacter { .. } => {
+                "the Basic Code (the 9 characters after the Prefix) must be uppercase letters or digits"
+                    .to_string()
+            }
+            Error::InvalidCheckDigit { .. } => {
+                "the Check Digit (the last character) must be a digit".to_string()
+            }
+            Error::IncorrectCheckDigit { expected, .. } => {
+                format!("the correct Check Digit is '{expected}'")
+            }
+            Error::UnallocatedPrefix { .. } => {
+                "the Prefix must be an ISO 3166 country code, or EU, EZ, XS or XA to XD".to_string()
+            }
+            _ => return None,
+        };
+        Some(Box::new(help))
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        match self {
+            Error::InvalidCharacter { index, byte, .. } => {
+                // Cover the whole character, so a label does not split a multi-byte character.
+                let len = match byte {
+                    0xf0.. => 4,
+                    0xe0.. => 3,
+                    0xc0.. => 2,
+                    _ => 1,
+                };
+                let label = miette::LabeledSpan::new_primary_with_span(
+                    Some("not allowed here".to_string()),
+                    (*index as usize, len),
+                );
+                Some(Box::new(std::iter::once(label)))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// All the ways parsing (or validating) a complete ISIN could fail.
+#[non_exhaustive]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The value string length is not exactly 12 characters.
+    InvalidValueStringLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The value byte array length is not exactly 12 bytes.
+    InvalidValueArrayLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The input has a character that is not allowed at its position: anything but an uppercase
+    /// ASCII letter in the _Prefix_, or an uppercase ASCII alphanumeric in the _Basic Code_. Only
+    /// the first such character is reported.
+    InvalidCharacter {
+        /// The zero-based byte offset of the character in the input
+        index: u32,
+        /// The byte we found (the first byte of its UTF-8 encoding if not ASCII)
+        byte: u8,
+        /// Whether the character is in the _Prefix_, rather than the _Basic Code_
+        in_prefix: bool,
+    },
+    /// The input _Check Digit_ is not a single ASCII decimal digit character.
+    InvalidCheckDigit {
+        /// The _Check Digit_ we found
+        was: u8,
+    },
+    /// The input _Check Digit_ is in a valid format, but has an incorrect value.
+    IncorrectCheckDigit {
+        /// The _Check Digit_ we found
+        was: CheckDigit,
+        /// The _Check Digit_ we expected
+        expected: CheckDigit,
+    },
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        match err {
+            ParseError::InvalidValueStringLength { was } => Error::InvalidValueStringLength { was },
+            ParseError::InvalidValueArrayLength { was } => Error::InvalidValueArrayLength { was },
+            ParseError::InvalidCharacter {
+                index,
+                byte,
+                in_prefix,
+            } => Error::InvalidCharacter {
+                index,
+                byte,
+                in_prefix,
+            },
+            ParseError::InvalidCheckDigit { was } => Error::InvalidCheckDigit { was },
+            ParseError::IncorrectCheckDigit { was, expected } => {
+                Error::IncorrectCheckDigit { was, expected }
+            }
+        }
+    }
+}
+
+/// Internal function converting a length to the `u8` stored in errors, saturating at 255.
+pub(crate) fn error_len(len: usize) -> u8 {
+    u8::try_from(len).unwrap_or(u8::MAX)
+}
+
+/// Internal wrapper rendering a byte stored in an error in messages: as a quoted character if it
+/// is ASCII, or as `0x` and two hexadecimal digits if not, since a byte of a multi-byte UTF-8
+/// character is not a character of its own.
+#[derive(Clone, Copy)]
+pub(crate) struct ErrorByte(pub(crate) u8);
+
+impl Debug for ErrorByte {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_ascii() {
+            write!(f, "{:?}", self.0 as char)
+        } else {
+            write!(f, "0x{:02X}", self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErrorByte {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.0.is_ascii() {
+            serializer.serialize_char(self.0 as char)
+        } else {
+            serializer.collect_str(&format_args!("0x{:02X}", self.0))
+        }
+    }
+}
+
+impl ParseError {
+    /// Internal constructor for a [`ParseError::InvalidCharacter`] at a `usize` offset into a
+    /// _Payload_ (so in the _Prefix_ if less than two), saturating at `u32::MAX`.
+    pub(crate) fn invalid_character(index: usize, byte: u8) -> ParseError {
+        ParseError::invalid_character_in(index, byte, index < 2)
+    }
+
+    /// Internal constructor for a [`ParseError::InvalidCharacter`] at a `usize` offset into input
+    /// that may not start with the _Prefix_, saturating at `u32::MAX`.
+    pub(crate) fn invalid_character_in(index: usize, byte: u8, in_prefix: bool) -> ParseError {
+        ParseError::InvalidCharacter {
+            index: u32::try_from(index).unwrap_or(u32::MAX),
+            byte,
+            in_prefix,
+        }
+    }
+
+    /// Return the kind of this error, without the details.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            ParseError::InvalidValueStringLength { .. } => ErrorKind::InvalidValueStringLength,
+            ParseError::InvalidValueArrayLength { .. } => ErrorKind::InvalidValueArrayLength,
+            ParseError::InvalidCharacter { .. } => ErrorKind::InvalidCharacter,
+            ParseError::InvalidCheckDigit { .. } => ErrorKind::InvalidCheckDigit,
+            ParseError::IncorrectCheckDigit { .. } => ErrorKind::IncorrectCheckDigit,
+        }
+    }
+}
+
+/// Serialized the same as the equivalent [`Error`].
+#[cfg(feature = "serde")]
+impl serde::Serialize for ParseError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Error::from(*self).serialize(serializer)
+    }
+}
+
+impl ParseError {
+    /// Suggest the ISIN that was probably meant by the `input` that produced this error. See
+    /// [`Error::suggestion()`].
+    pub fn suggestion(&self, input: &str) -> Option<ISIN> {
+        Error::from(*self).suggestion(input)
+    }
+
+    /// Attach a copy of the input that caused this error. See [`Error::with_input()`].
+    pub fn with_input(self, input: &str) -> ErrorWithInput {
+        Error::from(self).with_input(input)
+    }
+}
+
+impl Debug for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&Error::from(*self), f)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&Error::from(*self), f)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The same diagnostic as for the equivalent [`Error`].
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for ParseError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Error::from(*self).code().map(|code| {
+            let code: Box<dyn Display> = Box::new(code.to_string());
+            code
+        })
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Error::from(*self).help().map(|help| {
+            let help: Box<dyn Display> = Box::new(help.to_string());
+            help
+        })
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let labels: Vec<_> = Error::from(*self).labels()?.collect();
+        Some(Box::new(labels.into_iter()))
+    }
+}
+
+/// All the ways building an ISIN from its parts could fail.
+#[non_exhaustive]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    /// The _Payload_ string length is not exactly 11 characters.
+    InvalidPayloadStringLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The _Payload_ byte array length is not exactly 11 bytes.
+    InvalidPayloadArrayLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The _Prefix_ string length is not exactly 2 characters.
+    InvalidPrefixStringLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The _Prefix_ byte array length is not exactly 2 bytes.
+    InvalidPrefixArrayLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The _Basic Code_ string length is not exactly 9 characters.
+    InvalidBasicCodeStringLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The _Basic Code_ byte array length is not exactly 9 bytes.
+    InvalidBasicCodeArrayLength {
+        /// The length we found, or 255 if it was longer
+        was: u8,
+    },
+    /// The input _Prefix_ is not two uppercase ASCII alphabetic characters.
+    InvalidPrefix {
+        /// The zero-based offset of the first invalid byte in the _Prefix_
+        index: u8,
+        /// The byte we found
+        byte: u8,
+    },
+    /// The input _Basic Code_ is not nine uppercase ASCII alphanumeric characters.
+    InvalidBasicCode {
+        /// The zero-based offset of the first invalid byte in the _Basic Code_
+        index: u8,
+        /// The byte we found
+        byte: u8,
+    },
+    /// No _Prefix_ was given when building an ISIN from separately-supplied parts.
+    MissingPrefix,
+    /// No _Basic Code_ was given when building an ISIN from separately-supplied parts.
+    MissingBasicCode,
+}
+
+impl From<BuildError> for Error {
+    fn from(err: BuildError) -> Self {
+        match err {
+            BuildError::InvalidPayloadStringLength { was } => {
+                Error::InvalidPayloadStringLength { was }
+            }
+            BuildError::InvalidPayloadArrayLength { was } => {
+                Error::InvalidPayloadArrayLength { was }
+            }
+            BuildError::InvalidPrefixStringLength { was } => {
+                Error::InvalidPrefixStringLength { was }
+            }
+            BuildError::InvalidPrefixArrayLength { was } => Error::InvalidPrefixArrayLength { was },
+            BuildError::InvalidBasicCodeStringLength { was } => {
+                Error::InvalidBasicCodeStringLength { was }
+            }
+            BuildError::InvalidBasicCodeArrayLength { was } => {
+                Error::InvalidBasicCodeArrayLength { was }
+            }
+            BuildError::InvalidPrefix { index, byte } => Error::InvalidPrefix { index, byte },
+            BuildError::InvalidBasicCode { index, byte } => Error::InvalidBasicCode { index, byte },
+            BuildError::MissingPrefix => Error::MissingPrefix,
+            BuildError::MissingBasicCode => Error::MissingBasicCode,
+        }
+    }
+}
+
+impl BuildError {
+    /// Return the kind of this error, without the details.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            BuildError::InvalidPayloadStringLength { .. } => ErrorKind::InvalidPayloadStringLength,
+            BuildError::InvalidPayloadArrayLength { .. } => ErrorKind::InvalidPayloadArrayLength,
+            BuildError::InvalidPrefixStringLength { .. } => ErrorKind::InvalidPrefixStringLength,
+            BuildError::InvalidPrefixArrayLength { .. } => ErrorKind::InvalidPrefixArrayLength,
+            BuildError::InvalidBasicCodeStringLength { .. } => {
+                ErrorKind::InvalidBasicCodeStringLength
+            }
+            BuildError::InvalidBasicCodeArrayLength { .. } => {
+                ErrorKind::InvalidBasicCodeArrayLength
+            }
+            BuildError::InvalidPrefix { .. } => ErrorKind::InvalidPrefix,
+            BuildError::InvalidBasicCode { .. } => ErrorKind::InvalidBasicCode,
+            BuildError::MissingPrefix => ErrorKind::MissingPrefix,
+            BuildError::MissingBasicCode => ErrorKind::MissingBasicCode,
+        }
+    }
+}
+
+/// Serialized the same as the equivalent [`Error`].
+#[cfg(feature = "serde")]
+impl serde::Serialize for BuildError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Error::from(*self).serialize(serializer)
+    }
+}
+
+impl Debug for BuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&Error::from(*self), f)
+    }
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&Error::from(*self), f)
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// An [`Error`] together with the input that caused it, as produced by [`Error::with_input()`]
+/// or [`ParseError::with_input()`].
+///
+/// ```
+/// let input = "US0378331006";
+/// let err = isin::parse(input).unwrap_err().with_input(input);
+/// assert_eq!(err.input(), "US0378331006");
+/// assert_eq!(
+///     err.to_string(),
+///     "incorrect check digit '6' when expecting '5' in \"US0378331006\""
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ErrorWithInput {
+    input: String,
+    error: Error,
+}
+
+impl ErrorWithInput {
+    /// The input that caused the error.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The error.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Discard the input, returning the error.
+    pub fn into_error(self) -> Error {
+        self.error
+    }
+}
+
+impl Display for ErrorWithInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} in {:?}", self.error, self.input)
+    }
+}
+
+impl std::error::Error for ErrorWithInput {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// The same diagnostic as for the [`Error`], with the input as the source code the labels point
+/// into.
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for ErrorWithInput {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.code()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.help()
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.input)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        self.error.labels()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BuildError, Error, ErrorKind, ErrorWithInput, ParseError};
+    use crate::CheckDigit;
+
+    #[test]
+    fn render_display() {
+        let errors = [
+            (
+                Error::InvalidValueStringLength { was: 10 },
+                "invalid value string length 10 characters when expecting 12",
+            ),
+            (
+                Error::InvalidValueArrayLength { was: 10 },
+                "invalid value array length 10 bytes when expecting 12",
+            ),
+            (
+                Error::InvalidPayloadStringLength { was: 8 },
+                "invalid Payload string length 8 characters when expecting 11",
+            ),
+            (
+                Error::InvalidPayloadArrayLength { was: 8 },
+                "invalid Payload array length 8 bytes when expecting 11",
+            ),
+            (
+                Error::InvalidPrefixStringLength { was: 1 },
+                "invalid Prefix string length 1 characters when expecting 2",
+            ),
+            (
+                Error::InvalidPrefixArrayLength { was: 1 },
+                "invalid Prefix array length 1 bytes when expecting 2",
+            ),
+            (
+                Error::InvalidBasicCodeStringLength { was: 8 },
+                "invalid Basic Code string length 8 characters when expecting 9",
+            ),
+            (
+                Error::InvalidBasicCodeArrayLength { was: 8 },
+                "invalid Basic Code array length 8 bytes when expecting 9",
+            ),
+            (
+                Error::InvalidPrefix {
+                    index: 1,
+                    byte: b'{'
+                },
+                "invalid character '{' at index 1 of the prefix, which must be two uppercase ASCII alphabetic characters",
+            ),
+            (
+                Error::InvalidBasicCode {
+                    index: 8,
+                    byte: b'{'
+                },
+                "invalid character '{' at index 8 of the basic code, which must be nine uppercase ASCII alphanumeric characters",
+            ),
+            (
+                Error::InvalidCharacter {
+                    index: 3,
+                    byte: b'-',
+                    in_prefix: false
+                },
+                "invalid character '-' at index 3",
+            ),
+            (
+                Error::InvalidCharacter {
+                    index: 2,
+                    byte: 0xc3,
+                    in_prefix: false
+                },
+                "invalid character 0xC3 at index 2",
+            ),
+            (
+                Error::InvalidCheckDigit { was: b':' },
+                "check digit ':' is not one ASCII decimal digit",
+            ),
+            (
+                Error::IncorrectCheckDigit {
+                    was: CheckDigit::from_value(5).unwrap(),
+                    expected: CheckDigit::from_value(6).unwrap(),
+                },
+                "incorrect check digit '5' when expecting '6'",
+            ),
+            (Error::MissingPrefix, "missing Prefix"),
+            (Error::MissingBasicCode, "missing Basic Code"),
+            (
+                Error::UnallocatedPrefix { was: *b"QQ" },
+                "prefix \"QQ\" is not an allocated country code or special prefix",
+            ),
+        ];
+
+        for (error, expected) in errors.iter() {
+            assert_eq!(format!("{}", error), *expected);
+        }
+    }
+
+    #[test]
+    fn render_debug() {
+        let errors = [
+            (
+                Error::InvalidValueStringLength { was: 10 },
+                "InvalidValueStringLength { was: 10 }",
+            ),
+            (
+                Error::InvalidValueArrayLength { was: 10 },
+                "InvalidValueArrayLength { was: 10 }",
+            ),
+            (
+                Error::InvalidPayloadStringLength { was: 8 },
+                "InvalidPayloadStringLength { was: 8 }",
+            ),
+            (
+                Error::InvalidPayloadArrayLength { was: 8 },
+                "InvalidPayloadArrayLength { was: 8 }",
+            ),
+            (
+                Error::InvalidPrefixArrayLength { was: 1 },
+                "InvalidPrefixArrayLength { was: 1 }",
+            ),
+            (
+                Error::InvalidBasicCodeArrayLength { was: 8 },
+                "InvalidBasicCodeArrayLength { was: 8 }",
+            ),
+            (
+                Error::InvalidPrefix {
+                    index: 1,
+                    byte: b'{',
+                },
+                "InvalidPrefix { index: 1, byte: '{' }",
+            ),
+            (
+                Error::InvalidBasicCode {
+                    index: 8,
+                    byte: b'{',
+                },
+                "InvalidBasicCode { index: 8, byte: '{' }",
+            ),
+            (
+                Error::InvalidCharacter {
+                    index: 3,
+                    byte: b'-',
+                    in_prefix: false,
+                },
+                "InvalidCharacter { index: 3, byte: '-', in_prefix: false }",
+            ),
+            (
+                Error::InvalidCharacter {
+                    index: 2,
+                    byte: 0xc3,
+                    in_prefix: false,
+                },
+                "InvalidCharacter { index: 2, byte: 0xC3, in_prefix: false }",
+            ),
+            (
+                Error::InvalidCheckDigit { was: b':' },
+                "InvalidCheckDigit { was: ':' }",
+            ),
+            (
+                Error::IncorrectCheckDigit {
+                    was: CheckDigit::from_value(5).unwrap(),
+                    expected: CheckDigit::from_value(6).unwrap(),
+                },
+                "IncorrectCheckDigit { was: '5', expected: '6' }",
+            ),
+            (Error::MissingPrefix, "MissingPrefix"),
+            (Error::MissingBasicCode, "MissingBasicCode"),
+            (
+                Error::UnallocatedPrefix { was: *b"QQ" },
+                "UnallocatedPrefix { was: \"QQ\" }",
+            ),
+        ];
+
+        for (error, expected) in errors.iter() {
+            assert_eq!(format!("{:?}", error), *expected);
+        }
+    }
+
+    #[test]
+    fn convert_parse_error() {
+        let err = ParseError::IncorrectCheckDigit {
+            was: CheckDigit::from_value(5).unwrap(),
+            expected: CheckDigit::from_value(6).unwrap(),
+        };
+        assert_eq!(
+            format!("{}", err),
+            "incorrect check digit '5' when expecting '6'"
+        );
+        assert_eq!(
+            format!("{:?}", err),
+            "IncorrectCheckDigit { was: '5', expected: '6' }"
+        );
+        assert_eq!(
+            Error::from(err),
+            Error::IncorrectCheckDigit {
+                was: CheckDigit::from_value(5).unwrap(),
+                expected: CheckDigit::from_value(6).unwrap()
+            }
+        );
+    }
+
+    #[test]
+    fn convert_build_error() {
+        let err = BuildError::InvalidPrefix {
+            index: 1,
+            byte: b'{',
+        };
+        assert_eq!(
+            format!("{}", err),
+            "invalid character '{' at index 1 of the prefix, which must be two uppercase ASCII alphabetic characters"
+        );
+        assert_eq!(
+            format!("{:?}", err),
+            "InvalidPrefix { index: 1, byte: '{' }"
+        );
+        assert_eq!(
+            Error::from(err),
+            Error::InvalidPrefix {
+                index: 1,
+                byte: b'{'
+            }
+        );
+    }
+
+    #[test]
+    fn kinds() {
+        let err = ParseError::InvalidCharacter {
+            index: 0,
+            byte: b'u',
+            in_prefix: true,
+        };
+        assert_eq!(err.kind(), ErrorKind::InvalidCharacter);
+        assert_eq!(Error::from(err).kind(), ErrorKind::InvalidCharacter);
+
+        let err = BuildError::MissingBasicCode;
+        assert_eq!(err.kind(), ErrorKind::MissingBasicCode);
+        assert_eq!(Error::from(err).kind(), ErrorKind::MissingBasicCode);
+
+        let err = crate::parse("US0378331006").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IncorrectCheckDigit);
+        assert_eq!(err.kind().as_str(), "IncorrectCheckDigit");
+        assert_eq!(err.kind().to_string(), "IncorrectCheckDigit");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() {
+        let errors = [
+            (
+                Error::InvalidValueStringLength { was: 13 },
+                r#"{"InvalidValueStringLength":{"was":13}}"#,
+            ),
+            (
+                Error::InvalidPrefix {
+                    index: 1,
+                    byte: 0xff,
+                },
+                r#"{"InvalidPrefix":{"index":1,"byte":"0xFF"}}"#,
+            ),
+            (
+                Error::InvalidBasicCode {
+                    index: 8,
+                    byte: b'-',
+                },
+                r#"{"InvalidBasicCode":{"index":8,"byte":"-"}}"#,
+            ),
+            (
+                Error::InvalidCharacter {
+                    index: 3,
+                    byte: b'-',
+                    in_prefix: false,
+                },
+                r#"{"InvalidCharacter":{"index":3,"byte":"-","in_prefix":false}}"#,
+            ),
+            (
+                Error::InvalidCheckDigit { was: b'X' },
+                r#"{"InvalidCheckDigit":{"was":"X"}}"#,
+            ),
+            (
+                Error::IncorrectCheckDigit {
+                    was: CheckDigit::from_value(6).unwrap(),
+                    expected: CheckDigit::from_value(5).unwrap(),
+                },
+                r#"{"IncorrectCheckDigit":{"was":"6","expected":"5"}}"#,
+            ),
+            (Error::MissingPrefix, r#""MissingPrefix""#),
+        ];
+
+        for (error, expected) in errors.iter() {
+            assert_eq!(serde_json::to_string(error).unwrap(), *expected);
+        }
+
+        let err = crate::parse("US-378331005").unwrap_err();
+        assert_eq!(
+            serde_json::to_string(&err).unwrap(),
+            r#"{"InvalidCharacter":{"index":2,"byte":"-","in_prefix":false}}"#
+        );
+        let err = BuildError::MissingBasicCode;
+        assert_eq!(
+            serde_json::to_string(&err).unwrap(),
+            r#""MissingBasicCode""#
+        );
+    }
+
+    #[cfg(feature = "miette")]
+    #[test]
+    fn diagnostic() {
+        use miette::Diagnostic;
+
+        let err = crate::parse("US03783310-5").unwrap_err();
+        assert_eq!(
+            err.code().map(|c| c.to_string()),
+            Some("isin::InvalidCharacter".to_string())
+        );
+        assert!(err.help().is_some());
+        let labels: Vec<_> = err.labels().unwrap().collect();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].offset(), 10);
+        assert_eq!(labels[0].len(), 1);
+
+        let err = crate::parse("US0378331é5").unwrap_err();
+        let labels: Vec<_> = err.labels().unwrap().collect();
+        assert_eq!((labels[0].offset(), labels[0].len()), (9, 2));
+
+        let err = Error::from(crate::parse("US0378331006").unwrap_err());
+        assert_eq!(
+            err.help().map(|h| h.to_string()),
+            Some("the correct Check Digit is '5'".to_string())
+        );
+        assert!(err.labels().is_none());
+
+        // The help names the field of the character, not of its index in the original input.
+        let options = crate::ParseOptions::loose().allow_separators(true);
+        let prefix_help = Some("the Prefix (the first 2 characters) must be uppercase letters");
+        let err = options.parse(" u5-037833100-5").unwrap_err();
+        assert_eq!(err.help().map(|h| h.to_string()).as_deref(), prefix_help);
+        let err = options.parse("  us-0378_3100-5").unwrap_err();
+        assert_ne!(err.help().map(|h| h.to_string()).as_deref(), prefix_help);
+        let err = crate::parse_with_separators("U-$037833100-5", "-").unwrap_err();
+        assert_eq!(err.help().map(|h| h.to_string()).as_deref(), prefix_help);
+
+        let err = crate::parse("US03783310-5")
+            .unwrap_err()
+            .with_input("US03783310-5");
+        assert!(err.source_code().is_some());
+        assert_eq!(err.labels().unwrap().count(), 1);
+
+        let err = Error::MissingPrefix;
+        assert!(err.help().is_none());
+        assert_eq!(
+            err.code().map(|c| c.to_string()),
+            Some("isin::MissingPrefix".to_string())
+        );
+    }
+
+    #[test]
+    fn suggestions() {
+        let apple = crate::parse("US0378331005").unwrap();
+        let cases = [
+            ("US0378331001", Some(apple)),
+            ("US3078331005", Some(apple)),
+            ("US0738331005", Some(apple)),
+            ("US0387331005", None),
+            ("Us0378331005", Some(apple)),
+            ("US03783310-5", None),
+            ("US037833100A", None),
+            ("US037833100", None),
+        ];
+
+        for (input, expected) in cases {
+            let err = crate::parse(input).unwrap_err();
+            assert_eq!(err.suggestion(input), expected, "{input}");
+        }
+
+        assert_eq!(Error::MissingPrefix.suggestion("US0378331005"), None);
+    }
+
+    #[test]
+    fn suggestion_validates_input_not_from_error() {
+        let err = Error::IncorrectCheckDigit {
+            was: CheckDigit::from_value(1).unwrap(),
+            expected: CheckDigit::from_value(5).unwrap(),
+        };
+        assert_eq!(err.suggestion("us0378331001"), None);
+        assert_eq!(err.suggestion("US03783310\u{e9}"), None);
+        assert_eq!(
+            err.suggestion("US0378331001"),
+            Some(crate::parse("US0378331005").unwrap())
+        );
+    }
+
+    #[test]
+    fn with_input() {
+        let inputs = ["US0378331005", "US0378331006", "us0378331005"];
+        let failures: Vec<ErrorWithInput> = inputs
+            .iter()
+            .filter_map(|input| crate::parse(input).map_err(|e| e.with_input(input)).err())
+            .collect();
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].input(), "US0378331006");
+        assert_eq!(failures[0].error().kind(), ErrorKind::IncorrectCheckDigit);
+        assert_eq!(failures[1].input(), "us0378331005");
+        assert_eq!(
+            failures[1].to_string(),
+            "invalid character 'u' at index 0 in \"us0378331005\""
+        );
+        assert_eq!(
+            failures[1].clone().into_error(),
+            Error::InvalidCharacter {
+                index: 0,
+                byte: b'u',
+                in_prefix: true
+            }
+        );
+        let source = std::error::Error::source(&failures[1]).unwrap();
+        assert_eq!(source.to_string(), "invalid character 'u' at index 0");
+    }
+
+    #[test]
+    fn small_and_copy() {
+        fn is_copy<T: Copy>() {}
+        is_copy::<Error>();
+        is_copy::<ParseError>();
+        is_copy::<BuildError>();
+
+        assert_eq!(std::mem::size_of::<Error>(), 8);
+        assert!(std::mem::size_of::<ParseError>() <= 8);
+        assert!(std::mem::size_of::<BuildError>() <= 4);
+    }
+}
@@ -0,0 +1,82 @@
+//! # isin::field
+//!
+//! Helpers for reading ISINs from fixed-width fields in flat-file records, where the ISIN is
+//! typically space-padded to the width of the field and the field may be blank.
+
+use crate::{parse_bytes, ParseError, ISIN};
+
+/// Parse the ISIN in the fixed-width field of `width` bytes at the start of `record`.
+///
+/// Leading and trailing spaces (and NUL bytes, which some writers use for padding) are ignored,
+/// and anything else must be a valid ISIN as for `parse()`. Returns `None` if the field is blank,
+/// and an `InvalidValueArrayLength` error if `record` is shorter than `width`.
+///
+/// ```
+/// let record = b"US0378331005    APPLE INC";
+/// let isin = isin::field::parse_fixed(record, 16).unwrap().unwrap();
+/// assert_eq!(isin.to_string(), "US0378331005");
+///
+/// assert_eq!(isin::field::parse_fixed(b"                ", 16), None);
+/// ```
+pub fn parse_fixed(record: &[u8], width: usize) -> Option<Result<ISIN, ParseError>> {
+    let field = match record.get(0..width) {
+        Some(field) => field,
+        None => {
+            return Some(Err(ParseError::InvalidValueArrayLength {
+                was: crate::error::error_len(record.len()),
+            }));
+        }
+    };
+
+    let is_padding = |b: &u8| *b == b' ' || *b == 0;
+    let start = field.iter().position(|b| !is_padding(b))?;
+    let end = field.iter().rposition(|b| !is_padding(b))? + 1;
+
+    Some(parse_bytes(&field[start..end]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn padded_fields() {
+        let apple = parse("US0378331005").unwrap();
+        assert_eq!(parse_fixed(b"US0378331005", 12), Some(Ok(apple)));
+        assert_eq!(parse_fixed(b"US0378331005    ", 16), Some(Ok(apple)));
+        assert_eq!(parse_fixed(b"  US0378331005  ", 16), Some(Ok(apple)));
+        assert_eq!(parse_fixed(b"US0378331005\0\0\0\0", 16), Some(Ok(apple)));
+        assert_eq!(
+            parse_fixed(b"US0378331005    NEXT FIELD", 16),
+            Some(Ok(apple))
+        );
+    }
+
+    #[test]
+    fn blank_fields() {
+        assert_eq!(parse_fixed(b"            ", 12), None);
+        assert_eq!(parse_fixed(b"\0\0\0\0\0\0\0\0\0\0\0\0", 12), None);
+        assert_eq!(parse_fixed(b"", 0), None);
+    }
+
+    #[test]
+    fn bad_fields() {
+        assert_eq!(
+            parse_fixed(b"US0378331005", 16),
+            Some(Err(ParseError::InvalidValueArrayLength { was: 12 }))
+        );
+        assert_eq!(
+            parse_fixed(b"US037833100     ", 16),
+            Some(Err(ParseError::InvalidValueArrayLength { was: 11 }))
+        );
+        assert_eq!(
+            parse_fixed(b"US0378331005XXXX", 16),
+            Some(Err(ParseError::InvalidValueArrayLength { was: 16 }))
+        );
+        assert_eq!(
+            parse_fixed(b"US0378331005XXXX", 12),
+            Some(Ok(parse("US0378331005").unwrap()))
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! # isin::fixtures
+//!
+//! Well-known ISINs as constants, for the test suites of code that handles ISINs. Each constant is
+//! checked when the crate is compiled, so unlike a string literal in a test it can not have a typo:
+//!
+//! ```
+//! use isin::fixtures::{AAPL, ALLIANZ_BOND};
+//!
+//! assert_eq!(AAPL.to_string(), "US0378331005");
+//! assert_eq!(ALLIANZ_BOND.prefix(), "DE");
+//! assert_eq!(isin::parse("US0378331005"), Ok(AAPL));
+//! ```
+
+use crate::checksum::is_valid_isin_bytes;
+use crate::ISIN;
+
+/// Build an ISIN at compile time, failing the compilation if it is not valid.
+const fn fixture(value: &[u8; 12]) -> ISIN {
+    assert!(is_valid_isin_bytes(value));
+    ISIN(*value)
+}
+
+/// Apple Inc. common stock.
+pub const AAPL: ISIN = fixture(b"US0378331005");
+
+/// Microsoft Corporation common stock.
+pub const MSFT: ISIN = fixture(b"US5949181045");
+
+/// Amazon.com, Inc. common stock.
+pub const AMZN: ISIN = fixture(b"US0231351067");
+
+/// SAP SE ordinary shares.
+pub const SAP: ISIN = fixture(b"DE0007164600");
+
+/// Nestlé S.A. registered shares.
+pub const NESTLE: ISIN = fixture(b"CH0038863350");
+
+/// Toyota Motor Corporation shares.
+pub const TOYOTA: ISIN = fixture(b"JP3633400001");
+
+/// Hitachi, Ltd. shares, from Annex C of ISO 6166.
+pub const HITACHI: ISIN = fixture(b"JP3788600009");
+
+/// Allianz Finance II 5 3/8% bond without expiration date, from Annex C of ISO 6166.
+pub const ALLIANZ_BOND: ISIN = fixture(b"DE000A0GNPZ3");
+
+/// The IBEX 35 index, from Annex C of ISO 6166.
+pub const IBEX_35: ISIN = fixture(b"ES0SI0000005");
+
+/// Every fixture in this module.
+pub const ALL: [ISIN; 9] = [
+    AAPL,
+    MSFT,
+    AMZN,
+    SAP,
+    NESTLE,
+    TOYOTA,
+    HITACHI,
+    ALLIANZ_BOND,
+    IBEX_35,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn fixtures_are_valid() {
+        for isin in ALL {
+            assert_eq!(parse(AsRef::<str>::as_ref(&isin)), Ok(isin));
+        }
+        let mut sorted = ALL;
+        sorted.sort();
+        assert!(sorted.windows(2).all(|w| w[0] != w[1]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_fixture_panics() {
+        let _ = fixture(std::hint::black_box(b"US0378331006"));
+    }
+}
@@ -0,0 +1,234 @@
+//! # isin::fst
+//!
+//! A compact, immutable set of ISINs stored as a [finite state transducer](https://docs.rs/fst),
+//! for holding very large universes of identifiers in little memory.
+//!
+//! An FST shares the common prefixes and suffixes of its keys. ISINs issued by the same agency
+//! share long prefixes, so a real universe of ISINs usually takes fewer than the twelve bytes per
+//! ISIN of a sorted array, and far less than a `HashSet<ISIN>`. Membership tests and _Prefix_
+//! queries walk the transducer directly without decompressing it.
+//!
+//! A set is built from ISINs in ascending order, either in memory or streamed to a writer such
+//! as a file. Because the set can be read from any bytes, a file written this way can be
+//! memory-mapped and used without reading it all in:
+//!
+//! ```
+//! use isin::fst::IsinFstSet;
+//!
+//! let isins = ["DE000A0GNPZ3", "US0378331005", "US5949181045"].map(|s| isin::parse(s).unwrap());
+//!
+//! let mut bytes = Vec::new();
+//! IsinFstSet::write_sorted_into(&mut bytes, isins).unwrap();
+//!
+//! // `bytes` could be any `AsRef<[u8]>`, such as a memory-mapped file.
+//! let set = IsinFstSet::new(bytes).unwrap();
+//! assert!(set.contains(&isins[1]));
+//! assert_eq!(set.with_prefix("US").count(), 2);
+//! ```
+//!
+//! This module is only available with the `fst` feature enabled.
+
+use std::io;
+
+use ::fst::automaton::{Automaton, Str};
+use ::fst::{IntoStreamer, Set, SetBuilder, Streamer};
+
+use crate::{parse_bytes, ISIN};
+
+/// An immutable set of ISINs stored as a finite state transducer over the bytes `D`. See the
+/// [module docs](self).
+#[derive(Clone)]
+pub struct IsinFstSet<D = Vec<u8>>(Set<D>);
+
+impl<D: AsRef<[u8]>> std::fmt::Debug for IsinFstSet<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl IsinFstSet<Vec<u8>> {
+    /// Build a set in memory from ISINs in ascending order. Repeats are ignored, but an ISIN less
+    /// than the one before it is an error.
+    pub fn from_sorted_iter<I>(isins: I) -> Result<IsinFstSet<Vec<u8>>, ::fst::Error>
+    where
+        I: IntoIterator<Item = ISIN>,
+    {
+        let mut builder = SetBuilder::memory();
+        for isin in isins {
+            builder.insert(isin.as_bytes())?;
+        }
+        Ok(IsinFstSet(builder.into_set()))
+    }
+
+    /// Write a set to `writer` from ISINs in ascending order, without holding them in memory, so
+    /// it can later be read with [`new()`](Self::new). Repeats are ignored, but an ISIN less than
+    /// the one before it is an error.
+    pub fn write_sorted_into<W, I>(writer: W, isins: I) -> Result<(), ::fst::Error>
+    where
+        W: io::Write,
+        I: IntoIterator<Item = ISIN>,
+    {
+        let mut builder = SetBuilder::new(writer)?;
+        for isin in isins {
+            builder.insert(isin.as_bytes())?;
+        }
+        builder.finish()
+    }
+}
+
+impl<D: AsRef<[u8]>> IsinFstSet<D> {
+    /// Use the bytes of a set written by [`write_sorted_into()`](Self::write_sorted_into) or
+    /// taken from [`into_inner()`](Self::into_inner).
+    ///
+    /// Every key is checked to be a valid ISIN, which means reading the whole set once. Data that
+    /// is not an FST, or holds a key that is not a valid ISIN, is an error.
+    pub fn new(data: D) -> Result<IsinFstSet<D>, ::fst::Error> {
+        let set = Set::new(data)?;
+        let mut keys = set.stream();
+        while let Some(key) = keys.next() {
+            if parse_bytes(key).is_err() {
+                return Err(::fst::Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "key is not a valid ISIN",
+                )));
+            }
+        }
+        drop(keys);
+        Ok(IsinFstSet(set))
+    }
+
+    /// Return true if the ISIN is in the set.
+    pub fn contains(&self, isin: &ISIN) -> bool {
+        self.0.contains(isin.as_bytes())
+    }
+
+    /// Return the number of ISINs in the set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return true if the set contains no ISINs.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the ISINs in the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = ISIN> + '_ {
+        isins(self.0.stream())
+    }
+
+    /// Iterate over the ISINs in the set that start with `prefix`, in ascending order. The
+    /// prefix can be a _Prefix_ such as `"US"`, or any other leading part of an ISIN.
+    pub fn with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = ISIN> + 'a {
+        isins(self.0.search(Str::new(prefix).starts_with()).into_stream())
+    }
+
+    /// Return the bytes of the set, as written by
+    /// [`write_sorted_into()`](IsinFstSet::write_sorted_into).
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_fst().as_bytes()
+    }
+
+    /// Return the underlying bytes of the set.
+    pub fn into_inner(self) -> D {
+        self.0.into_fst().into_inner()
+    }
+}
+
+/// Convert a stream of keys, all known to be valid ISINs, to an iterator of ISINs.
+fn isins<'a, S>(mut stream: S) -> impl Iterator<Item = ISIN> + 'a
+where
+    S: for<'s> Streamer<'s, Item = &'s [u8]> + 'a,
+{
+    // The keys were checked when the set was made, so none are skipped
+    std::iter::from_fn(move || stream.next().map(parse_bytes)).filter_map(Result::ok)
+}
+
+impl FromIterator<ISIN> for IsinFstSet<Vec<u8>> {
+    /// Build a set in memory from ISINs in any order, with repeats. They are sorted first, so
+    /// [`from_sorted_iter()`](IsinFstSet::from_sorted_iter) needs less memory.
+    fn from_iter<I: IntoIterator<Item = ISIN>>(iter: I) -> Self {
+        let mut isins: Vec<ISIN> = iter.into_iter().collect();
+        isins.sort_unstable();
+        isins.dedup();
+        let mut builder = SetBuilder::memory();
+        // This cannot panic: building only fails for keys out of order or repeated, which sorting
+        // and deduplicating rule out, or for errors writing, which a `Vec` never has.
+        builder
+            .extend_iter(isins.iter().map(|isin| isin.as_bytes()))
+            .expect("sorted distinct keys");
+        IsinFstSet(builder.into_set())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use proptest::prelude::*;
+
+    fn isins(values: &[&str]) -> Vec<ISIN> {
+        values.iter().map(|s| parse(s).unwrap()).collect()
+    }
+
+    #[test]
+    fn membership_and_prefixes() {
+        let values = isins(&[
+            "DE000A0GNPZ3",
+            "JP3788600009",
+            "US0378331005",
+            "US5949181045",
+        ]);
+        let set = IsinFstSet::from_sorted_iter(values.clone()).unwrap();
+
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(&values[2]));
+        assert!(!set.contains(&parse("GB00BF0FCW58").unwrap()));
+        assert_eq!(set.iter().collect::<Vec<_>>(), values);
+        assert_eq!(set.with_prefix("US").collect::<Vec<_>>(), values[2..]);
+        assert_eq!(set.with_prefix("US03").collect::<Vec<_>>(), values[2..3]);
+        assert_eq!(set.with_prefix("GB").count(), 0);
+        assert_eq!(set.with_prefix("").count(), 4);
+    }
+
+    #[test]
+    fn unsorted_input() {
+        let values = isins(&["US0378331005", "DE000A0GNPZ3"]);
+        assert!(IsinFstSet::from_sorted_iter(values.clone()).is_err());
+        let repeated = isins(&["US0378331005", "US0378331005"]);
+        assert_eq!(IsinFstSet::from_sorted_iter(repeated).unwrap().len(), 1);
+
+        let set: IsinFstSet = values.iter().copied().chain(values.clone()).collect();
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            isins(&["DE000A0GNPZ3", "US0378331005"])
+        );
+    }
+
+    #[test]
+    fn reject_invalid_data() {
+        assert!(IsinFstSet::new(&b"not an fst"[..]).is_err());
+
+        let not_isins = Set::from_iter(["US0378331005", "US0378331006"]).unwrap();
+        assert!(IsinFstSet::new(not_isins.as_fst().as_bytes()).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(payloads in proptest::collection::btree_set("[A-Z]{2}[0-9A-Z]{9}", 0..50)) {
+            let values: Vec<ISIN> = payloads
+                .iter()
+                .map(|p| crate::build_from_payload(p).unwrap())
+                .collect();
+            let mut bytes = Vec::new();
+            IsinFstSet::write_sorted_into(&mut bytes, values.clone()).unwrap();
+            let set = IsinFstSet::new(&bytes[..]).unwrap();
+            prop_assert_eq!(set.len(), values.len());
+            prop_assert_eq!(set.iter().collect::<Vec<_>>(), values.clone());
+            prop_assert_eq!(set.as_bytes(), &bytes[..]);
+            for isin in &values {
+                prop_assert!(set.contains(isin));
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! # isin::generator
+//!
+//! Synthetic ISINs resembling production data, for performance testing and demo datasets. A
+//! [`Generator`] draws the _Prefix_ of each ISIN according to configurable weights, and the _Basic
+//! Code_ in a [`BasicCodeStyle`] chosen per _Prefix_, always with the correct _Check Digit_:
+//!
+//! ```
+//! use isin::generator::{BasicCodeStyle, Generator};
+//! use isin::Prefix;
+//! use rand::SeedableRng;
+//!
+//! let us = Prefix::parse("US").unwrap();
+//! let generator = Generator::new()
+//!     .prefix(us, 3)
+//!     .prefix(Prefix::parse("DE").unwrap(), 1)
+//!     .style(us, BasicCodeStyle::Numeric);
+//!
+//! let mut rng = rand::rngs::SmallRng::seed_from_u64(7);
+//! for _ in 0..100 {
+//!     let isin = generator.generate(&mut rng);
+//!     assert!(isin.prefix() == "US" || isin.prefix() == "DE");
+//!     if isin.prefix() == "US" {
+//!         assert!(isin.basic_code().bytes().all(|b| b.is_ascii_digit()));
+//!     }
+//! }
+//! ```
+//!
+//! [`Generator::realistic()`] is a ready-made configuration whose _Prefixes_ roughly follow the
+//! share of each country in the ISINs issued worldwide.
+//!
+//! This module is only available with the `rand` feature enabled.
+
+use rand::distributions::Distribution;
+use rand::Rng;
+
+use crate::{BasicCode, Prefix, BASIC_CODE_COUNT, ISIN};
+
+/// The characters used in the generated _Basic Codes_ of a _Prefix_.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BasicCodeStyle {
+    /// Any nine letters or digits, uniformly, as for `DE` or `GB` ISINs.
+    #[default]
+    Alphanumeric,
+    /// Nine digits, as for the national numbers of `US`-like and `CH`-like ISINs.
+    Numeric,
+}
+
+impl BasicCodeStyle {
+    /// Generate a _Basic Code_ in this style.
+    fn generate<R: Rng + ?Sized>(self, rng: &mut R) -> BasicCode {
+        match self {
+            BasicCodeStyle::Alphanumeric => {
+                BasicCode::from_value(rng.gen_range(0..BASIC_CODE_COUNT)).expect("value in range")
+            }
+            BasicCodeStyle::Numeric => {
+                let mut bb = [0u8; 9];
+                for b in bb.iter_mut() {
+                    *b = b'0' + rng.gen_range(0..10);
+                }
+                BasicCode::from_bytes_valid(bb)
+            }
+        }
+    }
+}
+
+/// A _Prefix_ with its weight and style.
+#[derive(Clone, Debug)]
+struct Entry {
+    prefix: Prefix,
+    weight: u32,
+    style: Option<BasicCodeStyle>,
+}
+
+/// A configurable source of random valid ISINs. See the [module documentation](self).
+///
+/// With no _Prefix_ weighted, every _Prefix_ is equally likely. Otherwise a _Prefix_ is drawn with
+/// probability proportional to its weight, and _Prefixes_ not weighted are never drawn.
+#[derive(Clone, Debug, Default)]
+pub struct Generator {
+    /// The configured _Prefixes_, in the order first configured
+    entries: Vec<Entry>,
+    default_style: BasicCodeStyle,
+}
+
+impl Generator {
+    /// A generator with no _Prefix_ weighted, and [`BasicCodeStyle::Alphanumeric`] for every
+    /// _Prefix_.
+    pub fn new() -> Generator {
+        Generator::default()
+    }
+
+    /// A generator whose _Prefixes_ and styles approximate the ISINs issued worldwide: a large
+    /// share for `US` and `DE` (with its many structured products), then other major markets and
+    /// `XS` for international securities. The weights are illustrative only, not statistics.
+    pub fn realistic() -> Generator {
+        use BasicCodeStyle::{Alphanumeric, Numeric};
+
+        const WEIGHTS: [(&str, u32, BasicCodeStyle); 20] = [
+            ("US", 250, Numeric),
+            ("DE", 200, Alphanumeric),
+            ("CH", 80, Numeric),
+            ("XS", 80, Alphanumeric),
+            ("GB", 60, Alphanumeric),
+            ("FR", 60, Numeric),
+            ("JP", 50, Numeric),
+            ("LU", 40, Numeric),
+            ("CA", 40, Alphanumeric),
+            ("NL", 30, Numeric),
+            ("IT", 30, Numeric),
+            ("AU", 30, Numeric),
+            ("SE", 20, Numeric),
+            ("HK", 20, Numeric),
+            ("KY", 20, Alphanumeric),
+            ("ES", 20, Alphanumeric),
+            ("IE", 20, Alphanumeric),
+            ("CN", 20, Numeric),
+            ("KR", 20, Numeric),
+            ("EU", 10, Numeric),
+        ];
+
+        WEIGHTS
+            .iter()
+            .fold(Generator::new(), |generator, &(prefix, weight, style)| {
+                let prefix = Prefix::parse(prefix).expect("valid prefix");
+                generator.prefix(prefix, weight).style(prefix, style)
+            })
+    }
+
+    /// Find or add the entry of a _Prefix_.
+    fn entry(&mut self, prefix: Prefix) -> &mut Entry {
+        let i = match self.entries.iter().position(|e| e.prefix == prefix) {
+            Some(i) => i,
+            None => {
+                self.entries.push(Entry {
+                    prefix,
+                    weight: 0,
+                    style: None,
+                });
+                self.entries.len() - 1
+            }
+        };
+        &mut self.entries[i]
+    }
+
+    /// Set the weight of a _Prefix_, replacing any weight set before. A weight of zero means the
+    /// _Prefix_ is not weighted.
+    pub fn prefix(mut self, prefix: Prefix, weight: u32) -> Generator {
+        self.entry(prefix).weight = weight;
+        self
+    }
+
+    /// Set the style of the _Basic Codes_ generated with a _Prefix_.
+    pub fn style(mut self, prefix: Prefix, style: BasicCodeStyle) -> Generator {
+        self.entry(prefix).style = Some(style);
+        self
+    }
+
+    /// Set the style of the _Basic Codes_ generated with any _Prefix_ without a style of its own.
+    pub fn default_style(mut self, style: BasicCodeStyle) -> Generator {
+        self.default_style = style;
+        self
+    }
+
+    /// Return the style of the _Basic Codes_ generated with a _Prefix_.
+    pub fn style_of(&self, prefix: Prefix) -> BasicCodeStyle {
+        self.entries
+            .iter()
+            .find(|e| e.prefix == prefix)
+            .and_then(|e| e.style)
+            .unwrap_or(self.default_style)
+    }
+
+    /// Draw a _Prefix_ according to the weights.
+    fn generate_prefix<R: Rng + ?Sized>(&self, rng: &mut R) -> Prefix {
+        let total: u64 = self.entries.iter().map(|e| e.weight as u64).sum();
+        if total == 0 {
+            let rank = rng.gen_range(0..26 * 26u16);
+            return Prefix::from_bytes_valid([b'A' + (rank / 26) as u8, b'A' + (rank % 26) as u8]);
+        }
+
+        let mut pick = rng.gen_range(0..total);
+        for e in &self.entries {
+            if pick < e.weight as u64 {
+                return e.prefix;
+            }
+            pick -= e.weight as u64;
+        }
+        unreachable!("pick is less than the total weight")
+    }
+
+    /// Generate a random valid ISIN using `rng`.
+    pub fn generate<R: Rng + ?Sized>(&self, rng: &mut R) -> ISIN {
+        let prefix = self.generate_prefix(rng);
+        let basic_code = self.style_of(prefix).generate(rng);
+        ISIN::from_parts(prefix, basic_code)
+    }
+}
+
+impl Distribution<ISIN> for Generator {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> ISIN {
+        self.generate(rng)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use std::collections::HashMap;
+
+    fn prefix(s: &str) -> Prefix {
+        Prefix::parse(s).unwrap()
+    }
+
+    #[test]
+    fn weights_are_respected() {
+        let generator = Generator::new()
+            .prefix(prefix("US"), 3)
+            .prefix(prefix("DE"), 1)
+            .prefix(prefix("GB"), 5)
+            .prefix(prefix("GB"), 0);
+        let mut rng = SmallRng::seed_from_u64(2349);
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for isin in (&mut rng).sample_iter(&generator).take(4000) {
+            assert_eq!(parse(AsRef::<str>::as_ref(&isin)), Ok(isin));
+            *counts.entry(isin.prefix().to_owned()).or_default() += 1;
+        }
+        assert_eq!(counts.len(), 2);
+        let ratio = counts["US"] as f64 / counts["DE"] as f64;
+        assert!((2.5..3.5).contains(&ratio), "US:DE ratio {ratio}");
+    }
+
+    #[test]
+    fn styles() {
+        let generator = Generator::new()
+            .style(prefix("US"), BasicCodeStyle::Numeric)
+            .prefix(prefix("US"), 1)
+            .prefix(prefix("DE"), 1);
+        assert_eq!(generator.style_of(prefix("US")), BasicCodeStyle::Numeric);
+        assert_eq!(
+            generator.style_of(prefix("DE")),
+            BasicCodeStyle::Alphanumeric
+        );
+
+        let mut rng = SmallRng::seed_from_u64(2349);
+        let mut letters = false;
+        for _ in 0..200 {
+            let isin = generator.generate(&mut rng);
+            let numeric = isin.basic_code().bytes().all(|b| b.is_ascii_digit());
+            if isin.prefix() == "US" {
+                assert!(numeric, "{isin}");
+            } else {
+                letters |= !numeric;
+            }
+        }
+        assert!(letters);
+
+        let numeric = Generator::new().default_style(BasicCodeStyle::Numeric);
+        let isin = numeric.generate(&mut rng);
+        assert!(isin.basic_code().bytes().all(|b| b.is_ascii_digit()));
+    }
+
+    #[test]
+    fn unweighted_uses_every_prefix() {
+        let mut rng = SmallRng::seed_from_u64(2349);
+        let generator = Generator::new().style(prefix("US"), BasicCodeStyle::Numeric);
+        let prefixes: std::collections::HashSet<Prefix> = (0..2000)
+            .map(|_| generator.generate(&mut rng).prefix_typed())
+            .collect();
+        assert!(prefixes.len() > 600, "{} prefixes", prefixes.len());
+    }
+
+    #[test]
+    fn realistic() {
+        let generator = Generator::realistic();
+        assert_eq!(generator.style_of(prefix("US")), BasicCodeStyle::Numeric);
+        assert_eq!(
+            generator.style_of(prefix("DE")),
+            BasicCodeStyle::Alphanumeric
+        );
+
+        let mut rng = SmallRng::seed_from_u64(2349);
+        let isins: Vec<ISIN> = (&mut rng).sample_iter(&generator).take(1000).collect();
+        assert!(isins.iter().all(|isin| isin.prefix_typed().is_allocated()));
+        let us = isins.iter().filter(|isin| isin.prefix() == "US").count();
+        assert!((150..350).contains(&us), "{us} US ISINs");
+    }
+}
@@ -0,0 +1,118 @@
+//! # isin::hash
+//!
+//! A fast `Hasher` for maps and sets keyed by ISIN.
+//!
+//! The `Hash` implementation of `ISIN` feeds the hasher exactly what the `str` form of the ISIN
+//! would, so its output depends only on the 12 bytes of the ISIN and is the same for an `ISIN` as
+//! for the equal `&str` or `&IsinStr`. It is stable for a given hasher.
+//!
+//! The standard library's default SipHash is designed to resist hash-flooding attacks, which
+//! makes it comparatively slow for short fixed-size keys. [`IsinHasher`] instead folds its input
+//! eight bytes at a time into a `u64` and mixes the result once when finished. It is **not**
+//! resistant to hash flooding, so only use it for keys that do not come from untrusted input.
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use isin::hash::IsinBuildHasher;
+//!
+//! let mut positions: HashMap<isin::ISIN, i64, IsinBuildHasher> = HashMap::default();
+//! positions.insert(isin::parse("US0378331005").unwrap(), 100);
+//! assert_eq!(positions.get("US0378331005"), Some(&100));
+//! ```
+
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// Multiplier for folding each word into the state, from FxHash.
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+/// A fast, non-cryptographic `Hasher` suited to ISIN keys. See the [module docs](self).
+#[derive(Clone, Copy, Default, Debug)]
+pub struct IsinHasher {
+    state: u64,
+}
+
+/// A `BuildHasher` producing [`IsinHasher`]s, for use as the `S` parameter of `HashMap` and
+/// `HashSet`.
+pub type IsinBuildHasher = BuildHasherDefault<IsinHasher>;
+
+impl IsinHasher {
+    /// Fold one word into the state.
+    #[inline]
+    fn add(&mut self, word: u64) {
+        self.state = (self.state.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for IsinHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(chunk);
+            self.add(u64::from_le_bytes(word));
+        }
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0u8; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add(u64::from_le_bytes(word));
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    /// Finish with the 64-bit finalizer from MurmurHash3, so every output bit depends on every
+    /// input bit. Hash tables select buckets using only some of the bits.
+    #[inline]
+    fn finish(&self) -> u64 {
+        let mut h = self.state;
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        h ^= h >> 33;
+        h
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_from_payload, parse, ISIN};
+    use std::collections::HashSet;
+    use std::hash::{BuildHasher, Hash};
+
+    fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+        IsinBuildHasher::default().hash_one(value)
+    }
+
+    #[test]
+    fn same_hash_as_str_form() {
+        let isin = parse("US0378331005").unwrap();
+        assert_eq!(hash_of(&isin), hash_of("US0378331005"));
+        assert_ne!(hash_of(&isin), hash_of(&parse("US5949181045").unwrap()));
+    }
+
+    #[test]
+    fn distinct_hashes_for_consecutive_isins() {
+        let hashes: HashSet<u64> = (0..10_000)
+            .map(|n| build_from_payload(&format!("US{n:09}")).unwrap())
+            .map(|isin: ISIN| hash_of(&isin))
+            .collect();
+        assert_eq!(hashes.len(), 10_000);
+    }
+}
@@ -0,0 +1,275 @@
+//! # isin::interner
+//!
+//! An interner mapping each distinct ISIN to a dense `u32` symbol, so that structures holding
+//! many references to a small universe of ISINs can store four bytes per reference instead of
+//! twelve, and compare them with one integer comparison.
+//!
+//! ```
+//! use isin::interner::IsinInterner;
+//!
+//! let apple = isin::parse("US0378331005").unwrap();
+//! let microsoft = isin::parse("US5949181045").unwrap();
+//!
+//! let mut interner = IsinInterner::new();
+//! assert_eq!(interner.intern(apple), Some(0));
+//! assert_eq!(interner.intern(microsoft), Some(1));
+//! assert_eq!(interner.intern(apple), Some(0));
+//! assert_eq!(interner.resolve(1), Some(microsoft));
+//! ```
+//!
+//! Symbols are assigned in order of first interning, starting from zero, and are never reused.
+//! The symbol table can be written out with [`IsinInterner::write_to()`] (or with `serde`, as a
+//! sequence of ISINs in symbol order) and read back with the same symbols.
+//!
+//! Lookups use the [`IsinHasher`](crate::hash::IsinHasher), which is fast but not resistant to
+//! hash flooding.
+
+use std::collections::HashMap;
+use std::io;
+
+use crate::hash::IsinBuildHasher;
+use crate::{parse_bytes, ISIN};
+
+/// A two-way mapping between ISINs and dense `u32` symbols. See the [module docs](self).
+#[derive(Clone, Default, Debug)]
+pub struct IsinInterner {
+    /// The ISIN of each symbol, indexed by symbol
+    isins: Vec<ISIN>,
+    /// The symbol of each ISIN
+    symbols: HashMap<ISIN, u32, IsinBuildHasher>,
+}
+
+impl PartialEq for IsinInterner {
+    fn eq(&self, other: &Self) -> bool {
+        // The map is determined by the table
+        self.isins == other.isins
+    }
+}
+
+impl Eq for IsinInterner {}
+
+impl IsinInterner {
+    /// Create an empty interner.
+    pub fn new() -> IsinInterner {
+        IsinInterner::default()
+    }
+
+    /// Create an empty interner with room for `capacity` ISINs before reallocating.
+    pub fn with_capacity(capacity: usize) -> IsinInterner {
+        IsinInterner {
+            isins: Vec::with_capacity(capacity),
+            symbols: HashMap::with_capacity_and_hasher(capacity, IsinBuildHasher::default()),
+        }
+    }
+
+    /// Return the symbol of an ISIN, assigning the next one if it has not been interned before.
+    /// Returns `None` only if all 2<sup>32</sup> symbols have been assigned to other ISINs.
+    pub fn intern(&mut self, isin: ISIN) -> Option<u32> {
+        if let Some(&symbol) = self.symbols.get(&isin) {
+            return Some(symbol);
+        }
+        let symbol = u32::try_from(self.isins.len()).ok()?;
+        self.isins.push(isin);
+        self.symbols.insert(isin, symbol);
+        Some(symbol)
+    }
+
+    /// Return the symbol of an ISIN, if it has been interned.
+    pub fn get(&self, isin: &ISIN) -> Option<u32> {
+        self.symbols.get(isin).copied()
+    }
+
+    /// Return the ISIN with a symbol, if it has been assigned.
+    pub fn resolve(&self, symbol: u32) -> Option<ISIN> {
+        self.isins.get(symbol as usize).copied()
+    }
+
+    /// Return the number of ISINs interned.
+    pub fn len(&self) -> usize {
+        self.isins.len()
+    }
+
+    /// Return true if no ISINs have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.isins.is_empty()
+    }
+
+    /// Iterate over the symbols and their ISINs in symbol order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, ISIN)> + '_ {
+        (0..).zip(self.isins.iter().copied())
+    }
+
+    /// Return the ISINs in symbol order: the ISIN of each symbol is at the index of the symbol.
+    pub fn as_slice(&self) -> &[ISIN] {
+        &self.isins
+    }
+
+    /// Write the symbol table as the twelve bytes of each ISIN in symbol order, with no
+    /// separators.
+    pub fn write_to<W: io::Write>(&self, mut writer: W) -> io::Result<()> {
+        for isin in &self.isins {
+            writer.write_all(isin.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Read a symbol table written by [`write_to()`](Self::write_to), giving each ISIN the same
+    /// symbol as before.
+    ///
+    /// Records that are not valid ISINs, repeated ISINs, and data that is not a whole number of
+    /// records are rejected with an error of kind [`io::ErrorKind::InvalidData`].
+    pub fn read_from<R: io::Read>(mut reader: R) -> io::Result<IsinInterner> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        if data.len() % 12 != 0 {
+            return Err(invalid_data("symbol table is not a whole number of ISINs"));
+        }
+
+        let mut interner = IsinInterner::with_capacity(data.len() / 12);
+        for record in data.chunks_exact(12) {
+            let isin = parse_bytes(record).map_err(|err| invalid_data(err.to_string()))?;
+            interner.push_unique(isin).map_err(invalid_data)?;
+        }
+        Ok(interner)
+    }
+
+    /// Intern an ISIN that must not already have been interned, as when rebuilding a table.
+    fn push_unique(&mut self, isin: ISIN) -> Result<(), &'static str> {
+        if self.symbols.contains_key(&isin) {
+            return Err("ISIN repeated in symbol table");
+        }
+        self.intern(isin).ok_or("too many ISINs in symbol table")?;
+        Ok(())
+    }
+}
+
+/// An error of kind `InvalidData` with a message.
+fn invalid_data<E>(message: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+impl Extend<ISIN> for IsinInterner {
+    /// Intern each ISIN. Any beyond the last symbol are ignored.
+    fn extend<I: IntoIterator<Item = ISIN>>(&mut self, iter: I) {
+        for isin in iter {
+            self.intern(isin);
+        }
+    }
+}
+
+impl FromIterator<ISIN> for IsinInterner {
+    fn from_iter<I: IntoIterator<Item = ISIN>>(iter: I) -> Self {
+        let mut interner = IsinInterner::new();
+        interner.extend(iter);
+        interner
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for IsinInterner {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(&self.isins)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IsinInterner {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = IsinInterner;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a sequence of distinct ISINs")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut interner =
+                    IsinInterner::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                while let Some(isin) = seq.next_element()? {
+                    interner
+                        .push_unique(isin)
+                        .map_err(serde::de::Error::custom)?;
+                }
+                Ok(interner)
+            }
+        }
+
+        deserializer.deserialize_seq(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn sample() -> IsinInterner {
+        [
+            "US0378331005",
+            "US5949181045",
+            "US0378331005",
+            "DE000A0GNPZ3",
+        ]
+        .iter()
+        .map(|s| parse(s).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn symbols_are_dense_and_stable() {
+        let mut interner = sample();
+        assert_eq!(interner.len(), 3);
+        assert_eq!(interner.get(&parse("DE000A0GNPZ3").unwrap()), Some(2));
+        assert_eq!(interner.get(&parse("GB00BF0FCW58").unwrap()), None);
+        assert_eq!(interner.resolve(3), None);
+        assert_eq!(interner.intern(parse("GB00BF0FCW58").unwrap()), Some(3));
+        let symbols: Vec<u32> = interner.iter().map(|(symbol, _)| symbol).collect();
+        assert_eq!(symbols, vec![0, 1, 2, 3]);
+        for (symbol, isin) in interner.iter() {
+            assert_eq!(interner.as_slice()[symbol as usize], isin);
+        }
+    }
+
+    #[test]
+    fn table_round_trip() {
+        let interner = sample();
+        let mut buffer = Vec::new();
+        interner.write_to(&mut buffer).unwrap();
+        assert_eq!(&buffer[..12], b"US0378331005");
+        assert_eq!(IsinInterner::read_from(&buffer[..]).unwrap(), interner);
+
+        let err = IsinInterner::read_from(&buffer[..13]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let err = IsinInterner::read_from(&b"US0378331005US0378331005"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let err = IsinInterner::read_from(&b"US0378331006"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let interner = sample();
+        let json = serde_json::to_string(&interner).unwrap();
+        assert_eq!(json, r#"["US0378331005","US5949181045","DE000A0GNPZ3"]"#);
+        let back: IsinInterner = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, interner);
+        assert!(
+            serde_json::from_str::<IsinInterner>(r#"["US0378331005","US0378331005"]"#).is_err()
+        );
+    }
+}
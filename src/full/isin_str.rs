@@ -0,0 +1,175 @@
+//! # isin::isin_str
+//!
+//! A borrowed, validated ISIN string slice. [`IsinStr`] is to [`ISIN`] what
+//! [`Path`](std::path::Path) is to [`PathBuf`](std::path::PathBuf): input can be validated in
+//! place and passed around as `&IsinStr` without copying, and converted to an owned `ISIN` when
+//! needed.
+//!
+//! This module is not available with the `forbid-unsafe` feature enabled.
+
+use std::borrow::{Borrow, ToOwned};
+use std::fmt;
+
+use crate::{validate, ParseError, ISIN};
+
+/// A validated ISIN borrowed from a string slice.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[repr(transparent)]
+pub struct IsinStr(str);
+
+impl IsinStr {
+    /// Validate a string in place, requiring the same strict format as `parse()`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(value: &str) -> Result<&IsinStr, ParseError> {
+        validate(value)?;
+        Ok(IsinStr::from_str_unchecked(value))
+    }
+
+    /// Internal function for viewing an already-validated string as an `IsinStr`.
+    pub(crate) fn from_str_unchecked(value: &str) -> &IsinStr {
+        // This is safe because IsinStr is a repr(transparent) wrapper around str
+        unsafe { &*(value as *const str as *const IsinStr) }
+    }
+
+    /// Return the underlying string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Return an owned copy as an `ISIN`.
+    pub fn to_isin(&self) -> ISIN {
+        self.to_owned()
+    }
+
+    /// Return just the _Prefix_ portion of the ISIN.
+    pub fn prefix(&self) -> &str {
+        &self.0[0..2]
+    }
+
+    /// Return just the _Basic Code_ portion of the ISIN.
+    pub fn basic_code(&self) -> &str {
+        &self.0[2..11]
+    }
+
+    /// Return the _Payload_ &mdash; everything except the _Check Digit_.
+    pub fn payload(&self) -> &str {
+        &self.0[0..11]
+    }
+
+    /// Return just the _Check Digit_ portion of the ISIN.
+    pub fn check_digit(&self) -> char {
+        self.0.as_bytes()[11] as char
+    }
+}
+
+impl AsRef<str> for IsinStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for IsinStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+impl fmt::Debug for IsinStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IsinStr({})", &self.0)
+    }
+}
+
+impl ToOwned for IsinStr {
+    type Owned = ISIN;
+
+    fn to_owned(&self) -> ISIN {
+        let mut bb = [0u8; 12];
+        bb.copy_from_slice(self.0.as_bytes());
+        ISIN(bb)
+    }
+}
+
+impl Borrow<IsinStr> for ISIN {
+    fn borrow(&self) -> &IsinStr {
+        self.as_isin_str()
+    }
+}
+
+impl AsRef<IsinStr> for ISIN {
+    fn as_ref(&self) -> &IsinStr {
+        self.as_isin_str()
+    }
+}
+
+impl PartialEq<ISIN> for IsinStr {
+    fn eq(&self, other: &ISIN) -> bool {
+        self.0.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<IsinStr> for ISIN {
+    fn eq(&self, other: &IsinStr) -> bool {
+        self.as_bytes() == other.0.as_bytes()
+    }
+}
+
+impl<'a> TryFrom<&'a str> for &'a IsinStr {
+    type Error = ParseError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        IsinStr::from_str(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CheckDigit;
+    use std::collections::{BTreeSet, HashSet};
+
+    #[test]
+    fn validate_in_place() {
+        let text = "US0378331005";
+        let isin_str = IsinStr::from_str(text).unwrap();
+        assert_eq!(isin_str.as_str().as_ptr(), text.as_ptr());
+        assert_eq!(isin_str.prefix(), "US");
+        assert_eq!(isin_str.basic_code(), "037833100");
+        assert_eq!(isin_str.payload(), "US037833100");
+        assert_eq!(isin_str.check_digit(), '5');
+        assert_eq!(isin_str.to_string(), "US0378331005");
+        assert_eq!(format!("{:?}", isin_str), "IsinStr(US0378331005)");
+    }
+
+    #[test]
+    fn reject_invalid() {
+        assert_eq!(
+            IsinStr::from_str("US0378331006").unwrap_err(),
+            ParseError::IncorrectCheckDigit {
+                was: CheckDigit::from_value(6).unwrap(),
+                expected: CheckDigit::from_value(5).unwrap()
+            }
+        );
+    }
+
+    #[test]
+    fn owned_and_borrowed() {
+        let isin_str = IsinStr::from_str("US0378331005").unwrap();
+        let isin = isin_str.to_isin();
+        assert_eq!(isin, crate::parse("US0378331005").unwrap());
+        assert_eq!(isin_str, &isin);
+        assert_eq!(isin.as_isin_str(), isin_str);
+    }
+
+    #[test]
+    fn lookup_owned_sets_by_borrowed_key() {
+        let isin = crate::parse("US0378331005").unwrap();
+        let isin_str = IsinStr::from_str("US0378331005").unwrap();
+
+        let hashed: HashSet<ISIN> = [isin].into_iter().collect();
+        assert!(hashed.contains(isin_str));
+
+        let ordered: BTreeSet<ISIN> = [isin].into_iter().collect();
+        assert!(ordered.contains(isin_str));
+    }
+}
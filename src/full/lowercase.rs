@@ -0,0 +1,58 @@
+//! # isin::lowercase
+//!
+//! A `Display` adapter rendering an ISIN in lowercase, for downstream systems that normalize keys
+//! that way. Unlike `isin.to_string().to_lowercase()`, it does not allocate.
+
+use std::fmt;
+
+use crate::{ascii_str, ISIN};
+
+/// An ISIN rendered in lowercase, as produced by
+/// [`ISIN::display_lowercase()`](crate::ISIN::display_lowercase).
+///
+/// Like the `Display` implementation of `ISIN`, this honors the width, fill and alignment flags of
+/// the formatter.
+#[derive(Clone, Copy)]
+pub struct IsinLowercase<'a>(&'a ISIN);
+
+impl<'a> IsinLowercase<'a> {
+    /// Wrap an ISIN for lowercase rendering.
+    pub(crate) fn new(isin: &'a ISIN) -> IsinLowercase<'a> {
+        IsinLowercase(isin)
+    }
+}
+
+impl fmt::Display for IsinLowercase<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bb = *self.0.as_bytes();
+        bb.make_ascii_lowercase();
+        f.pad(ascii_str(&bb))
+    }
+}
+
+impl fmt::Debug for IsinLowercase<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IsinLowercase({self})")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn render_lowercase() {
+        let isin = crate::parse("DE000A0GNPZ3").unwrap();
+        assert_eq!(isin.display_lowercase().to_string(), "de000a0gnpz3");
+        assert_eq!(
+            format!("{:>14}", isin.display_lowercase()),
+            "  de000a0gnpz3"
+        );
+        assert_eq!(
+            format!("{:?}", isin.display_lowercase()),
+            "IsinLowercase(de000a0gnpz3)"
+        );
+        assert_eq!(
+            crate::parse_loose(&isin.display_lowercase().to_string()),
+            Ok(isin)
+        );
+    }
+}
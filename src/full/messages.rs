@@ -0,0 +1,299 @@
+//! # isin::messages
+//!
+//! Error messages in languages other than English, for showing errors directly to people.
+//!
+//! The `Display` form of [`Error`] is always in English. To render it another way, pass a
+//! [`MessageCatalog`] to [`Error::localized()`]:
+//!
+//! ```
+//! use isin::messages::{German, Japanese};
+//!
+//! let err = isin::Error::from(isin::parse("US0378331006").unwrap_err());
+//! assert_eq!(
+//!     err.localized(&German).to_string(),
+//!     "falsche Prüfziffer '6', erwartet '5'"
+//! );
+//! assert_eq!(
+//!     err.localized(&Japanese).to_string(),
+//!     "チェックディジット '6' が正しくありません（正しくは '5'）"
+//! );
+//! ```
+//!
+//! Implement `MessageCatalog` for other languages, or to use your own wording.
+
+use std::fmt;
+
+use crate::error::ErrorByte;
+use crate::Error;
+
+/// A way of writing the message for any [`Error`].
+pub trait MessageCatalog {
+    /// Write the message for `error` to `f`.
+    fn write_message(&self, error: &Error, f: &mut fmt::Formatter<'_>) -> fmt::Result;
+}
+
+/// The messages of the `Display` implementation of [`Error`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct English;
+
+/// Messages in German.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct German;
+
+/// Messages in Japanese.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Japanese;
+
+/// An [`Error`] to display using a [`MessageCatalog`], as returned by [`Error::localized()`].
+pub struct Localized<'a> {
+    pub(crate) error: &'a Error,
+    pub(crate) catalog: &'a dyn MessageCatalog,
+}
+
+impl fmt::Display for Localized<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.catalog.write_message(self.error, f)
+    }
+}
+
+impl fmt::Debug for Localized<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Localized({:?})", self.error)
+    }
+}
+
+impl MessageCatalog for English {
+    fn write_message(&self, error: &Error, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(error, f)
+    }
+}
+
+impl MessageCatalog for German {
+    fn write_message(&self, error: &Error, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match error {
+            Error::InvalidValueStringLength { was } => {
+                write!(f, "ungültige Länge {was} Zeichen, erwartet 12")
+            }
+            Error::InvalidValueArrayLength { was } => {
+                write!(f, "ungültige Länge {was} Bytes, erwartet 12")
+            }
+            Error::InvalidPayloadStringLength { was } => {
+                write!(f, "ungültige Länge der Payload {was} Zeichen, erwartet 11")
+            }
+            Error::InvalidPayloadArrayLength { was } => {
+                write!(f, "ungültige Länge der Payload {was} Bytes, erwartet 11")
+            }
+            Error::InvalidPrefixStringLength { was } => {
+                write!(f, "ungültige Länge des Präfixes {was} Zeichen, erwartet 2")
+            }
+            Error::InvalidPrefixArrayLength { was } => {
+                write!(f, "ungültige Länge des Präfixes {was} Bytes, erwartet 2")
+            }
+            Error::InvalidBasicCodeStringLength { was } => {
+                write!(
+                    f,
+                    "ungültige Länge des Basic Code {was} Zeichen, erwartet 9"
+                )
+            }
+            Error::InvalidBasicCodeArrayLength { was } => {
+                write!(f, "ungültige Länge des Basic Code {was} Bytes, erwartet 9")
+            }
+            Error::InvalidPrefix { index, byte } => {
+                write!(
+                    f,
+                    "ungültiges Zeichen {:?} an Position {index} des Präfixes, der aus zwei ASCII-Großbuchstaben bestehen muss",
+                    ErrorByte(*byte)
+                )
+            }
+            Error::InvalidBasicCode { index, byte } => {
+                write!(
+                    f,
+                    "ungültiges Zeichen {:?} an Position {index} des Basic Code, der aus neun ASCII-Großbuchstaben oder Ziffern bestehen muss",
+                    ErrorByte(*byte)
+                )
+            }
+            Error::InvalidCharacter { index, byte, .. } => {
+                write!(
+                    f,
+                    "ungültiges Zeichen {:?} an Position {index}",
+                    ErrorByte(*byte)
+                )
+            }
+            Error::InvalidCheckDigit { was } => {
+                write!(
+                    f,
+                    "Prüfziffer {:?} ist keine Dezimalziffer",
+                    ErrorByte(*was)
+                )
+            }
+            Error::IncorrectCheckDigit { was, expected } => {
+                write!(
+                    f,
+                    "falsche Prüfziffer {:?}, erwartet {:?}",
+                    was.as_char(),
+                    expected.as_char()
+                )
+            }
+            Error::MissingPrefix => write!(f, "Präfix fehlt"),
+            Error::MissingBasicCode => write!(f, "Basic Code fehlt"),
+            Error::UnallocatedPrefix { was } => {
+                write!(
+                    f,
+                    "Präfix {:?} ist kein vergebener Ländercode oder Sonderpräfix",
+                    String::from_utf8_lossy(was)
+                )
+            }
+        }
+    }
+}
+
+impl MessageCatalog for Japanese {
+    fn write_message(&self, error: &Error, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match error {
+            Error::InvalidValueStringLength { was } => {
+                write!(f, "長さが無効です（{was}文字、正しくは12文字）")
+            }
+            Error::InvalidValueArrayLength { was } => {
+                write!(f, "長さが無効です（{was}バイト、正しくは12バイト）")
+            }
+            Error::InvalidPayloadStringLength { was } => {
+                write!(f, "ペイロードの長さが無効です（{was}文字、正しくは11文字）")
+            }
+            Error::InvalidPayloadArrayLength { was } => {
+                write!(
+                    f,
+                    "ペイロードの長さが無効です（{was}バイト、正しくは11バイト）"
+                )
+            }
+            Error::InvalidPrefixStringLength { was } => {
+                write!(
+                    f,
+                    "プレフィックスの長さが無効です（{was}文字、正しくは2文字）"
+                )
+            }
+            Error::InvalidPrefixArrayLength { was } => {
+                write!(
+                    f,
+                    "プレフィックスの長さが無効です（{was}バイト、正しくは2バイト）"
+                )
+            }
+            Error::InvalidBasicCodeStringLength { was } => {
+                write!(f, "基本コードの長さが無効です（{was}文字、正しくは9文字）")
+            }
+            Error::InvalidBasicCodeArrayLength { was } => {
+                write!(
+                    f,
+                    "基本コードの長さが無効です（{was}バイト、正しくは9バイト）"
+                )
+            }
+            Error::InvalidPrefix { index, byte } => {
+                write!(
+                    f,
+                    "プレフィックスの位置 {index} の文字 {:?} は無効です（2文字のASCII英大文字が必要です）",
+                    ErrorByte(*byte)
+                )
+            }
+            Error::InvalidBasicCode { index, byte } => {
+                write!(
+                    f,
+                    "基本コードの位置 {index} の文字 {:?} は無効です（9文字のASCII英大文字または数字が必要です）",
+                    ErrorByte(*byte)
+                )
+            }
+            Error::InvalidCharacter { index, byte, .. } => {
+                write!(f, "位置 {index} の文字 {:?} は無効です", ErrorByte(*byte))
+            }
+            Error::InvalidCheckDigit { was } => {
+                write!(
+                    f,
+                    "チェックディジット {:?} は数字ではありません",
+                    ErrorByte(*was)
+                )
+            }
+            Error::IncorrectCheckDigit { was, expected } => {
+                write!(
+                    f,
+                    "チェックディジット {:?} が正しくありません（正しくは {:?}）",
+                    was.as_char(),
+                    expected.as_char()
+                )
+            }
+            Error::MissingPrefix => write!(f, "プレフィックスがありません"),
+            Error::MissingBasicCode => write!(f, "基本コードがありません"),
+            Error::UnallocatedPrefix { was } => {
+                write!(
+                    f,
+                    "プレフィックス {:?} は割り当てられた国コードまたは特別なプレフィックスではありません",
+                    String::from_utf8_lossy(was)
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_from_parts, parse, BuildError};
+
+    #[test]
+    fn english_matches_display() {
+        let errors = [
+            Error::from(parse("US03783310").unwrap_err()),
+            Error::from(parse("US03783310-5").unwrap_err()),
+            Error::from(parse("US037833é05").unwrap_err()),
+            Error::from(build_from_parts("U", "037833100").unwrap_err()),
+            Error::from(BuildError::MissingPrefix),
+        ];
+        for err in errors {
+            assert_eq!(err.localized(&English).to_string(), err.to_string());
+        }
+    }
+
+    #[test]
+    fn german_and_japanese() {
+        let err = Error::from(parse("US03783310-5").unwrap_err());
+        assert_eq!(
+            err.localized(&German).to_string(),
+            "ungültiges Zeichen '-' an Position 10"
+        );
+        assert_eq!(
+            err.localized(&Japanese).to_string(),
+            "位置 10 の文字 '-' は無効です"
+        );
+
+        let err = Error::from(parse("US037833é05").unwrap_err());
+        assert_eq!(
+            err.localized(&German).to_string(),
+            "ungültiges Zeichen 0xC3 an Position 8"
+        );
+        assert_eq!(
+            err.localized(&Japanese).to_string(),
+            "位置 8 の文字 0xC3 は無効です"
+        );
+
+        let err = Error::from(parse("US03783310").unwrap_err());
+        assert_eq!(
+            err.localized(&German).to_string(),
+            "ungültige Länge 10 Zeichen, erwartet 12"
+        );
+        assert_eq!(
+            err.localized(&Japanese).to_string(),
+            "長さが無効です（10文字、正しくは12文字）"
+        );
+    }
+
+    #[test]
+    fn custom_catalog() {
+        struct Terse;
+
+        impl MessageCatalog for Terse {
+            fn write_message(&self, error: &Error, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(error.kind().as_str())
+            }
+        }
+
+        let err = Error::from(parse("US0378331006").unwrap_err());
+        assert_eq!(err.localized(&Terse).to_string(), "IncorrectCheckDigit");
+    }
+}
@@ -0,0 +1,198 @@
+//! # isin::mod10
+//!
+//! The "modulus 10 'double-add-double'" checksum of ISINs, for any alphabet.
+//!
+//! This is the Luhn algorithm, extended from decimal digits to any set of up to 100 characters by
+//! first replacing each character with the decimal digits of its value (its position in the
+//! alphabet). The ISIN alphabet is [`BASE36`]: `0` through `9` have values 0 through 9, and `A`
+//! through `Z` have values 10 through 35. See the [`checksum`](crate::checksum) module for the
+//! steps of the formula.
+//!
+//! ```
+//! use isin::mod10::{Alphabet, BASE36};
+//!
+//! assert_eq!(BASE36.checksum(b"US037833100"), Ok(5));
+//!
+//! // An alphabet of decimal digits gives the ordinary Luhn check digit.
+//! const DECIMAL: Alphabet = match Alphabet::new(b"0123456789") {
+//!     Some(alphabet) => alphabet,
+//!     None => panic!("invalid alphabet"),
+//! };
+//! assert_eq!(DECIMAL.checksum(b"7992739871"), Ok(3));
+//! ```
+
+use crate::ParseError;
+
+/// Marks bytes that are not in the alphabet in `Alphabet::values`.
+const INVALID: u8 = u8::MAX;
+
+/// The maximum value the accumulator can have and still be able to go another iteration without
+/// overflowing, as in `checksum_table()`.
+const MAX_ACCUM: u8 = u8::MAX - 9;
+
+/// The alphabet of ISINs: the ASCII digits followed by the ASCII uppercase letters.
+pub const BASE36: Alphabet = match Alphabet::new(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ") {
+    Some(alphabet) => alphabet,
+    None => unreachable!(),
+};
+
+/// A set of characters with values, and the tables for computing checksums over them. Like the
+/// tables of the [`checksum`](crate::checksum) module, they hold the net effect of each character
+/// on the sum, for either parity of its position, so computing a checksum needs no digit
+/// expansion.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Alphabet {
+    /// The value of each byte, or `INVALID`
+    values: [u8; 256],
+    /// The number of decimal digits in the value of each byte
+    widths: [u8; 256],
+    /// The net value added to the sum for each byte if its rightmost digit is doubled
+    evens: [u8; 256],
+    /// The net value added to the sum for each byte if its rightmost digit is not doubled
+    odds: [u8; 256],
+}
+
+/// The sum of the decimal digits of twice `x`, for `x` from 0 to 9.
+const fn double_add(x: u8) -> u8 {
+    let d = x * 2;
+    d / 10 + d % 10
+}
+
+impl Alphabet {
+    /// An alphabet giving each character the value of its position in `chars`. Returns `None` if
+    /// there are more than 100 characters (so a value would have more than two digits) or any
+    /// character appears twice.
+    pub const fn new(chars: &[u8]) -> Option<Alphabet> {
+        if chars.len() > 100 {
+            return None;
+        }
+        let mut alphabet = Alphabet {
+            values: [INVALID; 256],
+            widths: [0; 256],
+            evens: [0; 256],
+            odds: [0; 256],
+        };
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i] as usize;
+            if alphabet.values[c] != INVALID {
+                return None;
+            }
+            let v = i as u8;
+            alphabet.values[c] = v;
+            if v < 10 {
+                alphabet.widths[c] = 1;
+                alphabet.evens[c] = double_add(v);
+                alphabet.odds[c] = v;
+            } else {
+                let (high, low) = (v / 10, v % 10);
+                alphabet.widths[c] = 2;
+                alphabet.evens[c] = (high + double_add(low)) % 10;
+                alphabet.odds[c] = (double_add(high) + low) % 10;
+            }
+            i += 1;
+        }
+        Some(alphabet)
+    }
+
+    /// The value of a character, or `None` if it is not in the alphabet.
+    pub const fn value(&self, c: u8) -> Option<u8> {
+        match self.values[c as usize] {
+            INVALID => None,
+            v => Some(v),
+        }
+    }
+
+    /// Compute the checksum of `s`, from 0 to 9: the check digit to append to it. Returns an
+    /// [`InvalidCharacter`](ParseError::InvalidCharacter) error for the first character that is
+    /// not in the alphabet.
+    pub fn checksum(&self, s: &[u8]) -> Result<u8, ParseError> {
+        let mut sum: u8 = 0;
+        let mut idx: usize = 0;
+        for &c in s.iter().rev() {
+            let c = c as usize;
+            if self.values[c] == INVALID {
+                return Err(self.first_invalid_character(s));
+            }
+            let x = if (idx & 0x1) == 0 {
+                self.evens[c]
+            } else {
+                self.odds[c]
+            };
+            if sum > MAX_ACCUM {
+                sum %= 10
+            }
+            sum += x;
+            idx += self.widths[c] as usize;
+        }
+        Ok((10 - sum % 10) % 10)
+    }
+
+    /// Test whether or not the last character of `s` is a decimal digit equal to the checksum of
+    /// the rest.
+    pub fn is_valid(&self, s: &[u8]) -> bool {
+        match s.split_last() {
+            Some((&last, rest)) if last.is_ascii_digit() => self.checksum(rest) == Ok(last - b'0'),
+            _ => false,
+        }
+    }
+
+    /// The error for the first character of `s` that is not in the alphabet, of which there must
+    /// be at least one.
+    fn first_invalid_character(&self, s: &[u8]) -> ParseError {
+        let index = s.iter().position(|&c| self.value(c).is_none()).unwrap_or(0);
+        ParseError::invalid_character(index, s.get(index).copied().unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checksum::checksum_table;
+    use proptest::prelude::*;
+
+    #[test]
+    fn invalid_alphabets() {
+        assert_eq!(Alphabet::new(b"0123456789A1"), None);
+        assert_eq!(Alphabet::new(&[b'x'; 101]), None);
+        let all: Vec<u8> = (0..100).collect();
+        assert!(Alphabet::new(&all).is_some());
+    }
+
+    #[test]
+    fn base36_values() {
+        assert_eq!(BASE36.value(b'0'), Some(0));
+        assert_eq!(BASE36.value(b'Z'), Some(35));
+        assert_eq!(BASE36.value(b'a'), None);
+        assert!(BASE36.is_valid(b"US0378331005"));
+        assert!(!BASE36.is_valid(b"US0378331006"));
+        assert!(!BASE36.is_valid(b""));
+    }
+
+    #[test]
+    fn custom_alphabet() {
+        // Lowercase letters have the same values as uppercase ones in this alphabet.
+        let lower = Alphabet::new(b"0123456789abcdefghijklmnopqrstuvwxyz").unwrap();
+        assert_eq!(lower.checksum(b"us037833100"), Ok(5));
+        assert_eq!(
+            lower.checksum(b"US037833100"),
+            Err(ParseError::InvalidCharacter {
+                index: 0,
+                byte: b'U',
+                in_prefix: true
+            })
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn base36_matches_table(s in proptest::collection::vec(any::<u8>(), 0..40)) {
+            prop_assert_eq!(BASE36.checksum(&s), checksum_table(&s));
+        }
+
+        #[test]
+        fn base36_matches_table_on_valid(s in "[0-9A-Z]{0,40}") {
+            prop_assert_eq!(BASE36.checksum(s.as_bytes()), checksum_table(s.as_bytes()));
+        }
+    }
+}
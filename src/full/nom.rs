@@ -0,0 +1,132 @@
+//! # isin::nom
+//!
+//! Parsers for use with the [`nom`](https://docs.rs/nom) parser combinator library, so an ISIN can
+//! be part of a larger grammar such as a FIX message or a proprietary wire format.
+//!
+//! Each parser takes exactly the next twelve characters of the input and requires them to be a
+//! valid ISIN as `parse()` does. What follows them is left to the rest of the grammar.
+//!
+//! ```
+//! use nom::bytes::complete::tag;
+//! use nom::sequence::preceded;
+//! use nom::{IResult, Parser};
+//!
+//! // The SecurityID field of a FIX message, when the SecurityIDSource is an ISIN.
+//! fn security_id(input: &str) -> IResult<&str, isin::ISIN> {
+//!     preceded(tag("48="), isin::nom::isin).parse(input)
+//! }
+//!
+//! let (rest, isin) = security_id("48=US0378331005\x0122=4").unwrap();
+//! assert_eq!(isin.to_string(), "US0378331005");
+//! assert_eq!(rest, "\x0122=4");
+//! ```
+//!
+//! This module is only available with the `nom` feature enabled.
+
+use ::nom::bytes::complete::take;
+use ::nom::combinator::map_res;
+use ::nom::error::{Error, FromExternalError, ParseError as NomParseError};
+use ::nom::{IResult, Parser};
+
+use crate::{parse, parse_bytes, ParseError, ISIN};
+
+/// Parse an ISIN from the next twelve characters of a string.
+pub fn isin(input: &str) -> IResult<&str, ISIN> {
+    isin_with::<Error<&str>>(input)
+}
+
+/// Parse an ISIN from the next twelve bytes of a byte slice.
+pub fn isin_bytes(input: &[u8]) -> IResult<&[u8], ISIN> {
+    isin_bytes_with::<Error<&[u8]>>(input)
+}
+
+/// Parse an ISIN from the next twelve characters of a string, with an error type of your choice.
+/// If there are fewer than twelve characters, the error is made by `E::from_error_kind()`. If they
+/// are not a valid ISIN, the error is made by `E::from_external_error()` from the
+/// [`ParseError`], at the start of the twelve characters.
+pub fn isin_with<'a, E>(input: &'a str) -> IResult<&'a str, ISIN, E>
+where
+    E: NomParseError<&'a str> + FromExternalError<&'a str, ParseError>,
+{
+    map_res(take(12usize), parse).parse(input)
+}
+
+/// Parse an ISIN from the next twelve bytes of a byte slice, with an error type of your choice, as
+/// for [`isin_with()`].
+pub fn isin_bytes_with<'a, E>(input: &'a [u8]) -> IResult<&'a [u8], ISIN, E>
+where
+    E: NomParseError<&'a [u8]> + FromExternalError<&'a [u8], ParseError>,
+{
+    map_res(take(12usize), parse_bytes).parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::nom::error::ErrorKind;
+    use ::nom::multi::separated_list1;
+    use ::nom::Err;
+
+    #[test]
+    fn parse_and_leave_rest() {
+        let apple = parse("US0378331005").unwrap();
+        assert_eq!(isin("US0378331005"), Ok(("", apple)));
+        assert_eq!(isin("US0378331005, etc."), Ok((", etc.", apple)));
+        assert_eq!(isin_bytes(b"US0378331005\x01"), Ok((&b"\x01"[..], apple)));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            isin("US037833100"),
+            Err(Err::Error(Error::new("US037833100", ErrorKind::Eof)))
+        );
+        assert_eq!(
+            isin("US0378331006"),
+            Err(Err::Error(Error::new("US0378331006", ErrorKind::MapRes)))
+        );
+    }
+
+    #[test]
+    fn keep_parse_error() {
+        #[derive(Debug, PartialEq)]
+        enum MyError<'a> {
+            Nom(&'a str, ErrorKind),
+            Isin(&'a str, ParseError),
+        }
+
+        impl<'a> NomParseError<&'a str> for MyError<'a> {
+            fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+                MyError::Nom(input, kind)
+            }
+
+            fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+                other
+            }
+        }
+
+        impl<'a> FromExternalError<&'a str, ParseError> for MyError<'a> {
+            fn from_external_error(input: &'a str, _: ErrorKind, e: ParseError) -> Self {
+                MyError::Isin(input, e)
+            }
+        }
+
+        assert_eq!(
+            isin_with::<MyError>("US0378331006"),
+            Err(Err::Error(MyError::Isin(
+                "US0378331006",
+                parse("US0378331006").unwrap_err()
+            )))
+        );
+    }
+
+    #[test]
+    fn in_a_list() {
+        let (rest, isins) =
+            separated_list1(::nom::bytes::complete::tag::<_, _, Error<&str>>(","), isin)
+                .parse("US0378331005,US5949181045")
+                .unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(isins.len(), 2);
+    }
+}
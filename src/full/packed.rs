@@ -0,0 +1,155 @@
+//! # isin::packed
+//!
+//! A compact `u64` representation of an ISIN.
+//!
+//! The twelve characters are encoded as a mixed-radix number: the two _Prefix_ letters in base 26,
+//! the nine _Basic Code_ characters in base 36 (digits before letters), and the _Check Digit_ in
+//! base 10. The largest value is under 2<sup>60</sup>, and because each position's digit values
+//! follow the ASCII ordering of the characters, comparing packed values gives the same result as
+//! comparing the ISINs themselves.
+
+use std::fmt;
+
+use crate::{ISIN, PAYLOAD_COUNT};
+
+/// An ISIN packed into a `u64`, half the size of an `ISIN` and comparable as an integer.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash, Debug)]
+#[repr(transparent)]
+pub struct PackedIsin(u64);
+
+/// The error returned when a `u64` is not the packed form of any valid ISIN.
+#[derive(Eq, PartialEq, Clone, Copy, Debug)]
+pub struct TryFromPackedError {
+    /// The value we found
+    pub was: u64,
+}
+
+impl fmt::Display for TryFromPackedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "value {} is not a packed ISIN", self.was)
+    }
+}
+
+impl std::error::Error for TryFromPackedError {}
+
+impl PackedIsin {
+    /// The smallest packed ISIN, that of [`ISIN::MIN`], `AA0000000006`.
+    pub const MIN: PackedIsin = PackedIsin(6);
+
+    /// The largest packed ISIN, that of [`ISIN::MAX`], `ZZZZZZZZZZZ6`.
+    pub const MAX: PackedIsin = PackedIsin((PAYLOAD_COUNT - 1) * 10 + 6);
+
+    /// One more than the largest packed value that could be valid.
+    const LIMIT: u64 = PAYLOAD_COUNT * 10;
+
+    /// Return the packed value as an integer.
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    /// Unpack to an `ISIN`, with the _Check Digit_ it was packed with.
+    pub fn to_isin(self) -> ISIN {
+        // Every PackedIsin was made from a valid ISIN, so its payload rank is in range
+        let rank = self.0 / 10;
+        let mut isin = match ISIN::from_payload_rank(rank) {
+            Some(isin) => isin,
+            None => unreachable!("PackedIsin values are always in range"),
+        };
+        // Use the stored digit rather than the computed one, so an ISIN whose _Check Digit_ was
+        // never checked (see `ISIN::from_prevalidated_str()`) survives the round trip unchanged.
+        isin.0[11] = b'0' + (self.0 % 10) as u8;
+        isin
+    }
+}
+
+impl From<ISIN> for PackedIsin {
+    fn from(isin: ISIN) -> Self {
+        PackedIsin(isin.payload_rank() * 10 + (isin.0[11] - b'0') as u64)
+    }
+}
+
+impl From<PackedIsin> for ISIN {
+    fn from(packed: PackedIsin) -> Self {
+        packed.to_isin()
+    }
+}
+
+impl From<PackedIsin> for u64 {
+    fn from(packed: PackedIsin) -> Self {
+        packed.0
+    }
+}
+
+impl TryFrom<u64> for PackedIsin {
+    type Error = TryFromPackedError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        if value >= PackedIsin::LIMIT {
+            return Err(TryFromPackedError { was: value });
+        }
+        match ISIN::from_payload_rank(value / 10) {
+            Some(isin) if (isin.0[11] - b'0') as u64 == value % 10 => Ok(PackedIsin(value)),
+            _ => Err(TryFromPackedError { was: value }),
+        }
+    }
+}
+
+impl fmt::Display for PackedIsin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_isin(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_from_payload, parse};
+    use proptest::prelude::*;
+
+    #[test]
+    fn pack_apple() {
+        let isin = parse("US0378331005").unwrap();
+        let packed = PackedIsin::from(isin);
+        assert_eq!(ISIN::from(packed), isin);
+        assert_eq!(PackedIsin::try_from(packed.value()), Ok(packed));
+        assert_eq!(packed.to_string(), "US0378331005");
+    }
+
+    #[test]
+    fn extremes() {
+        assert_eq!(PackedIsin::from(ISIN::MIN), PackedIsin::MIN);
+        assert_eq!(PackedIsin::from(ISIN::MAX), PackedIsin::MAX);
+        assert_eq!(PackedIsin::MAX.to_isin(), ISIN::MAX);
+        assert!(PackedIsin::MAX.value() < 1 << 60);
+    }
+
+    #[test]
+    fn round_trip_keeps_unchecked_check_digit() {
+        let isin = ISIN::from_prevalidated_str("US0378331006").unwrap();
+        assert_eq!(PackedIsin::from(isin).to_isin(), isin);
+    }
+
+    #[test]
+    fn reject_invalid_values() {
+        let packed = PackedIsin::from(parse("US0378331005").unwrap()).value();
+        assert_eq!(
+            PackedIsin::try_from(packed + 1),
+            Err(TryFromPackedError { was: packed + 1 })
+        );
+        assert!(PackedIsin::try_from(u64::MAX).is_err());
+        assert!(PackedIsin::try_from(PackedIsin::LIMIT).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip_and_order(a in "[A-Z]{2}[0-9A-Z]{9}", b in "[A-Z]{2}[0-9A-Z]{9}") {
+            let a = build_from_payload(&a).unwrap();
+            let b = build_from_payload(&b).unwrap();
+            let pa = PackedIsin::from(a);
+            let pb = PackedIsin::from(b);
+            prop_assert_eq!(ISIN::from(pa), a);
+            prop_assert_eq!(PackedIsin::try_from(pa.value()), Ok(pa));
+            prop_assert_eq!(a.cmp(&b), pa.cmp(&pb));
+        }
+    }
+}
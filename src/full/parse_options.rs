@@ -0,0 +1,319 @@
+//! # isin::parse_options
+//!
+//! A configurable parser, for inputs that need some combination of normalizations between the
+//! fully strict `parse()` and the `parse_loose()` that trims whitespace and ignores case.
+
+use crate::{
+    compute_check_digit, validate_check_digit_value, validate_payload_characters, ParseError, ISIN,
+};
+
+/// The separator characters removed by `ParseOptions::allow_separators()`.
+pub const DEFAULT_SEPARATORS: &str = " -.";
+
+/// What to do about the _Check Digit_ of an input that is otherwise in valid format.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CheckDigitPolicy {
+    /// Reject an incorrect _Check Digit_, as `parse()` does.
+    #[default]
+    Require,
+    /// Replace an incorrect _Check Digit_ with the correct one.
+    Fix,
+    /// Accept an incorrect _Check Digit_ as-is, as `ISIN::from_prevalidated_str()` does.
+    Ignore,
+}
+
+/// Options controlling how a string is normalized before it is validated as an ISIN.
+///
+/// The default options are as strict as `parse()`. Each option relaxes one requirement:
+///
+/// ```
+/// use isin::{CheckDigitPolicy, ParseOptions};
+///
+/// let options = ParseOptions::new()
+///     .trim_whitespace(true)
+///     .allow_lowercase(true)
+///     .allow_separators(true)
+///     .check_digit(CheckDigitPolicy::Fix);
+/// let isin = options.parse(" us-037833100-0 ").unwrap();
+/// assert_eq!(isin.to_string(), "US0378331005");
+/// ```
+///
+/// Whatever the options, a _Check Digit_ that is not a decimal digit is always rejected.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ParseOptions {
+    allow_lowercase: bool,
+    trim_whitespace: bool,
+    separators: Option<&'static str>,
+    check_digit: CheckDigitPolicy,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions::new()
+    }
+}
+
+impl ParseOptions {
+    /// Options as strict as `parse()`.
+    pub const fn new() -> ParseOptions {
+        ParseOptions {
+            allow_lowercase: false,
+            trim_whitespace: false,
+            separators: None,
+            check_digit: CheckDigitPolicy::Require,
+        }
+    }
+
+    /// Options as lenient as `parse_loose()`: leading and trailing whitespace is removed and
+    /// lowercase letters are accepted.
+    pub const fn loose() -> ParseOptions {
+        ParseOptions::new()
+            .trim_whitespace(true)
+            .allow_lowercase(true)
+    }
+
+    /// Accept lowercase letters, converting them to uppercase.
+    pub const fn allow_lowercase(mut self, allow: bool) -> ParseOptions {
+        self.allow_lowercase = allow;
+        self
+    }
+
+    /// Remove leading and trailing whitespace.
+    pub const fn trim_whitespace(mut self, trim: bool) -> ParseOptions {
+        self.trim_whitespace = trim;
+        self
+    }
+
+    /// Remove the [`DEFAULT_SEPARATORS`] (space, hyphen and period) wherever they appear, so
+    /// `US-037833100-5` and `US 0378 331 005` are accepted.
+    pub const fn allow_separators(mut self, allow: bool) -> ParseOptions {
+        self.separators = if allow {
+            Some(DEFAULT_SEPARATORS)
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Remove any of the characters in `separators` wherever they appear. This replaces the
+    /// [`DEFAULT_SEPARATORS`] set by `allow_separators()`.
+    pub const fn separators(mut self, separators: &'static str) -> ParseOptions {
+        self.separators = Some(separators);
+        self
+    }
+
+    /// Choose what to do about an incorrect _Check Digit_.
+    pub const fn check_digit(mut self, policy: CheckDigitPolicy) -> ParseOptions {
+        self.check_digit = policy;
+        self
+    }
+
+    /// Parse a string to a valid ISIN or an error according to these options. Length errors
+    /// report the length after whitespace and separators are removed, but invalid characters are
+    /// reported at their position in `value` as given.
+    pub fn parse(&self, value: &str) -> Result<ISIN, ParseError> {
+        self.parse_with(self.separators, value)
+    }
+
+    /// Internal function parsing with these options, but the given set of separators.
+    pub(crate) fn parse_with(
+        &self,
+        separators: Option<&str>,
+        value: &str,
+    ) -> Result<ISIN, ParseError> {
+        let start = if self.trim_whitespace {
+            value.len() - value.trim_start().len()
+        } else {
+            0
+        };
+        let trimmed = if self.trim_whitespace {
+            value.trim()
+        } else {
+            value
+        };
+
+        // Copy the input into a fixed buffer as we normalize it, counting the full normalized
+        // length even if it overflows so the length error is accurate. Non-ASCII characters are
+        // copied as their UTF-8 bytes, which then fail format validation as they do in `parse()`.
+        // We note where each byte came from, so errors can point at the original input.
+
+        let mut bb = [0u8; 12];
+        let mut origins = [0usize; 12];
+        let mut len = 0usize;
+        for (offset, c) in trimmed.char_indices() {
+            if separators.is_some_and(|s| s.contains(c)) {
+                continue;
+            }
+            let mut buf = [0u8; 4];
+            for (i, b) in c.encode_utf8(&mut buf).bytes().enumerate() {
+                if len < 12 {
+                    bb[len] = if self.allow_lowercase {
+                        b.to_ascii_uppercase()
+                    } else {
+                        b
+                    };
+                    origins[len] = start + offset + i;
+                }
+                len += 1;
+            }
+        }
+        if len != 12 {
+            return Err(ParseError::InvalidValueStringLength {
+                was: crate::error::error_len(len),
+            });
+        }
+
+        if let Err(ParseError::InvalidCharacter {
+            index, in_prefix, ..
+        }) = validate_payload_characters(&bb[0..11])
+        {
+            let index = origins[index as usize];
+            return Err(ParseError::invalid_character_in(
+                index,
+                value.as_bytes()[index],
+                in_prefix,
+            ));
+        }
+
+        match self.check_digit {
+            CheckDigitPolicy::Require => {
+                validate_check_digit_value(&bb[0..11], bb[11])?;
+            }
+            CheckDigitPolicy::Fix | CheckDigitPolicy::Ignore if !bb[11].is_ascii_digit() => {
+                return Err(ParseError::InvalidCheckDigit { was: bb[11] });
+            }
+            CheckDigitPolicy::Fix => {
+                bb[11] = compute_check_digit(&bb[0..11]);
+            }
+            CheckDigitPolicy::Ignore => {}
+        }
+
+        Ok(ISIN(bb))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, CheckDigit};
+    use proptest::prelude::*;
+
+    #[test]
+    fn default_is_strict() {
+        let options = ParseOptions::default();
+        assert_eq!(options.parse("US0378331005"), parse("US0378331005"));
+        assert_eq!(
+            options.parse(" US0378331005"),
+            Err(ParseError::InvalidValueStringLength { was: 13 })
+        );
+        assert_eq!(
+            options.parse("us0378331005"),
+            Err(ParseError::InvalidCharacter {
+                index: 0,
+                byte: b'u',
+                in_prefix: true
+            })
+        );
+    }
+
+    #[test]
+    fn separators() {
+        let options = ParseOptions::new().allow_separators(true);
+        let apple = parse("US0378331005").unwrap();
+        assert_eq!(options.parse("US-037833100-5"), Ok(apple));
+        assert_eq!(options.parse("US 0378 331 005"), Ok(apple));
+        assert_eq!(options.parse("US.037833100.5"), Ok(apple));
+        assert_eq!(
+            options.parse("US/037833100/5"),
+            Err(ParseError::InvalidValueStringLength { was: 14 })
+        );
+    }
+
+    #[test]
+    fn custom_separators() {
+        let options = ParseOptions::new().separators("/_");
+        let apple = parse("US0378331005").unwrap();
+        assert_eq!(options.parse("US/037833100_5"), Ok(apple));
+        assert_eq!(
+            options.parse("US-037833100-5"),
+            Err(ParseError::InvalidValueStringLength { was: 14 })
+        );
+        assert_eq!(
+            crate::parse_with_separators("US:037833100:5", ":"),
+            Ok(apple)
+        );
+        assert_eq!(
+            crate::parse_with_separators("US:03783310!:5", ":"),
+            Err(ParseError::InvalidCharacter {
+                index: 11,
+                byte: b'!',
+                in_prefix: false
+            })
+        );
+    }
+
+    #[test]
+    fn check_digit_policies() {
+        let apple = parse("US0378331005").unwrap();
+        assert_eq!(
+            ParseOptions::new().parse("US0378331006"),
+            Err(ParseError::IncorrectCheckDigit {
+                was: CheckDigit::from_value(6).unwrap(),
+                expected: CheckDigit::from_value(5).unwrap()
+            })
+        );
+        let fix = ParseOptions::new().check_digit(CheckDigitPolicy::Fix);
+        assert_eq!(fix.parse("US0378331006"), Ok(apple));
+        assert_eq!(
+            fix.parse("US037833100X"),
+            Err(ParseError::InvalidCheckDigit { was: b'X' })
+        );
+        let ignore = ParseOptions::new().check_digit(CheckDigitPolicy::Ignore);
+        assert_eq!(
+            ignore.parse("US0378331006"),
+            ISIN::from_prevalidated_str("US0378331006")
+        );
+    }
+
+    #[test]
+    fn report_positions_in_original_input() {
+        let options = ParseOptions::loose().allow_separators(true);
+        assert_eq!(
+            options.parse("  us-0378_3100-5"),
+            Err(ParseError::InvalidCharacter {
+                index: 9,
+                byte: b'_',
+                in_prefix: false
+            })
+        );
+        assert_eq!(
+            options.parse("\tu5 037833100 5"),
+            Err(ParseError::InvalidCharacter {
+                index: 2,
+                byte: b'5',
+                in_prefix: true
+            })
+        );
+    }
+
+    #[test]
+    fn reject_unicode() {
+        let options = ParseOptions::loose().allow_separators(true);
+        assert_eq!(
+            options.parse("US037833100٥"),
+            Err(ParseError::InvalidValueStringLength { was: 13 })
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn loose_matches_trimmed_uppercase(
+            s in "[ \t]?[a-zA-Z]{2}[0-9a-zA-Z]{9}[0-9][ \t]?|\\PC*"
+        ) {
+            prop_assert_eq!(
+                ParseOptions::loose().parse(&s).ok(),
+                parse(&s.trim().to_ascii_uppercase()).ok()
+            );
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! # isin::payload
+//!
+//! The _Payload_ of an ISIN (its _Prefix_ and _Basic Code_ together) as a type of its own, so it
+//! can be validated, stored and passed around before the _Check Digit_ is appended.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{ascii_str, validate_payload_format, BasicCode, BuildError, Prefix, ISIN};
+
+/// An ISIN _Payload_ in confirmed valid format: a _Prefix_ followed by a _Basic Code_.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash)]
+#[repr(transparent)]
+pub struct Payload([u8; 11]);
+
+impl Payload {
+    /// Internal constructor for bytes already known to be a valid _Payload_.
+    pub(crate) fn from_bytes_valid(bytes: [u8; 11]) -> Payload {
+        Payload(bytes)
+    }
+
+    /// Parse a string to a valid _Payload_ or an error, requiring the string to already be only
+    /// uppercase alphanumerics with no leading or trailing whitespace in addition to being the
+    /// right length and format.
+    pub fn parse(value: &str) -> Result<Payload, BuildError> {
+        if value.len() != 11 {
+            return Err(BuildError::InvalidPayloadStringLength {
+                was: crate::error::error_len(value.len()),
+            });
+        }
+
+        // We make the preliminary assumption that the string is pure ASCII, so we work with the
+        // underlying bytes. If there is Unicode in the string, the bytes will be outside the
+        // allowed range and format validations will fail.
+
+        let b = value.as_bytes();
+        validate_payload_format(b)?;
+
+        let mut bb = [0u8; 11];
+        bb.copy_from_slice(b);
+
+        Ok(Payload(bb))
+    }
+
+    /// Combine an already-validated _Prefix_ and _Basic Code_.
+    pub fn from_parts(prefix: Prefix, basic_code: BasicCode) -> Payload {
+        let mut bb = [0u8; 11];
+        bb[0..2].copy_from_slice(prefix.as_bytes());
+        bb[2..11].copy_from_slice(basic_code.as_bytes());
+        Payload(bb)
+    }
+
+    /// Return the _Payload_ as a string slice.
+    pub fn as_str(&self) -> &str {
+        ascii_str(&self.0)
+    }
+
+    /// Return the _Payload_ as a fixed-size array of its eleven ASCII bytes.
+    pub fn as_bytes(&self) -> &[u8; 11] {
+        &self.0
+    }
+
+    /// Return just the _Prefix_ portion of the _Payload_.
+    pub fn prefix(&self) -> Prefix {
+        Prefix::from_bytes_valid([self.0[0], self.0[1]])
+    }
+
+    /// Return just the _Basic Code_ portion of the _Payload_.
+    pub fn basic_code(&self) -> BasicCode {
+        let mut bb = [0u8; 9];
+        bb.copy_from_slice(&self.0[2..11]);
+        BasicCode::from_bytes_valid(bb)
+    }
+
+    /// Append the computed _Check Digit_ to make an ISIN.
+    pub fn to_isin(&self) -> ISIN {
+        ISIN::from_payload(*self)
+    }
+}
+
+impl AsRef<str> for Payload {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Debug for Payload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Payload({})", self.as_str())
+    }
+}
+
+impl FromStr for Payload {
+    type Err = BuildError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Payload::parse(s)
+    }
+}
+
+impl TryFrom<[u8; 11]> for Payload {
+    type Error = BuildError;
+
+    fn try_from(value: [u8; 11]) -> Result<Self, Self::Error> {
+        validate_payload_format(&value)?;
+        Ok(Payload(value))
+    }
+}
+
+impl From<Payload> for ISIN {
+    fn from(payload: Payload) -> Self {
+        ISIN::from_payload(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_apple() {
+        let payload: Payload = "US037833100".parse().unwrap();
+        assert_eq!(payload.as_str(), "US037833100");
+        assert_eq!(payload.as_bytes(), b"US037833100");
+        assert_eq!(payload.prefix(), "US");
+        assert_eq!(payload.basic_code(), "037833100");
+        assert_eq!(format!("{:?}", payload), "Payload(US037833100)");
+        assert_eq!(payload.to_isin().to_string(), "US0378331005");
+    }
+
+    #[test]
+    fn from_parts_matches_parse() {
+        let payload = Payload::from_parts(
+            Prefix::parse("DE").unwrap(),
+            BasicCode::parse("000A0GNPZ").unwrap(),
+        );
+        assert_eq!(Payload::parse("DE000A0GNPZ"), Ok(payload));
+        assert_eq!(ISIN::from(payload).to_string(), "DE000A0GNPZ3");
+    }
+
+    #[test]
+    fn reject_invalid() {
+        assert_eq!(
+            Payload::parse("US0378331005"),
+            Err(BuildError::InvalidPayloadStringLength { was: 12 })
+        );
+        assert_eq!(
+            Payload::parse("U1037833100"),
+            Err(BuildError::InvalidPrefix {
+                index: 1,
+                byte: b'1'
+            })
+        );
+        assert_eq!(
+            Payload::try_from(*b"US03783310-"),
+            Err(BuildError::InvalidBasicCode {
+                index: 8,
+                byte: b'-'
+            })
+        );
+    }
+
+    #[test]
+    fn typed_accessor() {
+        let isin = crate::parse("US0378331005").unwrap();
+        assert_eq!(isin.payload_typed(), Payload::parse("US037833100").unwrap());
+    }
+}
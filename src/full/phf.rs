@@ -0,0 +1,252 @@
+//! # isin::phf
+//!
+//! Static sets and maps of ISINs and _Prefixes_, built at compile time as perfect hash tables by
+//! [`phf`](https://docs.rs/phf), for fixed lists such as sanctioned instruments or the
+//! constituents of a benchmark.
+//!
+//! The [`isin_phf_set!`](crate::isin_phf_set), [`isin_phf_map!`](crate::isin_phf_map) and
+//! [`prefix_phf_set!`](crate::prefix_phf_set) macros take string literals, check at compile time
+//! that each is a valid ISIN (or _Prefix_), and can initialize a `static`:
+//!
+//! ```
+//! use isin::phf::{IsinPhfMap, IsinPhfSet, PrefixPhfSet};
+//!
+//! static WATCH_LIST: IsinPhfSet = isin::isin_phf_set! {
+//!     "US0378331005",
+//!     "US5949181045",
+//! };
+//!
+//! static WEIGHTS: IsinPhfMap<f64> = isin::isin_phf_map! {
+//!     "US0378331005" => 0.6,
+//!     "US5949181045" => 0.4,
+//! };
+//!
+//! static EUROZONE: PrefixPhfSet = isin::prefix_phf_set! { "DE", "FR", "IT" };
+//!
+//! let apple = isin::parse("US0378331005").unwrap();
+//! assert!(WATCH_LIST.contains(&apple));
+//! assert_eq!(WEIGHTS.get(&apple), Some(&0.6));
+//! assert!(!EUROZONE.contains(&apple.prefix_typed()));
+//! ```
+//!
+//! An invalid literal is a compile error:
+//!
+//! ```compile_fail
+//! static WATCH_LIST: isin::phf::IsinPhfSet = isin::isin_phf_set! { "US0378331006" };
+//! ```
+//!
+//! This module is only available with the `phf` feature enabled.
+
+use crate::checksum::is_valid_isin_bytes;
+use crate::{parse, Prefix, ISIN};
+
+#[doc(hidden)]
+pub use ::phf as __phf;
+
+/// A static set of ISINs made by [`isin_phf_set!`](crate::isin_phf_set).
+#[derive(Debug)]
+pub struct IsinPhfSet(::phf::Set<&'static str>);
+
+/// A static map from ISINs to values made by [`isin_phf_map!`](crate::isin_phf_map).
+#[derive(Debug)]
+pub struct IsinPhfMap<V: 'static>(::phf::Map<&'static str, V>);
+
+/// A static set of _Prefixes_ made by [`prefix_phf_set!`](crate::prefix_phf_set).
+#[derive(Debug)]
+pub struct PrefixPhfSet(::phf::Set<&'static str>);
+
+impl IsinPhfSet {
+    #[doc(hidden)]
+    pub const fn __from_phf(set: ::phf::Set<&'static str>) -> IsinPhfSet {
+        IsinPhfSet(set)
+    }
+
+    /// Return true if the ISIN is in the set.
+    pub fn contains(&self, isin: &ISIN) -> bool {
+        self.0.contains(AsRef::<str>::as_ref(isin))
+    }
+
+    /// Return the number of ISINs in the set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return true if the set contains no ISINs.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the ISINs in the set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = ISIN> + '_ {
+        // The keys were checked when the set was made, so none are skipped
+        self.0.iter().filter_map(|key| parse(key).ok())
+    }
+}
+
+impl<V> IsinPhfMap<V> {
+    #[doc(hidden)]
+    pub const fn __from_phf(map: ::phf::Map<&'static str, V>) -> IsinPhfMap<V> {
+        IsinPhfMap(map)
+    }
+
+    /// Return the value for an ISIN, if it is in the map.
+    pub fn get(&self, isin: &ISIN) -> Option<&V> {
+        self.0.get(AsRef::<str>::as_ref(isin))
+    }
+
+    /// Return true if the ISIN is in the map.
+    pub fn contains_key(&self, isin: &ISIN) -> bool {
+        self.0.contains_key(AsRef::<str>::as_ref(isin))
+    }
+
+    /// Return the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return true if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the entries of the map, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (ISIN, &V)> + '_ {
+        self.0
+            .entries()
+            .filter_map(|(key, value)| Some((parse(key).ok()?, value)))
+    }
+}
+
+impl PrefixPhfSet {
+    #[doc(hidden)]
+    pub const fn __from_phf(set: ::phf::Set<&'static str>) -> PrefixPhfSet {
+        PrefixPhfSet(set)
+    }
+
+    /// Return true if the _Prefix_ is in the set.
+    pub fn contains(&self, prefix: &Prefix) -> bool {
+        self.0.contains(prefix.as_str())
+    }
+
+    /// Return the number of _Prefixes_ in the set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Return true if the set contains no _Prefixes_.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the _Prefixes_ in the set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = Prefix> + '_ {
+        self.0.iter().filter_map(|key| Prefix::parse(key).ok())
+    }
+}
+
+/// Test at compile time whether or not a string is a valid ISIN, as `parse()` would.
+#[doc(hidden)]
+pub const fn __is_isin(value: &str) -> bool {
+    is_valid_isin_bytes(value.as_bytes())
+}
+
+/// Test at compile time whether or not a string is a _Prefix_ in valid format.
+#[doc(hidden)]
+pub const fn __is_prefix_str(value: &str) -> bool {
+    let b = value.as_bytes();
+    b.len() == 2 && is_prefix_bytes(b[0], b[1])
+}
+
+const fn is_prefix_bytes(a: u8, b: u8) -> bool {
+    a.is_ascii_uppercase() && b.is_ascii_uppercase()
+}
+
+/// Make a static [`IsinPhfSet`](crate::phf::IsinPhfSet) from string literals, each of which must
+/// be a valid ISIN. See the [`phf` module](crate::phf).
+#[macro_export]
+macro_rules! isin_phf_set {
+    ($($isin:literal),* $(,)?) => {{
+        use $crate::phf::__phf as phf;
+        const _: () = {
+            $(assert!($crate::phf::__is_isin($isin), concat!("not a valid ISIN: ", $isin));)*
+        };
+        $crate::phf::IsinPhfSet::__from_phf(phf::phf_set! { $($isin),* })
+    }};
+}
+
+/// Make a static [`IsinPhfMap`](crate::phf::IsinPhfMap) from string literal keys, each of which
+/// must be a valid ISIN, and constant values. See the [`phf` module](crate::phf).
+#[macro_export]
+macro_rules! isin_phf_map {
+    ($($isin:literal => $value:expr),* $(,)?) => {{
+        use $crate::phf::__phf as phf;
+        const _: () = {
+            $(assert!($crate::phf::__is_isin($isin), concat!("not a valid ISIN: ", $isin));)*
+        };
+        $crate::phf::IsinPhfMap::__from_phf(phf::phf_map! { $($isin => $value),* })
+    }};
+}
+
+/// Make a static [`PrefixPhfSet`](crate::phf::PrefixPhfSet) from string literals, each of which
+/// must be two uppercase ASCII letters. See the [`phf` module](crate::phf).
+#[macro_export]
+macro_rules! prefix_phf_set {
+    ($($prefix:literal),* $(,)?) => {{
+        use $crate::phf::__phf as phf;
+        const _: () = {
+            $(assert!(
+                $crate::phf::__is_prefix_str($prefix),
+                concat!("not a valid Prefix: ", $prefix)
+            );)*
+        };
+        $crate::phf::PrefixPhfSet::__from_phf(phf::phf_set! { $($prefix),* })
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static SET: IsinPhfSet = crate::isin_phf_set! { "US0378331005", "DE000A0GNPZ3" };
+    static EMPTY: IsinPhfSet = crate::isin_phf_set! {};
+    static MAP: IsinPhfMap<u32> = crate::isin_phf_map! { "US0378331005" => 1, "US5949181045" => 2 };
+    static PREFIXES: PrefixPhfSet = crate::prefix_phf_set! { "US", "EU", };
+
+    #[test]
+    fn lookups() {
+        let apple = parse("US0378331005").unwrap();
+        let microsoft = parse("US5949181045").unwrap();
+
+        assert!(SET.contains(&apple));
+        assert!(!SET.contains(&microsoft));
+        assert_eq!(SET.len(), 2);
+        let mut all: Vec<ISIN> = SET.iter().collect();
+        all.sort();
+        assert_eq!(all, vec![parse("DE000A0GNPZ3").unwrap(), apple]);
+        assert!(EMPTY.is_empty());
+
+        assert_eq!(MAP.get(&microsoft), Some(&2));
+        assert!(!MAP.contains_key(&parse("DE000A0GNPZ3").unwrap()));
+        assert_eq!(MAP.iter().count(), 2);
+
+        assert!(PREFIXES.contains(&Prefix::EU));
+        assert!(!PREFIXES.contains(&Prefix::XS));
+        assert_eq!(PREFIXES.iter().count(), 2);
+    }
+
+    #[test]
+    fn const_checks_match_parse() {
+        for value in [
+            "US0378331005",
+            "US0378331006",
+            "us0378331005",
+            "1S0378331005",
+            "",
+        ] {
+            assert_eq!(__is_isin(value), parse(value).is_ok(), "{value}");
+        }
+        assert!(__is_prefix_str("US"));
+        assert!(!__is_prefix_str("U1"));
+        assert!(!__is_prefix_str("USA"));
+    }
+}
@@ -0,0 +1,263 @@
+//! # isin::prefix
+//!
+//! The _Prefix_ of an ISIN as a type of its own, validated independently of any full ISIN.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{ascii_str, validate_prefix_format, BuildError};
+
+/// The officially assigned ISO 3166-1 alpha-2 country codes, as published by the ISO 3166
+/// Maintenance Agency. Update this list when the Agency's newsletters assign or withdraw a code,
+/// but move a withdrawn code to [`WITHDRAWN_CODES`] rather than dropping it if ISINs with it are
+/// still in use.
+const COUNTRY_CODES: &str = "\
+    AD AE AF AG AI AL AM AO AQ AR AS AT AU AW AX AZ \
+    BA BB BD BE BF BG BH BI BJ BL BM BN BO BQ BR BS BT BV BW BY BZ \
+    CA CC CD CF CG CH CI CK CL CM CN CO CR CU CV CW CX CY CZ \
+    DE DJ DK DM DO DZ \
+    EC EE EG EH ER ES ET \
+    FI FJ FK FM FO FR \
+    GA GB GD GE GF GG GH GI GL GM GN GP GQ GR GS GT GU GW GY \
+    HK HM HN HR HT HU \
+    ID IE IL IM IN IO IQ IR IS IT \
+    JE JM JO JP \
+    KE KG KH KI KM KN KP KR KW KY KZ \
+    LA LB LC LI LK LR LS LT LU LV LY \
+    MA MC MD ME MF MG MH MK ML MM MN MO MP MQ MR MS MT MU MV MW MX MY MZ \
+    NA NC NE NF NG NI NL NO NP NR NU NZ \
+    OM \
+    PA PE PF PG PH PK PL PM PN PR PS PT PW PY \
+    QA \
+    RE RO RS RU RW \
+    SA SB SC SD SE SG SH SI SJ SK SL SM SN SO SR SS ST SV SX SY SZ \
+    TC TD TF TG TH TJ TK TL TM TN TO TR TT TV TW TZ \
+    UA UG UM US UY UZ \
+    VA VC VE VG VI VN VU \
+    WF WS \
+    YE YT \
+    ZA ZM ZW";
+
+/// The _Prefixes_ allocated for ISINs that are not country codes: `EU` and `XS` (see the constants
+/// on [`Prefix`]), `EZ` for OTC derivatives, and `XA` through `XD` for substitute numbering
+/// agencies.
+const SPECIAL_CODES: &str = "EU EZ XA XB XC XD XS";
+
+/// Codes withdrawn from ISO 3166-1 that remain the _Prefixes_ of ISINs still in use, because an
+/// ISIN is never reissued when its country code changes: `AN` (Netherlands Antilles, as for
+/// Schlumberger's `AN8068571086`) and `CS` (Serbia and Montenegro).
+const WITHDRAWN_CODES: &str = "AN CS";
+
+/// Set the bits of a bitmap of _Prefixes_ for each of the space-separated codes.
+const fn add_codes(mut bits: [u32; 26], codes: &str) -> [u32; 26] {
+    let codes = codes.as_bytes();
+    let mut i = 0;
+    while i + 1 < codes.len() {
+        if codes[i].is_ascii_uppercase() {
+            bits[(codes[i] - b'A') as usize] |= 1 << (codes[i + 1] - b'A');
+            i += 2;
+        } else {
+            i += 1;
+        }
+    }
+    bits
+}
+
+/// The allocated _Prefixes_ as a bitmap: bit `j` of entry `i` is set if the _Prefix_ with letters
+/// `i` and `j` (counting from `A` as zero) is allocated.
+const ALLOCATED: [u32; 26] = add_codes(
+    add_codes(add_codes([0; 26], COUNTRY_CODES), SPECIAL_CODES),
+    WITHDRAWN_CODES,
+);
+
+/// An ISIN _Prefix_ in confirmed valid format: two uppercase ASCII letters.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash)]
+#[repr(transparent)]
+pub struct Prefix([u8; 2]);
+
+impl Prefix {
+    /// The special _Prefix_ for OTC derivatives.
+    pub const EZ: Prefix = Prefix(*b"EZ");
+
+    /// The _Prefix_ for securities issued by institutions of the European Union.
+    pub const EU: Prefix = Prefix(*b"EU");
+
+    /// The _Prefix_ for international securities deposited with the international central
+    /// securities depositories (Euroclear and Clearstream).
+    pub const XS: Prefix = Prefix(*b"XS");
+
+    /// Internal constructor for bytes already known to be a valid _Prefix_.
+    pub(crate) fn from_bytes_valid(bytes: [u8; 2]) -> Prefix {
+        Prefix(bytes)
+    }
+
+    /// Parse a string to a valid _Prefix_ or an error, requiring the string to already be two
+    /// uppercase ASCII letters with no leading or trailing whitespace.
+    pub fn parse(value: &str) -> Result<Prefix, BuildError> {
+        if value.len() != 2 {
+            return Err(BuildError::InvalidPrefixStringLength {
+                was: crate::error::error_len(value.len()),
+            });
+        }
+        let b = value.as_bytes();
+        validate_prefix_format(b)
+            .map_err(|(index, byte)| BuildError::InvalidPrefix { index, byte })?;
+
+        let mut bb = [0u8; 2];
+        bb.copy_from_slice(b);
+
+        Ok(Prefix(bb))
+    }
+
+    /// Return the _Prefix_ as a string slice.
+    pub fn as_str(&self) -> &str {
+        ascii_str(&self.0)
+    }
+
+    /// Return the _Prefix_ as a fixed-size array of its two ASCII bytes.
+    pub fn as_bytes(&self) -> &[u8; 2] {
+        &self.0
+    }
+
+    /// Whether or not this _Prefix_ has been allocated for use in ISINs: either an officially
+    /// assigned ISO 3166-1 alpha-2 country code, a withdrawn one that ISINs still in use have
+    /// (`AN` and `CS`), or one of the special _Prefixes_ `EU`, `EZ`, `XS` and `XA` through `XD`.
+    /// Any two uppercase letters are in valid format, but ISINs are only issued with allocated
+    /// _Prefixes_.
+    pub fn is_allocated(&self) -> bool {
+        ALLOCATED[(self.0[0] - b'A') as usize] & (1 << (self.0[1] - b'A')) != 0
+    }
+
+    /// Internal function returning the width of the national number in the _Basic Code_ of ISINs
+    /// with this _Prefix_, if it is a fixed-width code whose leading zeros are part of it: the
+    /// nine characters of a CUSIP for `US` and `CA`, and the seven of a SEDOL for `GB` and `IE`.
+    pub(crate) fn national_number_width(&self) -> Option<usize> {
+        match &self.0 {
+            b"US" | b"CA" => Some(9),
+            b"GB" | b"IE" => Some(7),
+            _ => None,
+        }
+    }
+}
+
+impl AsRef<str> for Prefix {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Debug for Prefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Prefix({})", self.as_str())
+    }
+}
+
+impl FromStr for Prefix {
+    type Err = BuildError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Prefix::parse(s)
+    }
+}
+
+impl TryFrom<[u8; 2]> for Prefix {
+    type Error = BuildError;
+
+    fn try_from(value: [u8; 2]) -> Result<Self, Self::Error> {
+        validate_prefix_format(&value)
+            .map_err(|(index, byte)| BuildError::InvalidPrefix { index, byte })?;
+        Ok(Prefix(value))
+    }
+}
+
+impl PartialEq<str> for Prefix {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Prefix {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_us() {
+        let prefix: Prefix = "US".parse().unwrap();
+        assert_eq!(prefix.as_str(), "US");
+        assert_eq!(prefix.as_bytes(), b"US");
+        assert_eq!(prefix.to_string(), "US");
+        assert_eq!(format!("{:?}", prefix), "Prefix(US)");
+        assert_eq!(prefix, "US");
+    }
+
+    #[test]
+    fn special_codes() {
+        assert_eq!(Prefix::parse("EZ"), Ok(Prefix::EZ));
+        assert_eq!(Prefix::parse("EU"), Ok(Prefix::EU));
+        assert_eq!(Prefix::parse("XS"), Ok(Prefix::XS));
+        assert!(Prefix::EU < Prefix::EZ);
+    }
+
+    #[test]
+    fn allocated() {
+        for code in [
+            "US", "DE", "JP", "AD", "ZW", "EU", "EZ", "XS", "XA", "XD", "AN", "CS",
+        ] {
+            assert!(Prefix::parse(code).unwrap().is_allocated(), "{code}");
+        }
+        for code in ["AA", "QQ", "UK", "XX", "XE", "ZZ", "EA"] {
+            assert!(!Prefix::parse(code).unwrap().is_allocated(), "{code}");
+        }
+    }
+
+    #[test]
+    fn withdrawn_codes_in_use() {
+        use crate::{validate_at, ValidationLevel};
+
+        // Schlumberger, incorporated in Curaçao, still has its Netherlands Antilles ISIN
+        assert_eq!(
+            validate_at(ValidationLevel::RegistryAllocated, "AN8068571086"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn reject_invalid() {
+        assert_eq!(
+            Prefix::parse("USA"),
+            Err(BuildError::InvalidPrefixStringLength { was: 3 })
+        );
+        assert_eq!(
+            Prefix::parse("us"),
+            Err(BuildError::InvalidPrefix {
+                index: 0,
+                byte: b'u'
+            })
+        );
+        assert_eq!(
+            Prefix::try_from(*b"U1"),
+            Err(BuildError::InvalidPrefix {
+                index: 1,
+                byte: b'1'
+            })
+        );
+    }
+
+    #[test]
+    fn typed_accessor() {
+        let isin = crate::parse("US0378331005").unwrap();
+        assert_eq!(isin.prefix_typed(), Prefix::parse("US").unwrap());
+    }
+}
@@ -0,0 +1,104 @@
+//! # isin::proptest
+//!
+//! [`proptest`](https://docs.rs/proptest) strategies generating ISINs with correct _Check
+//! Digits_, and strings that are not valid ISINs, for property tests of code that handles them.
+//!
+//! ```
+//! use isin::proptest::{invalid_isin, isin_with_prefix, valid_isin};
+//! use isin::Prefix;
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn valid_isins_round_trip(isin in valid_isin()) {
+//!         prop_assert_eq!(isin.to_string().parse::<isin::ISIN>(), Ok(isin));
+//!     }
+//!
+//!     fn prefixes_are_kept(isin in isin_with_prefix(Prefix::XS)) {
+//!         prop_assert_eq!(isin.prefix(), "XS");
+//!     }
+//!
+//!     fn invalid_isins_are_rejected(value in invalid_isin()) {
+//!         prop_assert!(isin::parse(&value).is_err());
+//!     }
+//! }
+//! # valid_isins_round_trip();
+//! # prefixes_are_kept();
+//! # invalid_isins_are_rejected();
+//! ```
+//!
+//! Valid ISINs shrink towards the smallest ISIN with the same _Prefix_, or `AA0000000006`.
+//!
+//! This module is only available with the `proptest` feature enabled.
+
+use ::proptest::prelude::*;
+
+use crate::{parse, Prefix, BASIC_CODE_COUNT, ISIN, PAYLOAD_COUNT};
+
+/// Generate any valid ISIN.
+pub fn valid_isin() -> impl Strategy<Value = ISIN> {
+    (0..PAYLOAD_COUNT).prop_map(|rank| ISIN::from_payload_rank(rank).expect("rank in range"))
+}
+
+/// Generate valid ISINs with the given _Prefix_.
+pub fn isin_with_prefix(prefix: Prefix) -> impl Strategy<Value = ISIN> {
+    let [a, b] = *prefix.as_bytes();
+    let first = ((a - b'A') as u64 * 26 + (b - b'A') as u64) * BASIC_CODE_COUNT;
+    (0..BASIC_CODE_COUNT)
+        .prop_map(move |code| ISIN::from_payload_rank(first + code).expect("rank in range"))
+}
+
+/// Generate strings that are not valid ISINs: valid ISINs with an incorrect _Check Digit_ or a
+/// lowercase letter, and strings of the wrong length or with characters not allowed in an ISIN.
+pub fn invalid_isin() -> impl Strategy<Value = String> {
+    prop_oneof![
+        (valid_isin(), 1..10u8).prop_map(|(isin, offset)| {
+            let mut bytes = *isin.as_bytes();
+            bytes[11] = b'0' + (bytes[11] - b'0' + offset) % 10;
+            String::from_utf8_lossy(&bytes).into_owned()
+        }),
+        (valid_isin(), 0..11usize).prop_map(|(isin, i)| {
+            let mut value = isin.to_string();
+            value[i..].make_ascii_lowercase();
+            value
+        }),
+        "[A-Z0-9]{0,11}|[A-Z0-9]{13,16}",
+        "[A-Z]{2}[0-9A-Z]{0,8}[^0-9A-Z][0-9A-Z]{0,8}",
+    ]
+    .prop_filter("must not be a valid ISIN", |value| parse(value).is_err())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::proptest::strategy::ValueTree;
+
+    proptest! {
+        #[test]
+        fn valid_isins_parse(isin in valid_isin()) {
+            prop_assert_eq!(parse(AsRef::<str>::as_ref(&isin)), Ok(isin));
+        }
+
+        #[test]
+        fn prefix_is_kept(isin in isin_with_prefix(Prefix::EU)) {
+            prop_assert_eq!(isin.prefix_typed(), Prefix::EU);
+        }
+
+        #[test]
+        fn invalid_isins_fail(value in invalid_isin()) {
+            prop_assert!(parse(&value).is_err());
+        }
+    }
+
+    #[test]
+    fn prefix_bounds() {
+        let mut runner = ::proptest::test_runner::TestRunner::deterministic();
+        for prefix in ["AA", "ZZ"] {
+            let prefix = Prefix::parse(prefix).unwrap();
+            let isin = isin_with_prefix(prefix)
+                .new_tree(&mut runner)
+                .unwrap()
+                .current();
+            assert_eq!(isin.prefix_typed(), prefix);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! # isin::range
+//!
+//! Iteration over blocks of consecutive ISINs sharing a _Prefix_, for pre-generating identifiers.
+
+use std::iter::FusedIterator;
+
+use crate::ISIN;
+
+/// An iterator over the ISINs with consecutive _Basic Codes_ from a start ISIN through an end ISIN
+/// (inclusive) with the same _Prefix_, computing each _Check Digit_ on the fly.
+///
+/// _Basic Codes_ count in base 36, with digits `0`&ndash;`9` followed by letters `A`&ndash;`Z`,
+/// so `000000009` is followed by `00000000A`.
+///
+/// ```
+/// use isin::IsinRange;
+///
+/// let start = isin::build_from_payload("US000000008").unwrap();
+/// let end = isin::build_from_payload("US00000000B").unwrap();
+/// let block: Vec<String> = IsinRange::new(start, end).unwrap().map(|i| i.to_string()).collect();
+/// assert_eq!(block, ["US0000000085", "US0000000093", "US00000000A7", "US00000000B5"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IsinRange {
+    /// The payload rank of the next ISIN from the front.
+    front: u64,
+    /// One past the payload rank of the next ISIN from the back.
+    back: u64,
+}
+
+impl IsinRange {
+    /// Make a range from `start` through `end` inclusive. Returns `None` if they have different
+    /// _Prefixes_. If `end` comes before `start` the range is empty.
+    ///
+    /// The _Check Digits_ of `start` and `end` play no part, only their _Payloads_.
+    pub fn new(start: ISIN, end: ISIN) -> Option<IsinRange> {
+        if start.prefix_bytes() != end.prefix_bytes() {
+            return None;
+        }
+        let front = start.payload_rank();
+        let back = end.payload_rank() + 1;
+        Some(IsinRange {
+            front,
+            back: back.max(front),
+        })
+    }
+}
+
+impl Iterator for IsinRange {
+    type Item = ISIN;
+
+    fn next(&mut self) -> Option<ISIN> {
+        if self.front == self.back {
+            return None;
+        }
+        let isin = ISIN::from_payload_rank(self.front);
+        self.front += 1;
+        isin
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        match usize::try_from(len) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<ISIN> {
+        self.front = self.front.saturating_add(n as u64).min(self.back);
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for IsinRange {
+    fn next_back(&mut self) -> Option<ISIN> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        ISIN::from_payload_rank(self.back)
+    }
+}
+
+impl FusedIterator for IsinRange {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_from_payload, parse};
+
+    #[test]
+    fn walk_forwards_and_backwards() {
+        let start = build_from_payload("DE00000000Y").unwrap();
+        let end = build_from_payload("DE000000011").unwrap();
+        let range = IsinRange::new(start, end).unwrap();
+        assert_eq!(range.size_hint(), (4, Some(4)));
+
+        let forwards: Vec<ISIN> = range.clone().collect();
+        assert_eq!(forwards.len(), 4);
+        assert_eq!(forwards[0], start);
+        assert_eq!(forwards[1].basic_code(), "00000000Z");
+        assert_eq!(forwards[2].basic_code(), "000000010");
+        assert_eq!(forwards[3], end);
+        for isin in &forwards {
+            assert_eq!(parse(isin.as_ref()), Ok(*isin));
+        }
+
+        let mut backwards: Vec<ISIN> = range.rev().collect();
+        backwards.reverse();
+        assert_eq!(backwards, forwards);
+    }
+
+    #[test]
+    fn meet_in_the_middle() {
+        let start = build_from_payload("US000000000").unwrap();
+        let end = build_from_payload("US000000002").unwrap();
+        let mut range = IsinRange::new(start, end).unwrap();
+        assert_eq!(range.next(), Some(start));
+        assert_eq!(range.next_back(), Some(end));
+        assert_eq!(range.next().unwrap().basic_code(), "000000001");
+        assert_eq!(range.next_back(), None);
+        assert_eq!(range.next(), None);
+    }
+
+    #[test]
+    fn edge_cases() {
+        let us = parse("US0378331005").unwrap();
+        let de = parse("DE000A0GNPZ3").unwrap();
+        assert!(IsinRange::new(us, de).is_none());
+
+        let later = us.with_basic_code("037833101").unwrap();
+        assert_eq!(IsinRange::new(later, us).unwrap().count(), 0);
+        assert_eq!(IsinRange::new(us, us).unwrap().count(), 1);
+
+        let last = build_from_payload("ZZZZZZZZZZZ").unwrap();
+        assert_eq!(IsinRange::new(last, last).unwrap().next(), Some(last));
+
+        let mut range = IsinRange::new(us, later).unwrap();
+        assert_eq!(range.nth(1), Some(later));
+        assert_eq!(range.nth(5), None);
+    }
+}
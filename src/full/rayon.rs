@@ -0,0 +1,200 @@
+//! # isin::rayon
+//!
+//! Parallel parsing and validation with the [`rayon`](https://docs.rs/rayon) data-parallelism
+//! library, for when there are too many values to check on one thread.
+//!
+//! The [`ParallelIsinExt`] trait adds adapters to any parallel iterator of strings:
+//!
+//! ```
+//! use isin::rayon::ParallelIsinExt;
+//! use rayon::prelude::*;
+//!
+//! let values = vec!["US0378331005", "US0378331006", "US5949181045"];
+//! let isins: Vec<_> = values.par_iter().par_parse().filter_map(Result::ok).collect();
+//! assert_eq!(isins.len(), 2);
+//! assert_eq!(values.par_iter().par_validate().filter(Result::is_err).count(), 1);
+//! ```
+//!
+//! The [`fix_all()`] and [`validate_lines()`] functions are parallel versions of the ones in the
+//! [`bulk`](crate::bulk) module, with the same results.
+//!
+//! This module is only available with the `rayon` feature enabled.
+
+use std::io::{self, BufRead};
+use std::ops::Range;
+use std::time::Instant;
+
+use ::rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, Map, ParallelIterator};
+
+use crate::bulk::{fix_one, strip_line_ending, FixReport, Report, DEFAULT_KEPT_FAILURES};
+use crate::{parse, validate, ParseError, ISIN};
+
+/// The number of lines [`validate_lines()`] reads before validating them in parallel.
+const BATCH_LINES: usize = 64 * 1024;
+
+/// Parallel adapters for iterators of strings. This is implemented for every `ParallelIterator`
+/// whose items can be viewed as strings, such as `&str` and `String`.
+pub trait ParallelIsinExt: ParallelIterator {
+    /// Parse each item as `parse()` does.
+    #[allow(clippy::type_complexity)]
+    fn par_parse(self) -> Map<Self, fn(Self::Item) -> Result<ISIN, ParseError>>;
+
+    /// Validate each item as `validate()` does.
+    #[allow(clippy::type_complexity)]
+    fn par_validate(self) -> Map<Self, fn(Self::Item) -> Result<(), ParseError>>;
+}
+
+impl<I> ParallelIsinExt for I
+where
+    I: ParallelIterator,
+    I::Item: AsRef<str>,
+{
+    fn par_parse(self) -> Map<Self, fn(Self::Item) -> Result<ISIN, ParseError>> {
+        self.map(|value| parse(value.as_ref()))
+    }
+
+    fn par_validate(self) -> Map<Self, fn(Self::Item) -> Result<(), ParseError>> {
+        self.map(|value| validate(value.as_ref()))
+    }
+}
+
+/// Attempt to parse every input in parallel, repairing those that can be repaired, as
+/// [`bulk::fix_all()`](crate::bulk::fix_all) does.
+pub fn fix_all<S>(inputs: &[S]) -> FixReport
+where
+    S: AsRef<str> + Sync,
+{
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("par_fix_all", inputs = inputs.len()).entered();
+
+    let outcomes: Vec<_> = inputs
+        .par_iter()
+        .enumerate()
+        .map(|(index, input)| fix_one(index, input.as_ref()))
+        .collect();
+
+    let mut report = FixReport::default();
+    for outcome in outcomes {
+        report.push(outcome);
+    }
+    #[cfg(feature = "tracing")]
+    report.trace();
+    report
+}
+
+/// Validate every line read from `reader` as an ISIN, as
+/// [`bulk::validate_lines()`](crate::bulk::validate_lines) does, validating batches of lines in
+/// parallel.
+pub fn validate_lines<R: BufRead>(reader: R) -> io::Result<Report> {
+    validate_lines_keeping(reader, DEFAULT_KEPT_FAILURES)
+}
+
+/// Validate every line read from `reader` as [`validate_lines()`] does, keeping the first
+/// `keep_failures` failures.
+///
+/// Lines are read on the calling thread, a batch at a time, and each batch is validated on the
+/// `rayon` thread pool before the next is read.
+pub fn validate_lines_keeping<R: BufRead>(
+    mut reader: R,
+    keep_failures: usize,
+) -> io::Result<Report> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("par_validate_lines", keep_failures).entered();
+
+    let start = Instant::now();
+    let mut report = Report::default();
+    let mut buf = Vec::new();
+    let mut lines: Vec<Range<usize>> = Vec::with_capacity(BATCH_LINES);
+    let mut first_line = 1;
+
+    loop {
+        buf.clear();
+        lines.clear();
+        while lines.len() < BATCH_LINES {
+            let begin = buf.len();
+            if reader.read_until(b'\n', &mut buf)? == 0 {
+                break;
+            }
+            lines.push(begin..buf.len());
+        }
+        if lines.is_empty() {
+            break;
+        }
+
+        let batch = lines
+            .par_iter()
+            .enumerate()
+            .fold(Report::default, |mut batch, (i, range)| {
+                let value = strip_line_ending(&buf[range.clone()]);
+                batch.record(first_line + i, value, keep_failures, &mut |_| {});
+                batch
+            })
+            .reduce(Report::default, |mut a, b| {
+                a.merge(b, keep_failures);
+                a
+            });
+        report.merge(batch, keep_failures);
+        first_line += lines.len();
+    }
+
+    report.finish(start);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bulk;
+    use ::rayon::iter::IntoParallelIterator;
+    use proptest::prelude::*;
+
+    #[test]
+    fn adapters_keep_order() {
+        let values = vec![
+            "US0378331005".to_string(),
+            "US0378331006".to_string(),
+            "us0378331005".to_string(),
+        ];
+        let parsed: Vec<_> = values.into_par_iter().par_parse().collect();
+        assert_eq!(parsed[0], parse("US0378331005"));
+        assert_eq!(parsed[1], parse("US0378331006"));
+        assert_eq!(parsed[2], parse("us0378331005"));
+    }
+
+    #[test]
+    fn validate_lines_across_batches() {
+        let mut data = String::new();
+        for i in 0..BATCH_LINES + 10 {
+            data.push_str(match i % 5 {
+                0 => "US0378331006\n",
+                1 => "\r\n",
+                _ => "US0378331005\n",
+            });
+        }
+
+        let mut expected = bulk::validate_lines(data.as_bytes()).unwrap();
+        let mut report = validate_lines(data.as_bytes()).unwrap();
+        expected.duration = Default::default();
+        report.duration = Default::default();
+        assert_eq!(report, expected);
+        assert_eq!(report.failures.len(), DEFAULT_KEPT_FAILURES);
+        assert_eq!(report.failures[1].line, 6);
+    }
+
+    proptest! {
+        #[test]
+        fn same_as_bulk(
+            lines in proptest::collection::vec("([A-Z]{2}[0-9A-Z]{9}[0-9])|( ?[a-zA-Z0-9]{0,13})", 0..50),
+            keep in 0usize..5,
+        ) {
+            prop_assert_eq!(fix_all(&lines), bulk::fix_all(&lines));
+
+            let data = lines.join("\n");
+            let mut expected = bulk::validate_lines_keeping(data.as_bytes(), keep).unwrap();
+            let mut report = validate_lines_keeping(data.as_bytes(), keep).unwrap();
+            expected.duration = Default::default();
+            report.duration = Default::default();
+            prop_assert_eq!(report, expected);
+        }
+    }
+}
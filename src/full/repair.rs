@@ -0,0 +1,227 @@
+//! # isin::repair
+//!
+//! Suggest the ISIN that was probably meant when an input is one typo away from being valid.
+//!
+//! The "double-add-double" _Check Digit_ is designed to catch the two most common data-entry
+//! mistakes: a single mistyped character, and two adjacent characters typed in the wrong order.
+//! Since such mistakes are detected, we can also try undoing them to find candidates for what was
+//! intended:
+//!
+//! ```
+//! use isin::repair;
+//!
+//! // The "3" and "7" of Apple's ISIN are swapped.
+//! let candidates = repair::transpositions("US0738331005");
+//! assert_eq!(candidates, vec![isin::parse("US0378331005").unwrap()]);
+//! ```
+//!
+//! Mistyped characters are more ambiguous than swapped ones, because there are usually several
+//! ways to change one character to get a valid ISIN. See
+//! [`Error::suggestion()`](crate::Error::suggestion) for picking a single most likely candidate
+//! for the error found.
+//!
+//! When the position of a bad character is known, such as one an OCR engine could not read, use
+//! [`solve()`] to find the characters there consistent with the _Check Digit_:
+//!
+//! ```
+//! use isin::repair;
+//!
+//! let candidates = repair::solve("US03783?100", '5');
+//! assert!(candidates.contains(&isin::parse("US0378331005").unwrap()));
+//! ```
+
+use crate::{is_payload_char, validate_fields, ISIN};
+
+/// Return the input as a 12-byte array, if it is the right length.
+fn as_array(input: &str) -> Option<[u8; 12]> {
+    input.as_bytes().try_into().ok()
+}
+
+/// Test whether or not a byte is allowed at the given position of an ISIN.
+fn is_isin_char(index: usize, b: u8) -> bool {
+    if index < 11 {
+        is_payload_char(index, b)
+    } else {
+        b.is_ascii_digit()
+    }
+}
+
+/// The valid ISINs produced by swapping two adjacent characters of the 12-character input, in
+/// order of the position of the swap.
+pub fn transpositions(input: &str) -> Vec<ISIN> {
+    let mut candidates = Vec::new();
+    if let Some(bb) = as_array(input) {
+        for index in 0..11 {
+            if let Some(isin) = transposed(&bb, index) {
+                candidates.push(isin);
+            }
+        }
+    }
+    candidates
+}
+
+/// The valid ISINs produced by replacing one character of the 12-character input with another
+/// allowed at that position, in order of the position and then the replacement character.
+pub fn substitutions(input: &str) -> Vec<ISIN> {
+    let mut candidates = Vec::new();
+    if let Some(bb) = as_array(input) {
+        for index in 0..12 {
+            candidates.extend(substituted(&bb, index));
+        }
+    }
+    candidates
+}
+
+/// All the valid ISINs one adjacent transposition or one substitution away from the 12-character
+/// input: the [`transpositions()`] followed by the [`substitutions()`].
+pub fn suggestions(input: &str) -> Vec<ISIN> {
+    let mut candidates = transpositions(input);
+    for isin in substitutions(input) {
+        if !candidates.contains(&isin) {
+            candidates.push(isin);
+        }
+    }
+    candidates
+}
+
+/// The character marking the unknown position in the input to [`solve()`].
+pub const PLACEHOLDER: char = '?';
+
+/// The valid ISINs made of the 11-character _Payload_ with one [`PLACEHOLDER`] replaced by a
+/// character allowed at that position, followed by `check_digit`, in order of the replacement
+/// character. The result is empty if `payload_with_placeholder` is the wrong length, does not have
+/// exactly one placeholder, or has other characters not allowed in a _Payload_, or if
+/// `check_digit` is not a decimal digit.
+///
+/// Since the _Check Digit_ has ten possible values, expect about one in ten of the characters
+/// allowed at the position to be consistent with it: two or three in the _Prefix_, and three or
+/// four in the _Basic Code_.
+pub fn solve(payload_with_placeholder: &str, check_digit: char) -> Vec<ISIN> {
+    let mut bb = [0u8; 12];
+    let mut unknown = None;
+    let mut len = 0;
+    for (index, c) in payload_with_placeholder.chars().enumerate() {
+        if index >= 11 {
+            return Vec::new();
+        }
+        if c == PLACEHOLDER {
+            if unknown.replace(index).is_some() {
+                return Vec::new();
+            }
+        } else if c.is_ascii() && is_isin_char(index, c as u8) {
+            bb[index] = c as u8;
+        } else {
+            return Vec::new();
+        }
+        len += 1;
+    }
+    match (unknown, len, check_digit) {
+        (Some(index), 11, '0'..='9') => {
+            bb[11] = check_digit as u8;
+            substituted(&bb, index).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The ISIN produced by swapping the characters at `index` and `index + 1`, if it is valid and
+/// differs from the input.
+pub(crate) fn transposed(bb: &[u8; 12], index: usize) -> Option<ISIN> {
+    if index >= 11 || bb[index] == bb[index + 1] {
+        return None;
+    }
+    let mut candidate = *bb;
+    candidate.swap(index, index + 1);
+    validate_fields(&candidate).ok().map(|_| ISIN(candidate))
+}
+
+/// The valid ISINs produced by replacing the character at `index` with another allowed there.
+pub(crate) fn substituted(bb: &[u8; 12], index: usize) -> impl Iterator<Item = ISIN> + '_ {
+    (b'0'..=b'9')
+        .chain(b'A'..=b'Z')
+        .filter(move |&b| b != bb[index] && is_isin_char(index, b))
+        .filter_map(move |b| {
+            let mut candidate = *bb;
+            candidate[index] = b;
+            validate_fields(&candidate).ok().map(|_| ISIN(candidate))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, ParseError};
+
+    #[test]
+    fn transposition_of_apple() {
+        let apple = parse("US0378331005").unwrap();
+        assert_eq!(transpositions("US3078331005"), vec![apple]);
+        assert!(transpositions("US0387331005").contains(&apple));
+        assert!(transpositions("US0378331001").is_empty());
+        assert!(transpositions("US037833100").is_empty());
+    }
+
+    #[test]
+    fn substitutions_are_valid_and_one_character_away() {
+        let apple = parse("US0378331005").unwrap();
+        let candidates = substitutions("US0378331006");
+        assert!(candidates.len() > 1);
+        assert!(candidates.contains(&apple));
+        for isin in candidates {
+            assert_eq!(
+                isin.as_bytes()
+                    .iter()
+                    .zip(b"US0378331006")
+                    .filter(|(a, b)| a != b)
+                    .count(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn suggestions_have_no_duplicates() {
+        let candidates = suggestions("US0378331050");
+        for (i, isin) in candidates.iter().enumerate() {
+            assert!(!candidates[i + 1..].contains(isin));
+            assert!(parse(isin.as_ref()).is_ok());
+        }
+    }
+
+    #[test]
+    fn solve_for_one_unknown() {
+        let apple = parse("US0378331005").unwrap();
+        for index in 0..11 {
+            let mut payload = b"US037833100".to_vec();
+            payload[index] = b'?';
+            let payload = String::from_utf8(payload).unwrap();
+            let candidates = solve(&payload, '5');
+            assert!(candidates.contains(&apple), "{payload}");
+            for isin in candidates {
+                assert_eq!(isin.check_digit(), '5');
+                assert!(parse(isin.as_ref()).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    fn solve_needs_exactly_one_unknown() {
+        assert!(solve("US037833100", '5').is_empty());
+        assert!(solve("US0378??100", '5').is_empty());
+        assert!(solve("US03783?10", '5').is_empty());
+        assert!(solve("US03783?1000", '5').is_empty());
+        assert!(solve("US03783?10-", '5').is_empty());
+        assert!(solve("US03783?100", 'X').is_empty());
+        assert!(solve("US03783?10é", '5').is_empty());
+    }
+
+    #[test]
+    fn nothing_for_bad_length() {
+        assert!(suggestions("").is_empty());
+        assert!(suggestions("US03783310055").is_empty());
+        assert_eq!(
+            parse("US03783310055"),
+            Err(ParseError::InvalidValueStringLength { was: 13 })
+        );
+    }
+}
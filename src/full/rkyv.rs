@@ -0,0 +1,168 @@
+//! # isin::rkyv
+//!
+//! Zero-copy archiving of ISINs with [`rkyv`](https://docs.rs/rkyv). An `ISIN` is archived as an
+//! [`ArchivedISIN`]: its twelve ASCII bytes, with no padding and an alignment of one, so it can be
+//! used directly from a memory-mapped file.
+//!
+//! Validating an archive with `rkyv::access()` checks that each archived ISIN is valid, as for
+//! `parse_bytes()`, so an archive with a corrupted ISIN is rejected rather than yielding one:
+//!
+//! ```
+//! use isin::rkyv::ArchivedISIN;
+//! use rkyv::rancor::Error;
+//! use rkyv::vec::ArchivedVec;
+//!
+//! let isins = vec![isin::parse("US0378331005").unwrap(), isin::parse("US5949181045").unwrap()];
+//! let mut bytes = rkyv::to_bytes::<Error>(&isins).unwrap();
+//!
+//! let archived = rkyv::access::<ArchivedVec<ArchivedISIN>, Error>(&bytes).unwrap();
+//! assert_eq!(archived[1], isins[1]);
+//! assert_eq!(archived[1].as_str(), "US5949181045");
+//!
+//! let at = bytes.windows(12).position(|w| w == b"US5949181045").unwrap();
+//! bytes[at + 11] = b'6';
+//! assert!(rkyv::access::<ArchivedVec<ArchivedISIN>, Error>(&bytes).is_err());
+//! ```
+//!
+//! This module is only available with the `rkyv` feature enabled, and not with the
+//! `forbid-unsafe` feature.
+
+use std::fmt;
+
+use ::rkyv::bytecheck::CheckBytes;
+use ::rkyv::rancor::{Fallible, Source};
+use ::rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+
+use crate::{parse_bytes, ISIN};
+
+/// The archived form of an [`ISIN`]: its twelve ASCII bytes.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ArchivedISIN([u8; 12]);
+
+// SAFETY: `ArchivedISIN` is a transparent wrapper of a byte array, so it has the same layout on
+// every platform and no interior mutability.
+unsafe impl Portable for ArchivedISIN {}
+
+// SAFETY: `check_bytes()` only returns `Ok` if the bytes are a valid ISIN.
+unsafe impl<C> CheckBytes<C> for ArchivedISIN
+where
+    C: Fallible + ?Sized,
+    C::Error: Source,
+{
+    unsafe fn check_bytes(value: *const Self, _: &mut C) -> Result<(), C::Error> {
+        // SAFETY: The caller guarantees that `value` is aligned and points to twelve initialized
+        // bytes, and any bytes are a valid `[u8; 12]`.
+        let bytes = unsafe { &(*value).0 };
+        parse_bytes(bytes).map(|_| ()).map_err(C::Error::new)
+    }
+}
+
+impl ArchivedISIN {
+    /// Return the archived ISIN as an `ISIN`.
+    pub fn to_isin(&self) -> ISIN {
+        // Archived ISINs are valid: they were checked when the archive was accessed, or the
+        // caller of `rkyv::access_unchecked()` guaranteed the archive was valid.
+        ISIN(self.0)
+    }
+
+    /// Return the twelve bytes of the archived ISIN.
+    pub fn as_bytes(&self) -> &[u8; 12] {
+        &self.0
+    }
+
+    /// Return the archived ISIN as a string.
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("archived ISINs are ASCII")
+    }
+}
+
+impl fmt::Display for ArchivedISIN {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for ArchivedISIN {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ArchivedISIN({})", self.as_str())
+    }
+}
+
+impl PartialEq<ISIN> for ArchivedISIN {
+    fn eq(&self, other: &ISIN) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl PartialEq<ArchivedISIN> for ISIN {
+    fn eq(&self, other: &ArchivedISIN) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Archive for ISIN {
+    type Archived = ArchivedISIN;
+    type Resolver = ();
+
+    fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+        // SAFETY: `ArchivedISIN` is a transparent wrapper of `[u8; 12]`.
+        let out = unsafe { out.cast_unchecked::<[u8; 12]>() };
+        out.write(self.0);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for ISIN {
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<ISIN, D> for ArchivedISIN {
+    fn deserialize(&self, _: &mut D) -> Result<ISIN, D::Error> {
+        Ok(self.to_isin())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use ::rkyv::rancor::Error;
+    use proptest::prelude::*;
+
+    #[test]
+    fn archived_layout() {
+        let apple = parse("US0378331005").unwrap();
+        let bytes = ::rkyv::to_bytes::<Error>(&apple).unwrap();
+        assert_eq!(&bytes[..], b"US0378331005");
+        assert_eq!(std::mem::align_of::<ArchivedISIN>(), 1);
+
+        let archived = ::rkyv::access::<ArchivedISIN, Error>(&bytes).unwrap();
+        assert_eq!(archived.to_string(), "US0378331005");
+        assert_eq!(format!("{archived:?}"), "ArchivedISIN(US0378331005)");
+        assert_eq!(::rkyv::deserialize::<ISIN, Error>(archived).unwrap(), apple);
+    }
+
+    #[test]
+    fn reject_invalid_archives() {
+        for bytes in [&b"US0378331006"[..], b"us0378331005", b"US03783310\xff5"] {
+            let mut aligned = ::rkyv::util::AlignedVec::<16>::new();
+            aligned.extend_from_slice(bytes);
+            assert!(::rkyv::access::<ArchivedISIN, Error>(&aligned).is_err());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(payloads in proptest::collection::vec("[A-Z]{2}[0-9A-Z]{9}", 0..20)) {
+            let isins: Vec<ISIN> = payloads
+                .iter()
+                .map(|p| crate::build_from_payload(p).unwrap())
+                .collect();
+            let bytes = ::rkyv::to_bytes::<Error>(&isins).unwrap();
+            let back = ::rkyv::from_bytes::<Vec<ISIN>, Error>(&bytes).unwrap();
+            prop_assert_eq!(back, isins);
+        }
+    }
+}
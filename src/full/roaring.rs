@@ -0,0 +1,332 @@
+//! # isin::roaring
+//!
+//! A compressed set of ISINs backed by a [roaring bitmap](https://roaringbitmap.org/), for fast set
+//! algebra over very large universes of identifiers.
+//!
+//! Each ISIN is mapped to a dense 64-bit key: the rank of its _Payload_ among all possible
+//! payloads (two base-26 letters followed by nine base-36 characters). The _Check Digit_ is implied
+//! by the _Payload_, so it need not be stored. The key preserves the ordering of ISINs, so
+//! iteration yields ISINs in ascending order. It is the [`PackedIsin`] value without its last
+//! decimal digit (the _Check Digit_), so sets can be built from and iterated as packed values too.
+//!
+//! Overlap between two sets can be measured without building the combined set:
+//!
+//! ```
+//! use isin::roaring::IsinRoaring;
+//!
+//! let portfolio: IsinRoaring = ["US0378331005", "US5949181045"]
+//!     .iter()
+//!     .map(|s| isin::parse(s).unwrap())
+//!     .collect();
+//! let benchmark: IsinRoaring = ["US5949181045", "DE000A0GNPZ3"]
+//!     .iter()
+//!     .map(|s| isin::parse(s).unwrap())
+//!     .collect();
+//! assert_eq!(portfolio.intersection_len(&benchmark), 1);
+//! assert!(!portfolio.is_disjoint(&benchmark));
+//! ```
+//!
+//! This module is only available with the `roaring` feature enabled.
+
+use std::io;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
+
+use ::roaring::RoaringTreemap;
+
+use crate::{PackedIsin, ISIN};
+
+/// A set of ISINs stored as a compressed bitmap over their _Payload_ ranks.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct IsinRoaring(RoaringTreemap);
+
+impl IsinRoaring {
+    /// Create an empty set.
+    pub fn new() -> IsinRoaring {
+        IsinRoaring(RoaringTreemap::new())
+    }
+
+    /// Add an ISIN to the set, returning true if it was not already present.
+    pub fn insert(&mut self, isin: ISIN) -> bool {
+        self.0.insert(isin.payload_rank())
+    }
+
+    /// Remove an ISIN from the set, returning true if it was present.
+    pub fn remove(&mut self, isin: &ISIN) -> bool {
+        self.0.remove(isin.payload_rank())
+    }
+
+    /// Return true if the ISIN is in the set.
+    pub fn contains(&self, isin: &ISIN) -> bool {
+        self.0.contains(isin.payload_rank())
+    }
+
+    /// Return the number of ISINs in the set.
+    pub fn len(&self) -> u64 {
+        self.0.len()
+    }
+
+    /// Return true if the set contains no ISINs.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the ISINs in the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = ISIN> + '_ {
+        self.0.iter().filter_map(ISIN::from_payload_rank)
+    }
+
+    /// Return the ISINs in either this set or the other one.
+    pub fn union(&self, other: &IsinRoaring) -> IsinRoaring {
+        IsinRoaring(&self.0 | &other.0)
+    }
+
+    /// Return the ISINs in both this set and the other one.
+    pub fn intersection(&self, other: &IsinRoaring) -> IsinRoaring {
+        IsinRoaring(&self.0 & &other.0)
+    }
+
+    /// Return the ISINs in this set but not the other one.
+    pub fn difference(&self, other: &IsinRoaring) -> IsinRoaring {
+        IsinRoaring(&self.0 - &other.0)
+    }
+
+    /// Return the ISINs in exactly one of this set and the other one.
+    pub fn symmetric_difference(&self, other: &IsinRoaring) -> IsinRoaring {
+        IsinRoaring(&self.0 ^ &other.0)
+    }
+
+    /// Iterate over the ISINs in the set in ascending order, in their packed form.
+    pub fn iter_packed(&self) -> impl Iterator<Item = PackedIsin> + '_ {
+        self.iter().map(PackedIsin::from)
+    }
+
+    /// Return the number of ISINs in both this set and the other one, without building the
+    /// intersection.
+    pub fn intersection_len(&self, other: &IsinRoaring) -> u64 {
+        self.0.intersection_len(&other.0)
+    }
+
+    /// Return the number of ISINs in either this set or the other one, without building the
+    /// union.
+    pub fn union_len(&self, other: &IsinRoaring) -> u64 {
+        self.0.union_len(&other.0)
+    }
+
+    /// Return the number of ISINs in this set but not the other one, without building the
+    /// difference.
+    pub fn difference_len(&self, other: &IsinRoaring) -> u64 {
+        self.0.difference_len(&other.0)
+    }
+
+    /// Return true if every ISIN in this set is also in the other one.
+    pub fn is_subset(&self, other: &IsinRoaring) -> bool {
+        self.0.is_subset(&other.0)
+    }
+
+    /// Return true if no ISIN is in both this set and the other one.
+    pub fn is_disjoint(&self, other: &IsinRoaring) -> bool {
+        self.0.is_disjoint(&other.0)
+    }
+
+    /// Return the number of bytes `serialize_into()` will write.
+    pub fn serialized_size(&self) -> usize {
+        self.0.serialized_size()
+    }
+
+    /// Write the set in the portable roaring serialization format.
+    pub fn serialize_into<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        self.0.serialize_into(writer)
+    }
+
+    /// Read a set previously written by `serialize_into()`.
+    ///
+    /// Keys that do not correspond to any _Payload_ are rejected with an error of kind
+    /// [`io::ErrorKind::InvalidData`].
+    pub fn deserialize_from<R: io::Read>(reader: R) -> io::Result<IsinRoaring> {
+        let map = RoaringTreemap::deserialize_from(reader)?;
+        match map.max() {
+            Some(max) if ISIN::from_payload_rank(max).is_none() => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "key out of range for an ISIN payload",
+            )),
+            _ => Ok(IsinRoaring(map)),
+        }
+    }
+}
+
+impl FromIterator<ISIN> for IsinRoaring {
+    fn from_iter<I: IntoIterator<Item = ISIN>>(iter: I) -> Self {
+        IsinRoaring(iter.into_iter().map(|isin| isin.payload_rank()).collect())
+    }
+}
+
+impl Extend<ISIN> for IsinRoaring {
+    fn extend<I: IntoIterator<Item = ISIN>>(&mut self, iter: I) {
+        self.0
+            .extend(iter.into_iter().map(|isin| isin.payload_rank()))
+    }
+}
+
+impl FromIterator<PackedIsin> for IsinRoaring {
+    fn from_iter<I: IntoIterator<Item = PackedIsin>>(iter: I) -> Self {
+        iter.into_iter().map(ISIN::from).collect()
+    }
+}
+
+impl Extend<PackedIsin> for IsinRoaring {
+    fn extend<I: IntoIterator<Item = PackedIsin>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(ISIN::from))
+    }
+}
+
+impl BitOr for &IsinRoaring {
+    type Output = IsinRoaring;
+
+    fn bitor(self, rhs: Self) -> IsinRoaring {
+        self.union(rhs)
+    }
+}
+
+impl BitAnd for &IsinRoaring {
+    type Output = IsinRoaring;
+
+    fn bitand(self, rhs: Self) -> IsinRoaring {
+        self.intersection(rhs)
+    }
+}
+
+impl Sub for &IsinRoaring {
+    type Output = IsinRoaring;
+
+    fn sub(self, rhs: Self) -> IsinRoaring {
+        self.difference(rhs)
+    }
+}
+
+impl BitXor for &IsinRoaring {
+    type Output = IsinRoaring;
+
+    fn bitxor(self, rhs: Self) -> IsinRoaring {
+        self.symmetric_difference(rhs)
+    }
+}
+
+impl BitOrAssign<&IsinRoaring> for IsinRoaring {
+    fn bitor_assign(&mut self, rhs: &IsinRoaring) {
+        self.0 |= &rhs.0;
+    }
+}
+
+impl BitAndAssign<&IsinRoaring> for IsinRoaring {
+    fn bitand_assign(&mut self, rhs: &IsinRoaring) {
+        self.0 &= &rhs.0;
+    }
+}
+
+impl SubAssign<&IsinRoaring> for IsinRoaring {
+    fn sub_assign(&mut self, rhs: &IsinRoaring) {
+        self.0 -= &rhs.0;
+    }
+}
+
+impl BitXorAssign<&IsinRoaring> for IsinRoaring {
+    fn bitxor_assign(&mut self, rhs: &IsinRoaring) {
+        self.0 ^= &rhs.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn set_of(values: &[&str]) -> IsinRoaring {
+        values.iter().map(|s| parse(s).unwrap()).collect()
+    }
+
+    #[test]
+    fn membership() {
+        let mut set = IsinRoaring::new();
+        let apple = parse("US0378331005").unwrap();
+        assert!(set.is_empty());
+        assert!(set.insert(apple));
+        assert!(!set.insert(apple));
+        assert!(set.contains(&apple));
+        assert!(!set.contains(&parse("DE000A0GNPZ3").unwrap()));
+        assert_eq!(set.len(), 1);
+        assert!(set.remove(&apple));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn set_algebra() {
+        let a = set_of(&["US0378331005", "DE000A0GNPZ3", "JP3788600009"]);
+        let b = set_of(&["DE000A0GNPZ3", "JP3788600009", "GB00BF0FCW58"]);
+
+        assert_eq!((&a | &b).len(), 4);
+        assert_eq!(
+            (&a & &b).iter().collect::<Vec<_>>(),
+            vec![
+                parse("DE000A0GNPZ3").unwrap(),
+                parse("JP3788600009").unwrap()
+            ]
+        );
+        assert_eq!(
+            (&a - &b).iter().collect::<Vec<_>>(),
+            vec![parse("US0378331005").unwrap()]
+        );
+        assert_eq!((&a ^ &b).len(), 2);
+    }
+
+    #[test]
+    fn overlap_and_in_place_algebra() {
+        let a = set_of(&["US0378331005", "DE000A0GNPZ3", "JP3788600009"]);
+        let b = set_of(&["DE000A0GNPZ3", "JP3788600009", "GB00BF0FCW58"]);
+
+        assert_eq!(a.intersection_len(&b), 2);
+        assert_eq!(a.union_len(&b), 4);
+        assert_eq!(a.difference_len(&b), 1);
+        assert!((&a & &b).is_subset(&a));
+        assert!(!a.is_subset(&b));
+        assert!((&a - &b).is_disjoint(&b));
+
+        let mut c = a.clone();
+        c |= &b;
+        assert_eq!(c, &a | &b);
+        c &= &b;
+        assert_eq!(c, b);
+        c -= &a;
+        assert_eq!(c, &b - &a);
+        c ^= &a;
+        assert_eq!(c, &(&b - &a) ^ &a);
+    }
+
+    #[test]
+    fn packed_round_trip() {
+        let set = set_of(&["US0378331005", "DE000A0GNPZ3"]);
+        let packed: Vec<PackedIsin> = set.iter_packed().collect();
+        assert_eq!(packed[0], PackedIsin::from(parse("DE000A0GNPZ3").unwrap()));
+        assert_eq!(packed.iter().copied().collect::<IsinRoaring>(), set);
+    }
+
+    #[test]
+    fn serialization_round_trip() {
+        let mut set = set_of(&["US0378331005", "DE000A0GNPZ3"]);
+        set.insert(crate::build_from_payload("ZZZZZZZZZZZ").unwrap());
+        let mut buffer = Vec::with_capacity(set.serialized_size());
+        set.serialize_into(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), set.serialized_size());
+        let read = IsinRoaring::deserialize_from(&buffer[..]).unwrap();
+        assert_eq!(read, set);
+    }
+
+    #[test]
+    fn reject_out_of_range_keys() {
+        let mut map = RoaringTreemap::new();
+        map.insert(u64::MAX);
+        let mut buffer = Vec::new();
+        map.serialize_into(&mut buffer).unwrap();
+        let err = IsinRoaring::deserialize_from(&buffer[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,471 @@
+//! # isin::scanner
+//!
+//! Find ISINs in free text, such as emails, log lines or documents converted to text.
+//!
+//! A _candidate_ is any twelve characters that have the format of an ISIN: two ASCII uppercase
+//! letters, nine ASCII uppercase letters or digits, and a digit. Each candidate is reported with
+//! its byte range in the text and the result of parsing it, so a candidate with an incorrect
+//! _Check Digit_ (a likely typo) is reported rather than silently skipped:
+//!
+//! ```
+//! use isin::scanner::find_isins;
+//!
+//! let text = "Bought US0378331005 and US5949181046 (ISIN:US0000000000).";
+//! let hits: Vec<_> = find_isins(text).collect();
+//! assert_eq!(hits.len(), 3);
+//!
+//! let (range, result) = &hits[0];
+//! assert_eq!(&text[range.clone()], "US0378331005");
+//! assert_eq!(result.unwrap().to_string(), "US0378331005");
+//! assert!(hits[1].1.is_err());
+//! ```
+//!
+//! Candidates never overlap. By default a candidate must not be immediately preceded or followed
+//! by an ASCII letter or digit, so the middle of a longer code is not mistaken for an ISIN. Use
+//! [`ScanOptions`] to choose another [`Boundary`].
+//!
+//! Text too large to hold in memory can be scanned from any [`io::Read`] with
+//! [`find_isins_in_reader()`] or [`ScanOptions::scan_reader()`], which read it a block at a time
+//! and report candidates by their byte offsets in the stream.
+//!
+//! With the `tracing` feature enabled, the scanners emit a `TRACE` event for each candidate, and a
+//! `ReadScanner` emits a `DEBUG` event for each block it reads.
+
+use std::io::{self, Read};
+use std::ops::Range;
+
+use crate::{is_payload_char, parse_bytes, Error, ISIN};
+
+/// What must surround a candidate for it to be reported.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Boundary {
+    /// The start or end of the text, or any character other than an ASCII letter or digit.
+    #[default]
+    NotAlphanumeric,
+    /// The start or end of the text, or ASCII whitespace. Use this for text known to separate
+    /// identifiers with whitespace only, so `ISIN:US0378331005` is not reported.
+    Whitespace,
+    /// Anything. Every run of twelve characters with the format of an ISIN is a candidate, even in
+    /// the middle of a longer code.
+    None,
+}
+
+impl Boundary {
+    /// Test whether or not the byte next to a candidate (or `None` at the start or end of the
+    /// text) is allowed by this boundary.
+    pub(crate) fn allows(self, b: Option<u8>) -> bool {
+        match (self, b) {
+            (_, None) | (Boundary::None, _) => true,
+            (Boundary::NotAlphanumeric, Some(b)) => !b.is_ascii_alphanumeric(),
+            (Boundary::Whitespace, Some(b)) => b.is_ascii_whitespace(),
+        }
+    }
+}
+
+/// Options controlling which candidates a [`Scanner`] reports.
+///
+/// ```
+/// use isin::scanner::{Boundary, ScanOptions};
+///
+/// let text = "ISIN:US0378331005 XUS0378331005X";
+/// assert_eq!(ScanOptions::new().scan(text).count(), 1);
+/// assert_eq!(ScanOptions::new().boundary(Boundary::Whitespace).scan(text).count(), 0);
+/// assert_eq!(ScanOptions::new().boundary(Boundary::None).scan(text).count(), 2);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ScanOptions {
+    boundary: Boundary,
+    valid_only: bool,
+}
+
+impl ScanOptions {
+    /// The default options: [`Boundary::NotAlphanumeric`], reporting invalid candidates too.
+    pub const fn new() -> ScanOptions {
+        ScanOptions {
+            boundary: Boundary::NotAlphanumeric,
+            valid_only: false,
+        }
+    }
+
+    /// Choose what must surround a candidate.
+    pub const fn boundary(mut self, boundary: Boundary) -> ScanOptions {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Report only the candidates that are valid ISINs.
+    pub const fn valid_only(mut self, valid_only: bool) -> ScanOptions {
+        self.valid_only = valid_only;
+        self
+    }
+
+    /// Scan `text` with these options.
+    pub fn scan(self, text: &str) -> Scanner<'_> {
+        Scanner {
+            text: text.as_bytes(),
+            pos: 0,
+            options: self,
+        }
+    }
+
+    /// Scan everything read from `reader` with these options. The reader is read in blocks of
+    /// [`READ_BLOCK_SIZE`] bytes, so it need not be buffered.
+    pub fn scan_reader<R: Read>(self, reader: R) -> ReadScanner<R> {
+        ReadScanner {
+            reader,
+            buf: Vec::new(),
+            pos: 0,
+            offset: 0,
+            eof: false,
+            options: self,
+        }
+    }
+}
+
+/// Scan `text` for ISINs with the default [`ScanOptions`].
+pub fn find_isins(text: &str) -> Scanner<'_> {
+    ScanOptions::new().scan(text)
+}
+
+/// Scan everything read from `reader` for ISINs with the default [`ScanOptions`].
+///
+/// ```
+/// use isin::scanner::find_isins_in_reader;
+///
+/// let log = "2024-01-02 BUY US0378331005 100\n2024-01-03 SELL US5949181045 50\n";
+/// let offsets: Vec<_> = find_isins_in_reader(log.as_bytes())
+///     .map(|hit| hit.unwrap().0.start)
+///     .collect();
+/// assert_eq!(offsets, vec![15, 48]);
+/// ```
+pub fn find_isins_in_reader<R: Read>(reader: R) -> ReadScanner<R> {
+    ScanOptions::new().scan_reader(reader)
+}
+
+/// An iterator over the candidates in a text, in order, as returned by [`find_isins()`] and
+/// [`ScanOptions::scan()`]. Each item is the byte range of the candidate and the result of parsing
+/// it.
+#[derive(Clone, Debug)]
+pub struct Scanner<'a> {
+    text: &'a [u8],
+    pos: usize,
+    options: ScanOptions,
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = (Range<usize>, Result<ISIN, Error>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let start = next_candidate(self.text, self.pos, self.options.boundary)?;
+            let end = start + 12;
+            let result = parse_bytes(&self.text[start..end]).map_err(Error::from);
+            if self.options.valid_only && result.is_err() {
+                self.pos = start + 1;
+                continue;
+            }
+            self.pos = end;
+            #[cfg(feature = "tracing")]
+            tracing::trace!(start, end, valid = result.is_ok(), "ISIN candidate");
+            return Some((start..end, result));
+        }
+    }
+}
+
+/// The number of bytes a [`ReadScanner`] asks its reader for at a time.
+pub const READ_BLOCK_SIZE: usize = 64 * 1024;
+
+/// An iterator over the candidates read from an [`io::Read`], in order, as returned by
+/// [`find_isins_in_reader()`] and [`ScanOptions::scan_reader()`]. Each item is the range of byte
+/// offsets of the candidate in the stream and the result of parsing it, or an error from the
+/// reader. After a reader error, the next call to `next()` tries reading again.
+///
+/// At most one block and the twelve bytes before it are held in memory at a time.
+#[derive(Debug)]
+pub struct ReadScanner<R> {
+    reader: R,
+    /// The unscanned part of the stream, preceded by the byte before it (if any) as context for
+    /// the boundary check
+    buf: Vec<u8>,
+    /// Where in `buf` to look for the next candidate
+    pos: usize,
+    /// The stream offset of `buf[0]`
+    offset: u64,
+    eof: bool,
+    options: ScanOptions,
+}
+
+impl<R> ReadScanner<R> {
+    /// Unwrap the reader. Anything already read from it but not yet scanned is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> ReadScanner<R> {
+    /// Discard the scanned part of the buffer, keeping the byte before the unscanned part, and
+    /// read the next block. Returns `false` at the end of the stream.
+    fn refill(&mut self) -> io::Result<bool> {
+        if self.eof {
+            return Ok(false);
+        }
+        let keep_from = self.pos.saturating_sub(1);
+        self.buf.drain(..keep_from);
+        self.offset += keep_from as u64;
+        self.pos -= keep_from;
+
+        let len = self.buf.len();
+        self.buf.resize(len + READ_BLOCK_SIZE, 0);
+        let read = loop {
+            match self.reader.read(&mut self.buf[len..]) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        };
+        match read {
+            Ok(read) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    offset = self.offset + len as u64,
+                    bytes = read,
+                    "read block"
+                );
+                self.buf.truncate(len + read);
+                self.eof = read == 0;
+                Ok(true)
+            }
+            Err(e) => {
+                self.buf.truncate(len);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for ReadScanner<R> {
+    type Item = io::Result<(Range<u64>, Result<ISIN, Error>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Until the end of the stream, a candidate must be followed by a byte we have read,
+            // to check the boundary after it.
+            let limit = if self.eof {
+                self.buf.len()
+            } else {
+                self.buf.len().saturating_sub(1)
+            };
+            let boundary = self.options.boundary;
+            let found = next_candidate(&self.buf[..limit], self.pos, boundary);
+            let Some(start) = found else {
+                // Candidates starting in the last eleven bytes are not complete yet.
+                self.pos = self.pos.max(limit.saturating_sub(11));
+                match self.refill() {
+                    Ok(true) => continue,
+                    Ok(false) => return None,
+                    Err(e) => return Some(Err(e)),
+                }
+            };
+            let end = start + 12;
+            if !boundary.allows(self.buf.get(end).copied()) {
+                self.pos = start + 1;
+                continue;
+            }
+            let result = parse_bytes(&self.buf[start..end]).map_err(Error::from);
+            if self.options.valid_only && result.is_err() {
+                self.pos = start + 1;
+                continue;
+            }
+            self.pos = end;
+            let range = self.offset + start as u64..self.offset + end as u64;
+            #[cfg(feature = "tracing")]
+            tracing::trace!(
+                start = range.start,
+                end = range.end,
+                valid = result.is_ok(),
+                "ISIN candidate"
+            );
+            return Some(Ok((range, result)));
+        }
+    }
+}
+
+/// Test whether or not a byte is allowed at the given position of a candidate.
+fn is_candidate_char(index: usize, b: u8) -> bool {
+    if index < 11 {
+        is_payload_char(index, b)
+    } else {
+        b.is_ascii_digit()
+    }
+}
+
+/// Find the start of the first candidate in `bytes` at or after `from`, with the boundary checked
+/// against the neighbouring bytes. The start and end of `bytes` count as boundaries.
+pub(crate) fn next_candidate(bytes: &[u8], from: usize, boundary: Boundary) -> Option<usize> {
+    let mut start = from;
+    'outer: while start + 12 <= bytes.len() {
+        // Check from the end, so a disallowed character lets us skip past it.
+        for index in (0..12).rev() {
+            let b = bytes[start + index];
+            if !is_candidate_char(index, b) {
+                // A byte that is not allowed anywhere, or a non-letter in the Prefix, cannot be
+                // part of any candidate starting before it.
+                let anywhere = b.is_ascii_digit() || b.is_ascii_uppercase();
+                start += if index < 2 || !anywhere { index + 1 } else { 1 };
+                continue 'outer;
+            }
+        }
+        let before = start.checked_sub(1).map(|i| bytes[i]);
+        let after = bytes.get(start + 12).copied();
+        if boundary.allows(before) && boundary.allows(after) {
+            return Some(start);
+        }
+        start += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use proptest::prelude::*;
+
+    fn ranges(options: ScanOptions, text: &str) -> Vec<Range<usize>> {
+        options.scan(text).map(|(range, _)| range).collect()
+    }
+
+    #[test]
+    fn find_in_text() {
+        let text = "Apple (US0378331005), Microsoft: US5949181045.";
+        let hits: Vec<_> = find_isins(text).collect();
+        assert_eq!(
+            hits,
+            vec![
+                (7..19, Ok(parse("US0378331005").unwrap())),
+                (33..45, Ok(parse("US5949181045").unwrap())),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_candidates() {
+        let text = "US0378331006 US0378331005";
+        let hits: Vec<_> = find_isins(text).collect();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].1, Err(parse("US0378331006").unwrap_err().into()));
+
+        let valid: Vec<_> = ScanOptions::new().valid_only(true).scan(text).collect();
+        assert_eq!(valid, vec![(13..25, Ok(parse("US0378331005").unwrap()))]);
+    }
+
+    #[test]
+    fn boundaries() {
+        let text = "XUS0378331005 US0378331005é\tUS5949181045";
+        assert_eq!(ranges(ScanOptions::new(), text), vec![14..26, 29..41]);
+        assert_eq!(
+            ranges(ScanOptions::new().boundary(Boundary::Whitespace), text),
+            vec![29..41]
+        );
+        assert_eq!(
+            ranges(ScanOptions::new().boundary(Boundary::None), text),
+            vec![0..12, 14..26, 29..41]
+        );
+    }
+
+    #[test]
+    fn valid_only_finds_overlapping_valid_candidate() {
+        // "AUS0378331005" starts with a candidate that is not valid, but contains Apple's ISIN.
+        let options = ScanOptions::new().boundary(Boundary::None).valid_only(true);
+        assert_eq!(ranges(options, "AUS0378331005"), vec![1..13]);
+    }
+
+    /// A reader returning one byte at a time, the worst case for a `ReadScanner`.
+    struct OneByte<'a>(&'a [u8]);
+
+    impl Read for OneByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.split_first() {
+                Some((&b, rest)) if !buf.is_empty() => {
+                    buf[0] = b;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    fn from_reader(
+        options: ScanOptions,
+        reader: impl Read,
+    ) -> Vec<(Range<usize>, Result<ISIN, Error>)> {
+        options
+            .scan_reader(reader)
+            .map(|hit| {
+                let (range, result) = hit.unwrap();
+                (range.start as usize..range.end as usize, result)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reader_across_blocks() {
+        let mut text = "x".repeat(READ_BLOCK_SIZE - 5);
+        text.push_str(" US0378331005 ");
+        text.push_str(&"y".repeat(READ_BLOCK_SIZE));
+        text.push_str(" US5949181045");
+        let hits = from_reader(ScanOptions::new(), text.as_bytes());
+        assert_eq!(hits, find_isins(&text).collect::<Vec<_>>());
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].0.start, READ_BLOCK_SIZE - 4);
+    }
+
+    #[test]
+    fn reader_errors() {
+        struct Failing;
+
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("failed"))
+            }
+        }
+
+        let mut scanner = find_isins_in_reader(b"US0378331005 ".chain(Failing));
+        assert!(scanner.next().unwrap().is_ok());
+        assert!(scanner.next().unwrap().is_err());
+        assert!(scanner.next().unwrap().is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn reader_matches_str(
+            text in "([A-Z0-9]{1,14}[ ,\n]{1,2}|US0378331005|x){0,12}",
+            boundary in prop_oneof![
+                Just(Boundary::NotAlphanumeric),
+                Just(Boundary::Whitespace),
+                Just(Boundary::None),
+            ],
+            valid_only in any::<bool>(),
+        ) {
+            let options = ScanOptions::new().boundary(boundary).valid_only(valid_only);
+            let expected: Vec<_> = options.scan(&text).collect();
+            prop_assert_eq!(from_reader(options, text.as_bytes()), expected.clone());
+            prop_assert_eq!(from_reader(options, OneByte(text.as_bytes())), expected);
+        }
+    }
+
+    #[test]
+    fn not_candidates() {
+        for text in [
+            "",
+            "US037833100",
+            "us0378331005",
+            "0US378331005",
+            "US037833100X",
+            "US0378331005123",
+            "The quick brown fox.",
+        ] {
+            assert_eq!(find_isins(text).count(), 0, "{text:?}");
+        }
+    }
+}
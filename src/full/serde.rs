@@ -0,0 +1,525 @@
+//! # isin::serde
+//!
+//! The `Serialize` and `Deserialize` implementations for [`ISIN`], and modules for use with
+//! `#[serde(with = "...")]` to choose a different representation for one field:
+//!
+//! * [`loose`]: serialize as usual, but deserialize from strings as `parse_loose()` accepts them.
+//! * [`bytes`]: serialize as the twelve bytes of the ISIN, for binary formats.
+//! * [`packed`]: serialize as the `u64` value of a [`PackedIsin`].
+//! * [`parts`]: serialize as a struct with the _Prefix_, _Basic Code_ and _Check Digit_ as separate
+//!   fields, for schemas that store them separately.
+//!
+//! Where a `with` attribute can not be used, such as for the elements of a `Vec` or the keys of a
+//! map, the [`LooseIsin`] wrapper deserializes as [`loose`] does.
+//!
+//! ```
+//! use isin::ISIN;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Position {
+//!     #[serde(with = "isin::serde::loose")]
+//!     isin: ISIN,
+//!     #[serde(with = "isin::serde::packed")]
+//!     benchmark: ISIN,
+//! }
+//!
+//! let json = r#"{ "isin": " us0378331005 ", "benchmark": 546395075064859685 }"#;
+//! let position: Position = serde_json::from_str(json).unwrap();
+//! assert_eq!(position.isin.to_string(), "US0378331005");
+//! assert_eq!(
+//!     serde_json::to_string(&position).unwrap(),
+//!     r#"{"isin":"US0378331005","benchmark":546395075064859685}"#
+//! );
+//! ```
+//!
+//! This module is only available with the `serde` feature enabled.
+
+use std::fmt;
+use std::ops::Deref;
+
+use ::serde::de::{Deserializer, Error as _, MapAccess, SeqAccess, Visitor};
+use ::serde::{Deserialize, Serialize, Serializer};
+
+use crate::packed::PackedIsin;
+use crate::{ParseOptions, ISIN};
+
+/// Accepts a string, or for binary formats the twelve bytes of the ISIN as a byte string or a
+/// sequence of bytes. Either way the value must be valid, as for `parse()` or `parse_bytes()`.
+impl<'de> Deserialize<'de> for ISIN {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(IsinVisitor)
+    }
+}
+
+impl Serialize for ISIN {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_ref())
+    }
+}
+
+/// Visits a strictly valid ISIN as a string, byte string or sequence of bytes.
+struct IsinVisitor;
+
+impl<'de> Visitor<'de> for IsinVisitor {
+    type Value = ISIN;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an ISIN")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: ::serde::de::Error,
+    {
+        crate::parse(v).map_err(E::custom)
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: ::serde::de::Error,
+    {
+        self.visit_str(&v)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: ::serde::de::Error,
+    {
+        crate::parse_bytes(v).map_err(E::custom)
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: ::serde::de::Error,
+    {
+        self.visit_bytes(&v)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut bytes = [0u8; 12];
+        for (i, b) in bytes.iter_mut().enumerate() {
+            *b = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+        }
+        let mut len = 12;
+        while seq.next_element::<u8>()?.is_some() {
+            len += 1;
+        }
+        if len != 12 {
+            return Err(A::Error::invalid_length(len, &self));
+        }
+        crate::parse_bytes(&bytes).map_err(A::Error::custom)
+    }
+}
+
+/// Visits an ISIN as a string, parsed with the given options.
+struct LooseVisitor(ParseOptions);
+
+impl Visitor<'_> for LooseVisitor {
+    type Value = ISIN;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an ISIN, possibly with whitespace or lowercase letters")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: ::serde::de::Error,
+    {
+        self.0.parse(v).map_err(E::custom)
+    }
+}
+
+/// Serialize an ISIN as a string, as usual, but deserialize it from any string `parse_loose()`
+/// accepts, with surrounding whitespace or lowercase letters. For use with
+/// `#[serde(with = "isin::serde::loose")]`.
+pub mod loose {
+    use super::*;
+
+    /// Serialize an ISIN as a string.
+    pub fn serialize<S>(isin: &ISIN, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        isin.serialize(serializer)
+    }
+
+    /// Deserialize an ISIN from a string as `parse_loose()` does.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ISIN, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(LooseVisitor(ParseOptions::loose()))
+    }
+}
+
+/// Serialize an ISIN as its twelve bytes, which binary formats store without a length prefix or
+/// UTF-8 check, and deserialize it from twelve bytes or a string. For use with
+/// `#[serde(with = "isin::serde::bytes")]`.
+pub mod bytes {
+    use super::*;
+
+    /// Serialize an ISIN as a byte string.
+    pub fn serialize<S>(isin: &ISIN, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(isin.as_bytes())
+    }
+
+    /// Deserialize an ISIN from a byte string, a sequence of bytes or a string.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ISIN, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(IsinVisitor)
+    }
+}
+
+/// Serialize an ISIN as the `u64` value of its [`PackedIsin`], and deserialize it from one. For
+/// use with `#[serde(with = "isin::serde::packed")]`.
+pub mod packed {
+    use super::*;
+
+    /// Serialize an ISIN as a packed `u64`.
+    pub fn serialize<S>(isin: &ISIN, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(PackedIsin::from(*isin).value())
+    }
+
+    /// Deserialize an ISIN from a packed `u64`, which must be the packed value of a valid ISIN.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ISIN, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = u64::deserialize(deserializer)?;
+        let packed = PackedIsin::try_from(value).map_err(D::Error::custom)?;
+        Ok(packed.to_isin())
+    }
+}
+
+/// Serialize an ISIN as a struct with its parts as string fields, and deserialize it from one:
+/// `{ "prefix": "US", "basic_code": "037833100", "check_digit": "5" }` in JSON. The parts must
+/// make a valid ISIN, as for `parse()`. For use with `#[serde(with = "isin::serde::parts")]`.
+///
+/// ```
+/// use isin::ISIN;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Row {
+///     #[serde(with = "isin::serde::parts")]
+///     isin: ISIN,
+/// }
+///
+/// let row = Row { isin: isin::parse("US0378331005").unwrap() };
+/// assert_eq!(
+///     serde_json::to_string(&row).unwrap(),
+///     r#"{"isin":{"prefix":"US","basic_code":"037833100","check_digit":"5"}}"#
+/// );
+/// ```
+pub mod parts {
+    use ::serde::ser::SerializeStruct;
+
+    use super::*;
+
+    const FIELDS: &[&str] = &["prefix", "basic_code", "check_digit"];
+
+    /// Serialize an ISIN as a struct of its parts.
+    pub fn serialize<S>(isin: &ISIN, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("ISIN", FIELDS.len())?;
+        st.serialize_field("prefix", isin.prefix())?;
+        st.serialize_field("basic_code", isin.basic_code())?;
+        st.serialize_field("check_digit", &AsRef::<str>::as_ref(isin)[11..])?;
+        st.end()
+    }
+
+    /// Deserialize an ISIN from a struct of its parts, as a map or a sequence in field order.
+    /// Fields other than the parts are ignored.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<ISIN, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PartsVisitor;
+
+        impl<'de> Visitor<'de> for PartsVisitor {
+            type Value = ISIN;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("the prefix, basic code and check digit of an ISIN")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut parts: [String; 3] = Default::default();
+                for (i, part) in parts.iter_mut().enumerate() {
+                    *part = seq
+                        .next_element()?
+                        .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+                }
+                join(parts)
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut parts: [Option<String>; 3] = Default::default();
+                while let Some(key) = map.next_key::<String>()? {
+                    let Some(i) = FIELDS.iter().position(|&field| field == key) else {
+                        map.next_value::<::serde::de::IgnoredAny>()?;
+                        continue;
+                    };
+                    if parts[i].is_some() {
+                        return Err(A::Error::duplicate_field(FIELDS[i]));
+                    }
+                    parts[i] = Some(map.next_value()?);
+                }
+                let [prefix, basic_code, check_digit] = parts;
+                join([
+                    prefix.ok_or_else(|| A::Error::missing_field(FIELDS[0]))?,
+                    basic_code.ok_or_else(|| A::Error::missing_field(FIELDS[1]))?,
+                    check_digit.ok_or_else(|| A::Error::missing_field(FIELDS[2]))?,
+                ])
+            }
+        }
+
+        /// Parse the parts joined together as an ISIN, once each is known to be the right length.
+        fn join<E: ::serde::de::Error>(parts: [String; 3]) -> Result<ISIN, E> {
+            for ((part, field), len) in parts.iter().zip(FIELDS).zip([2, 9, 1]) {
+                if part.len() != len {
+                    return Err(E::custom(format_args!(
+                        "{field} must be {len} characters, but was {}",
+                        part.len()
+                    )));
+                }
+            }
+            crate::parse(&parts.concat()).map_err(E::custom)
+        }
+
+        deserializer.deserialize_struct("ISIN", FIELDS, PartsVisitor)
+    }
+}
+
+/// An `ISIN` that deserializes from any string `parse_loose()` accepts, with surrounding
+/// whitespace or lowercase letters. With `SEPARATORS` set to `true`, the
+/// [`DEFAULT_SEPARATORS`](crate::parse_options::DEFAULT_SEPARATORS) are also removed wherever they
+/// appear. It serializes as the ISIN does, and dereferences to the `ISIN`.
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use isin::serde::LooseIsin;
+///
+/// let json = r#"{ " us0378331005": 10, "US5949181045 ": 20 }"#;
+/// let holdings: BTreeMap<LooseIsin, u32> = serde_json::from_str(json).unwrap();
+/// let (first, _) = holdings.first_key_value().unwrap();
+/// assert_eq!(first.prefix(), "US");
+/// assert_eq!(**first, isin::parse("US0378331005").unwrap());
+///
+/// let isins: Vec<LooseIsin<true>> = serde_json::from_str(r#"["us-037833100-5"]"#).unwrap();
+/// assert_eq!(isins[0].to_string(), "US0378331005");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[repr(transparent)]
+pub struct LooseIsin<const SEPARATORS: bool = false>(pub ISIN);
+
+impl<const SEPARATORS: bool> LooseIsin<SEPARATORS> {
+    /// Return the wrapped `ISIN`.
+    pub fn into_inner(self) -> ISIN {
+        self.0
+    }
+}
+
+impl<const SEPARATORS: bool> Deref for LooseIsin<SEPARATORS> {
+    type Target = ISIN;
+
+    fn deref(&self) -> &ISIN {
+        &self.0
+    }
+}
+
+impl<const SEPARATORS: bool> From<ISIN> for LooseIsin<SEPARATORS> {
+    fn from(isin: ISIN) -> Self {
+        LooseIsin(isin)
+    }
+}
+
+impl<const SEPARATORS: bool> From<LooseIsin<SEPARATORS>> for ISIN {
+    fn from(loose: LooseIsin<SEPARATORS>) -> Self {
+        loose.0
+    }
+}
+
+impl<const SEPARATORS: bool> fmt::Display for LooseIsin<SEPARATORS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<const SEPARATORS: bool> Serialize for LooseIsin<SEPARATORS> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, const SEPARATORS: bool> Deserialize<'de> for LooseIsin<SEPARATORS> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let options = ParseOptions::loose().allow_separators(SEPARATORS);
+        deserializer
+            .deserialize_str(LooseVisitor(options))
+            .map(LooseIsin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use proptest::prelude::*;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Fields {
+        #[serde(with = "loose")]
+        loose: ISIN,
+        #[serde(with = "bytes")]
+        bytes: ISIN,
+        #[serde(with = "packed")]
+        packed: ISIN,
+    }
+
+    #[test]
+    fn with_modules() {
+        let apple = parse("US0378331005").unwrap();
+        let fields = Fields {
+            loose: apple,
+            bytes: apple,
+            packed: apple,
+        };
+        let json = serde_json::to_value(&fields).unwrap();
+        let packed = PackedIsin::from(apple).value();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "loose": "US0378331005",
+                "bytes": apple.as_bytes().to_vec(),
+                "packed": packed,
+            })
+        );
+        assert_eq!(serde_json::from_value::<Fields>(json).unwrap(), fields);
+
+        let json = serde_json::json!({
+            "loose": "\tus0378331005 ",
+            "bytes": "US0378331005",
+            "packed": packed,
+        });
+        assert_eq!(serde_json::from_value::<Fields>(json).unwrap(), fields);
+    }
+
+    #[test]
+    fn with_modules_reject_invalid() {
+        let packed = PackedIsin::from(parse("US0378331005").unwrap()).value();
+        for json in [
+            serde_json::json!({ "loose": "US0378331006", "bytes": "US0378331005", "packed": packed }),
+            serde_json::json!({ "loose": "US0378331005", "bytes": [85, 83], "packed": packed }),
+            serde_json::json!({ "loose": "US0378331005", "bytes": "US0378331005", "packed": packed + 1 }),
+            serde_json::json!({ "loose": "US0378331005", "bytes": "US0378331005", "packed": -1 }),
+        ] {
+            assert!(serde_json::from_value::<Fields>(json).is_err());
+        }
+    }
+
+    #[test]
+    fn loose_isin() {
+        let apple = parse("US0378331005").unwrap();
+        let loose: LooseIsin = serde_json::from_str(r#""\tus0378331005 ""#).unwrap();
+        assert_eq!(loose, LooseIsin(apple));
+        assert_eq!(*loose, apple);
+        assert_eq!(ISIN::from(loose), apple);
+        assert_eq!(serde_json::to_string(&loose).unwrap(), r#""US0378331005""#);
+
+        assert!(serde_json::from_str::<LooseIsin>(r#""US-037833100-5""#).is_err());
+        let loose: LooseIsin<true> = serde_json::from_str(r#"" us 0378.331005""#).unwrap();
+        assert_eq!(loose.into_inner(), apple);
+        assert_eq!(format!("{loose}"), "US0378331005");
+        assert!(serde_json::from_str::<LooseIsin<true>>(r#""US0378331006""#).is_err());
+        assert!(serde_json::from_str::<LooseIsin<true>>("12").is_err());
+    }
+
+    #[test]
+    fn parts() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Row {
+            #[serde(with = "super::parts")]
+            isin: ISIN,
+        }
+
+        let row = Row {
+            isin: parse("US0378331005").unwrap(),
+        };
+        let json = serde_json::json!({
+            "isin": { "prefix": "US", "basic_code": "037833100", "check_digit": "5" }
+        });
+        assert_eq!(serde_json::to_value(&row).unwrap(), json);
+        assert_eq!(serde_json::from_value::<Row>(json).unwrap(), row);
+
+        let json = serde_json::json!({ "isin": ["US", "037833100", "5"] });
+        assert_eq!(serde_json::from_value::<Row>(json).unwrap(), row);
+        let json = serde_json::json!({
+            "isin": { "check_digit": "5", "name": "Apple", "basic_code": "037833100", "prefix": "US" }
+        });
+        assert_eq!(serde_json::from_value::<Row>(json).unwrap(), row);
+
+        for (json, message) in [
+            (
+                serde_json::json!({ "isin": { "prefix": "US", "basic_code": "037833100" } }),
+                "missing field `check_digit`",
+            ),
+            (
+                serde_json::json!({ "isin": { "prefix": "U", "basic_code": "S03783310", "check_digit": "05" } }),
+                "prefix must be 2 characters, but was 1",
+            ),
+            (
+                serde_json::json!({ "isin": ["US", "037833100", "6"] }),
+                "incorrect check digit",
+            ),
+        ] {
+            let err = serde_json::from_value::<Row>(json).unwrap_err().to_string();
+            assert!(err.contains(message), "{err}");
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn with_modules_round_trip(payload in "[A-Z]{2}[0-9A-Z]{9}") {
+            let isin = crate::build_from_payload(&payload).unwrap();
+            let fields = Fields { loose: isin, bytes: isin, packed: isin };
+            let json = serde_json::to_string(&fields).unwrap();
+            prop_assert_eq!(serde_json::from_str::<Fields>(&json).unwrap(), fields);
+        }
+    }
+}
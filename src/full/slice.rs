@@ -0,0 +1,135 @@
+//! # isin::slice
+//!
+//! Queries over sorted slices of ISINs, the simplest compact storage for a fixed universe of
+//! identifiers. Each query is a binary search, so takes time logarithmic in the length of the
+//! slice.
+//!
+//! ```
+//! use isin::slice::{group_ranges_by_prefix, range_of_prefix};
+//!
+//! let mut isins: Vec<isin::ISIN> = ["US5949181045", "DE000A0GNPZ3", "US0378331005"]
+//!     .iter()
+//!     .map(|s| isin::parse(s).unwrap())
+//!     .collect();
+//! isins.sort();
+//!
+//! assert_eq!(range_of_prefix(&isins, "US"), 1..3);
+//! assert_eq!(range_of_prefix(&isins, "US03"), 1..2);
+//!
+//! let groups: Vec<_> = group_ranges_by_prefix(&isins)
+//!     .map(|(prefix, range)| (prefix.to_string(), range))
+//!     .collect();
+//! assert_eq!(groups, [("DE".to_string(), 0..1), ("US".to_string(), 1..3)]);
+//! ```
+//!
+//! The results are unspecified (but there is no panic) if the slice is not sorted.
+
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+use crate::{Prefix, ISIN};
+
+/// Return the range of indexes of the ISINs in `sorted` that start with `prefix`. The prefix can
+/// be a _Prefix_ such as `"US"`, or any other leading part of an ISIN. If there are none, the range
+/// is empty, and starts where such ISINs would be inserted.
+pub fn range_of_prefix(sorted: &[ISIN], prefix: &str) -> Range<usize> {
+    let prefix = prefix.as_bytes();
+    let start = sorted.partition_point(|isin| isin.as_bytes().as_slice() < prefix);
+    let len = sorted[start..].partition_point(|isin| isin.as_bytes().starts_with(prefix));
+    start..start + len
+}
+
+/// Iterate over the _Prefixes_ of the ISINs in `sorted`, in order, with the range of indexes of
+/// the ISINs with each one. Each range is found by binary search, so this takes time proportional
+/// to the number of _Prefixes_, not the number of ISINs.
+pub fn group_ranges_by_prefix(sorted: &[ISIN]) -> PrefixGroups<'_> {
+    PrefixGroups { sorted, start: 0 }
+}
+
+/// The iterator returned by [`group_ranges_by_prefix()`].
+#[derive(Clone, Debug)]
+pub struct PrefixGroups<'a> {
+    sorted: &'a [ISIN],
+    /// The index of the first ISIN of the next group
+    start: usize,
+}
+
+impl Iterator for PrefixGroups<'_> {
+    type Item = (Prefix, Range<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = self.sorted.get(self.start)?;
+        let prefix = first.prefix_typed();
+        let rest = &self.sorted[self.start..];
+        let len = rest.partition_point(|isin| isin.prefix_bytes() == prefix.as_bytes()[..]);
+        // An unsorted slice can not make the group empty, as it includes its first ISIN
+        let range = self.start..self.start + len.max(1);
+        self.start = range.end;
+        Some((prefix, range))
+    }
+}
+
+impl FusedIterator for PrefixGroups<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use proptest::prelude::*;
+
+    #[test]
+    fn prefix_ranges() {
+        let isins: Vec<ISIN> = ["DE000A0GNPZ3", "US0378331005", "US5949181045"]
+            .iter()
+            .map(|s| parse(s).unwrap())
+            .collect();
+
+        assert_eq!(range_of_prefix(&isins, ""), 0..3);
+        assert_eq!(range_of_prefix(&isins, "GB"), 1..1);
+        assert_eq!(range_of_prefix(&isins, "ZZ"), 3..3);
+        assert_eq!(range_of_prefix(&isins, "US0378331005"), 1..2);
+        assert_eq!(range_of_prefix(&isins, "US0378331005X"), 2..2);
+        assert_eq!(range_of_prefix(&[], "US"), 0..0);
+        assert_eq!(group_ranges_by_prefix(&[]).next(), None);
+    }
+
+    #[test]
+    fn unsorted_input_does_not_panic() {
+        let isins: Vec<ISIN> = ["US0378331005", "DE000A0GNPZ3", "US5949181045"]
+            .iter()
+            .map(|s| parse(s).unwrap())
+            .collect();
+        let _ = range_of_prefix(&isins, "US");
+        let total: usize = group_ranges_by_prefix(&isins).map(|(_, r)| r.len()).sum();
+        assert_eq!(total, 3);
+    }
+
+    proptest! {
+        #[test]
+        fn match_linear_scan(
+            payloads in proptest::collection::vec("[A-C]{2}[0-9A-C]{9}", 0..40),
+            prefix in "[A-C]{0,2}[0-9A-C]{0,3}",
+        ) {
+            let mut isins: Vec<ISIN> = payloads
+                .iter()
+                .map(|p| crate::build_from_payload(p).unwrap())
+                .collect();
+            isins.sort();
+
+            let matching: Vec<usize> = (0..isins.len())
+                .filter(|&i| isins[i].as_bytes().starts_with(prefix.as_bytes()))
+                .collect();
+            let range = range_of_prefix(&isins, &prefix);
+            prop_assert_eq!(range.clone().collect::<Vec<_>>(), matching);
+
+            let mut expected: Vec<(Prefix, Range<usize>)> = Vec::new();
+            for (i, isin) in isins.iter().enumerate() {
+                match expected.last_mut() {
+                    Some((p, r)) if *p == isin.prefix_typed() => r.end = i + 1,
+                    _ => expected.push((isin.prefix_typed(), i..i + 1)),
+                }
+            }
+            prop_assert_eq!(group_ranges_by_prefix(&isins).collect::<Vec<_>>(), expected);
+        }
+    }
+}
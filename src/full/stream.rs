@@ -0,0 +1,195 @@
+//! # isin::stream
+//!
+//! Validation of asynchronous streams of values, for use with
+//! [`futures`](https://docs.rs/futures) and the async runtimes built on it.
+//!
+//! The [`IsinStreamExt`] trait adds adapters to any `Stream` whose items can be viewed as
+//! strings:
+//!
+//! ```
+//! use futures::executor::block_on;
+//! use futures::stream::{self, StreamExt};
+//! use isin::stream::{IsinStreamExt, StreamOptions};
+//!
+//! let values = stream::iter(vec!["US0378331005".to_string(), "US0378331006".to_string()]);
+//! let results: Vec<_> = block_on(values.parse_isins(StreamOptions::new()).collect());
+//! assert!(results[0].is_ok());
+//! assert!(results[1].is_err());
+//! ```
+//!
+//! Validation is done inline when the stream is polled, without spawning tasks, as it takes much
+//! less time than a context switch. The values that are ready are validated together in batches
+//! by [`validate_batch()`](crate::validate_batch), and the batch size bounds the work done in
+//! one poll, so a large backlog of values can not keep the task from yielding to the executor.
+//!
+//! This module is only available with the `async` feature enabled.
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+
+use crate::{validate_batch, Error, ISIN};
+
+/// The default number of values [`ParseIsins`] validates in one poll.
+pub const DEFAULT_BATCH_SIZE: usize = 256;
+
+/// Options for [`IsinStreamExt::parse_isins()`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StreamOptions {
+    batch_size: usize,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        StreamOptions::new()
+    }
+}
+
+impl StreamOptions {
+    /// Options validating up to [`DEFAULT_BATCH_SIZE`] values in one poll.
+    pub const fn new() -> Self {
+        StreamOptions {
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    /// Set the largest number of ready values to take from the inner stream and validate in one
+    /// poll. A size of one validates each value as it arrives, with no buffering. A size of zero
+    /// is treated as one.
+    pub const fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = if batch_size == 0 { 1 } else { batch_size };
+        self
+    }
+}
+
+/// Adapters for streams of strings.
+pub trait IsinStreamExt: Stream {
+    /// Parse each value as `parse()` does, yielding the results in order.
+    fn parse_isins(self, options: StreamOptions) -> ParseIsins<Self>
+    where
+        Self: Sized,
+    {
+        ParseIsins {
+            stream: Box::pin(self),
+            batch_size: options.batch_size,
+            values: Vec::with_capacity(options.batch_size),
+            results: VecDeque::with_capacity(options.batch_size),
+            done: false,
+        }
+    }
+}
+
+impl<S> IsinStreamExt for S
+where
+    S: Stream,
+    S::Item: AsRef<str>,
+{
+}
+
+/// The stream returned by [`IsinStreamExt::parse_isins()`].
+pub struct ParseIsins<S: Stream> {
+    stream: Pin<Box<S>>,
+    batch_size: usize,
+    /// The values taken from `stream` for the next batch
+    values: Vec<S::Item>,
+    /// The results of the last batch not yet yielded
+    results: VecDeque<Result<ISIN, Error>>,
+    done: bool,
+}
+
+// The inner stream is pinned in its own allocation, and nothing else is ever pinned.
+impl<S: Stream> Unpin for ParseIsins<S> {}
+
+impl<S> Stream for ParseIsins<S>
+where
+    S: Stream,
+    S::Item: AsRef<str>,
+{
+    type Item = Result<ISIN, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.results.is_empty() && !this.done {
+            while this.values.len() < this.batch_size {
+                match this.stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(value)) => this.values.push(value),
+                    Poll::Ready(None) => {
+                        this.done = true;
+                        break;
+                    }
+                    Poll::Pending => break,
+                }
+            }
+            this.results.extend(validate_batch(&this.values));
+            this.values.clear();
+        }
+
+        match this.results.pop_front() {
+            Some(result) => Poll::Ready(Some(result)),
+            None if this.done => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.results.len();
+        if self.done {
+            return (buffered, Some(buffered));
+        }
+        let (lower, upper) = self.stream.size_hint();
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|upper| upper.checked_add(buffered)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use futures::executor::block_on;
+    use futures::stream::{self, StreamExt};
+
+    #[test]
+    fn results_in_order() {
+        let values: Vec<String> = (0..1000)
+            .map(|i| match i % 3 {
+                0 => "US0378331006".to_string(),
+                _ => "US0378331005".to_string(),
+            })
+            .collect();
+        let expected: Vec<_> = values
+            .iter()
+            .map(|v| parse(v).map_err(Error::from))
+            .collect();
+
+        for batch_size in [0, 1, 7, DEFAULT_BATCH_SIZE, 5000] {
+            let options = StreamOptions::new().batch_size(batch_size);
+            let stream = stream::iter(values.clone()).parse_isins(options);
+            assert_eq!(stream.size_hint(), (1000, Some(1000)));
+            let results: Vec<_> = block_on(stream.collect());
+            assert_eq!(results, expected);
+        }
+    }
+
+    #[test]
+    fn pending_inner_stream() {
+        // A channel whose sender has not yet sent anything is pending, and the adapter must be
+        // woken when values arrive.
+        let (tx, rx) = futures::channel::mpsc::unbounded::<&str>();
+        let mut stream = rx.parse_isins(StreamOptions::new());
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        assert!(Pin::new(&mut stream).poll_next(&mut cx).is_pending());
+
+        tx.unbounded_send("US0378331005").unwrap();
+        tx.unbounded_send("US0378331006").unwrap();
+        drop(tx);
+        let results: Vec<_> = block_on(stream.collect());
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok() && results[1].is_err());
+    }
+}
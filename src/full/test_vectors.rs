@@ -0,0 +1,145 @@
+//! # isin::test_vectors
+//!
+//! The example ISINs from the annexes of ISO 6166, for testing code layered on this crate, or
+//! ports of it to other languages, against the authoritative examples. Every vector is a valid
+//! ISIN:
+//!
+//! ```
+//! for vector in isin::test_vectors::all() {
+//!     let isin = vector.to_isin();
+//!     assert_eq!(isin.to_string(), vector.value, "{}", vector.source);
+//! }
+//! ```
+
+use crate::{parse, ISIN};
+
+/// An example ISIN from ISO 6166.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TestVector {
+    /// The ISIN, which is valid
+    pub value: &'static str,
+    /// What the ISIN identifies, as given by the standard, if it says
+    pub description: &'static str,
+    /// Where in the standard the example is
+    pub source: &'static str,
+}
+
+impl TestVector {
+    /// Return the vector as an `ISIN`.
+    pub fn to_isin(&self) -> ISIN {
+        parse(self.value).expect("test vectors are valid")
+    }
+}
+
+/// Define a `TestVector` from its value, description and source.
+const fn vector(
+    value: &'static str,
+    description: &'static str,
+    source: &'static str,
+) -> TestVector {
+    TestVector {
+        value,
+        description,
+        source,
+    }
+}
+
+/// The worked examples of Annex C, with the calculation of their _Check Digits_.
+pub const ANNEX_C: [TestVector; 3] = [
+    vector("ES0SI0000005", "IBEX 35", "Annex C, example 1, page 10"),
+    vector(
+        "JP3788600009",
+        "Hitachi Ltd. Shares",
+        "Annex C, example 2, page 11",
+    ),
+    vector(
+        "DE000A0GNPZ3",
+        "Allianz Finance II 5 3/8% without expiration date",
+        "Annex C, example 3, page 11",
+    ),
+];
+
+/// The sample ISINs of Annex E.
+pub const ANNEX_E: [TestVector; 24] = [
+    vector("JP3788600009", "", "Annex E, page 13"),
+    vector("US9047847093", "", "Annex E, page 13"),
+    vector("IE00BFXC1P95", "", "Annex E, page 13"),
+    vector("DE000A0GNPZ3", "", "Annex E, page 13"),
+    vector("XS2021448886", "", "Annex E, page 13"),
+    vector("US36962GXZ26", "", "Annex E, page 13"),
+    vector("FR0000571077", "", "Annex E, page 13"),
+    vector("US277847UB38", "", "Annex E, page 13"),
+    vector("US65412AEW80", "", "Annex E, page 13"),
+    vector("GB00BF0FCW58", "", "Annex E, page 13"),
+    vector("FR0000312928", "", "Annex E, page 13"),
+    vector("DE000DL3T7M1", "", "Annex E, page 13"),
+    vector("ES0A02234250", "", "Annex E, page 14"),
+    vector("EZR9HY1361L7", "", "Annex E, page 14"),
+    vector("CH0107166065", "", "Annex E, page 14"),
+    vector("XS0313614355", "", "Annex E, page 14"),
+    vector("DE000A0AE077", "", "Annex E, page 14"),
+    vector("CH0002813860", "", "Annex E, page 14"),
+    vector("TRLTCMB00045", "", "Annex E, page 14"),
+    vector("ES0SI0000005", "", "Annex E, page 14"),
+    vector("GB00B56Z6W79", "", "Annex E, page 14"),
+    vector("AU000000SKI7", "", "Annex E, page 14"),
+    vector("EU000A1RRN98", "", "Annex E, page 14"),
+    vector("LI0024807526", "", "Annex E, page 14"),
+];
+
+/// Iterate over every test vector: those of [`ANNEX_C`], then those of [`ANNEX_E`]. Some ISINs
+/// appear in both annexes.
+pub fn all() -> impl Iterator<Item = &'static TestVector> {
+    ANNEX_C.iter().chain(ANNEX_E.iter())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checksum::has_valid_check_digit;
+
+    #[test]
+    fn vectors_are_valid() {
+        for vector in all() {
+            assert_eq!(vector.to_isin().to_string(), vector.value);
+            assert!(has_valid_check_digit(vector.value.as_bytes()));
+            assert!(vector.source.starts_with("Annex "));
+        }
+        assert_eq!(all().count(), 27);
+        assert!(ANNEX_C.iter().all(|v| !v.description.is_empty()));
+    }
+
+    #[test]
+    fn vectors_match_the_standard() {
+        let annex_c = ["ES0SI0000005", "JP3788600009", "DE000A0GNPZ3"];
+        let annex_e = [
+            "JP3788600009",
+            "US9047847093",
+            "IE00BFXC1P95",
+            "DE000A0GNPZ3",
+            "XS2021448886",
+            "US36962GXZ26",
+            "FR0000571077",
+            "US277847UB38",
+            "US65412AEW80",
+            "GB00BF0FCW58",
+            "FR0000312928",
+            "DE000DL3T7M1",
+            "ES0A02234250",
+            "EZR9HY1361L7",
+            "CH0107166065",
+            "XS0313614355",
+            "DE000A0AE077",
+            "CH0002813860",
+            "TRLTCMB00045",
+            "ES0SI0000005",
+            "GB00B56Z6W79",
+            "AU000000SKI7",
+            "EU000A1RRN98",
+            "LI0024807526",
+        ];
+        let values: Vec<&str> = all().map(|v| v.value).collect();
+        let expected: Vec<&str> = annex_c.iter().chain(annex_e.iter()).copied().collect();
+        assert_eq!(values, expected);
+    }
+}
@@ -0,0 +1,208 @@
+//! # isin::tokenizer
+//!
+//! A push-based tokenizer for finding ISINs in a stream of bytes that arrives in arbitrary chunks,
+//! such as from a socket or a decompressor, without first reassembling it into lines.
+//!
+//! The stream is split into tokens at delimiter bytes (by default ASCII whitespace, `,`, `;` and
+//! `|`), and every non-empty token is parsed as an ISIN. A token split across two chunks is
+//! buffered until the rest of it arrives.
+//!
+//! ```
+//! use isin::tokenizer::{IsinTokenizer, Token};
+//!
+//! let mut tokenizer = IsinTokenizer::new();
+//! let mut tokens = Vec::new();
+//! for chunk in [&b"US03783"[..], b"31005\nUS59491810", b"45,XX\n", b"US037833"] {
+//!     tokenizer.push(chunk, |token| tokens.push(token));
+//! }
+//! tokenizer.finish(|token| tokens.push(token));
+//!
+//! assert_eq!(tokens.len(), 4);
+//! assert_eq!(tokens[0], Token::Valid(isin::parse("US0378331005").unwrap()));
+//! assert_eq!(tokens[1], Token::Valid(isin::parse("US5949181045").unwrap()));
+//! assert!(matches!(&tokens[2], Token::Rejected { text, .. } if text == b"XX"));
+//! assert!(matches!(&tokens[3], Token::Rejected { text, .. } if text == b"US037833"));
+//! ```
+
+use crate::{parse_bytes, ParseError, ISIN};
+
+/// The delimiters used by [`IsinTokenizer::new()`]: ASCII whitespace, `,`, `;` and `|`.
+pub const DEFAULT_DELIMITERS: &[u8] = b" \t\n\x0b\x0c\r,;|";
+
+/// The most bytes of a rejected token kept in [`Token::Rejected`]. Longer tokens are truncated,
+/// although the error reports their full length.
+pub const MAX_REJECTED_TEXT: usize = 64;
+
+/// A token emitted by an [`IsinTokenizer`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Token {
+    /// A token that is a valid ISIN.
+    Valid(ISIN),
+    /// A token that is not a valid ISIN.
+    Rejected {
+        /// The token, truncated to at most [`MAX_REJECTED_TEXT`] bytes
+        text: Vec<u8>,
+        /// Why the token is not a valid ISIN
+        error: ParseError,
+    },
+}
+
+/// A push-based tokenizer emitting a [`Token`] for every delimited token of a byte stream. See the
+/// [module docs](self).
+#[derive(Clone, Debug)]
+pub struct IsinTokenizer {
+    delimiters: &'static [u8],
+    buf: Vec<u8>,
+    len: usize,
+}
+
+impl Default for IsinTokenizer {
+    fn default() -> Self {
+        IsinTokenizer::new()
+    }
+}
+
+impl IsinTokenizer {
+    /// A tokenizer splitting at the [`DEFAULT_DELIMITERS`].
+    pub fn new() -> IsinTokenizer {
+        IsinTokenizer::with_delimiters(DEFAULT_DELIMITERS)
+    }
+
+    /// A tokenizer splitting at any of the given delimiter bytes.
+    pub fn with_delimiters(delimiters: &'static [u8]) -> IsinTokenizer {
+        IsinTokenizer {
+            delimiters,
+            buf: Vec::with_capacity(MAX_REJECTED_TEXT),
+            len: 0,
+        }
+    }
+
+    /// Process the next chunk of the stream, passing each token completed within it to `emit`. A
+    /// token still incomplete at the end of the chunk is kept until the next call.
+    pub fn push<F: FnMut(Token)>(&mut self, chunk: &[u8], mut emit: F) {
+        for &b in chunk {
+            if self.delimiters.contains(&b) {
+                if let Some(token) = self.take() {
+                    emit(token);
+                }
+            } else {
+                if self.buf.len() < MAX_REJECTED_TEXT {
+                    self.buf.push(b);
+                }
+                self.len += 1;
+            }
+        }
+    }
+
+    /// End the stream, passing the final token to `emit` if the stream did not end with a
+    /// delimiter. The tokenizer is then ready for a new stream.
+    pub fn finish<F: FnMut(Token)>(&mut self, mut emit: F) {
+        if let Some(token) = self.take() {
+            emit(token);
+        }
+    }
+
+    /// Return the buffered token, if any, leaving the buffer empty.
+    fn take(&mut self) -> Option<Token> {
+        if self.len == 0 {
+            return None;
+        }
+        let token = if self.len == self.buf.len() {
+            match parse_bytes(&self.buf) {
+                Ok(isin) => Token::Valid(isin),
+                Err(error) => Token::Rejected {
+                    text: self.buf.clone(),
+                    error,
+                },
+            }
+        } else {
+            Token::Rejected {
+                text: self.buf.clone(),
+                error: ParseError::InvalidValueArrayLength {
+                    was: crate::error::error_len(self.len),
+                },
+            }
+        };
+        self.buf.clear();
+        self.len = 0;
+        Some(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use proptest::prelude::*;
+
+    fn tokenize(chunks: &[&[u8]]) -> Vec<Token> {
+        let mut tokenizer = IsinTokenizer::new();
+        let mut tokens = Vec::new();
+        for chunk in chunks {
+            tokenizer.push(chunk, |token| tokens.push(token));
+        }
+        tokenizer.finish(|token| tokens.push(token));
+        tokens
+    }
+
+    #[test]
+    fn empty_and_delimiters_only() {
+        assert!(tokenize(&[]).is_empty());
+        assert!(tokenize(&[b"", b" \r\n", b",,;|"]).is_empty());
+    }
+
+    #[test]
+    fn rejects() {
+        let long = [b'A'; 100];
+        let tokens = tokenize(&[b"US0378331006 ", &long]);
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Rejected {
+                    text: b"US0378331006".to_vec(),
+                    error: parse("US0378331006").unwrap_err(),
+                },
+                Token::Rejected {
+                    text: long[..MAX_REJECTED_TEXT].to_vec(),
+                    error: ParseError::InvalidValueArrayLength { was: 100 },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_delimiters() {
+        let mut tokenizer = IsinTokenizer::with_delimiters(b"\x01");
+        let mut tokens = Vec::new();
+        tokenizer.push(b"US0378331005\x01US5949", |t| tokens.push(t));
+        tokenizer.push(b"181045\x01", |t| tokens.push(t));
+        tokenizer.finish(|t| tokens.push(t));
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Valid(parse("US0378331005").unwrap()),
+                Token::Valid(parse("US5949181045").unwrap()),
+            ]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn chunking_does_not_matter(
+            text in "([A-Z0-9]{1,14}[ ,\n]{1,2}){0,6}",
+            splits in proptest::collection::vec(any::<prop::sample::Index>(), 0..4),
+        ) {
+            let bytes = text.as_bytes();
+            let mut cuts: Vec<usize> = splits.iter().map(|i| i.index(bytes.len() + 1)).collect();
+            cuts.sort();
+            let mut chunks = Vec::new();
+            let mut start = 0;
+            for cut in cuts {
+                chunks.push(&bytes[start..cut]);
+                start = cut;
+            }
+            chunks.push(&bytes[start..]);
+            prop_assert_eq!(tokenize(&chunks), tokenize(&[bytes]));
+        }
+    }
+}
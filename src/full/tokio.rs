@@ -0,0 +1,147 @@
+//! # isin::tokio
+//!
+//! Asynchronous versions of the line validation functions of the [`bulk`](crate::bulk) module,
+//! reading from a [`tokio`](https://docs.rs/tokio) `AsyncBufRead` such as a buffered file or
+//! socket. They produce the same [`Report`], and as the lines are validated as they are read,
+//! reading waits on the source rather than blocking a thread.
+//!
+//! ```
+//! # futures::executor::block_on(async {
+//! let data = "US0378331005\nUS0378331006\n\nUS5949181045\n";
+//! let report = isin::tokio::validate_lines(data.as_bytes()).await.unwrap();
+//! assert_eq!((report.total, report.valid, report.invalid), (3, 2, 1));
+//! # });
+//! ```
+//!
+//! This module is only available with the `tokio` feature enabled.
+
+use std::io;
+use std::time::Instant;
+
+use ::tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+use crate::bulk::{strip_line_ending, Observer, Report, DEFAULT_KEPT_FAILURES};
+
+/// Validate every line read from `reader` as an ISIN, as
+/// [`bulk::validate_lines()`](crate::bulk::validate_lines) does.
+pub async fn validate_lines<R>(reader: R) -> io::Result<Report>
+where
+    R: AsyncBufRead + Unpin,
+{
+    validate_lines_keeping(reader, DEFAULT_KEPT_FAILURES).await
+}
+
+/// Validate every line read from `reader` as
+/// [`bulk::validate_lines_keeping()`](crate::bulk::validate_lines_keeping) does, keeping the
+/// first `keep_failures` failures.
+pub async fn validate_lines_keeping<R>(reader: R, keep_failures: usize) -> io::Result<Report>
+where
+    R: AsyncBufRead + Unpin,
+{
+    validate_lines_observed(reader, keep_failures, &mut |_| {}).await
+}
+
+/// Validate every line read from `reader` as [`validate_lines_keeping()`] does, passing the
+/// outcome for each line that is not blank to `observer`, as
+/// [`bulk::validate_lines_observed()`](crate::bulk::validate_lines_observed) does.
+pub async fn validate_lines_observed<R, O>(
+    reader: R,
+    keep_failures: usize,
+    observer: &mut O,
+) -> io::Result<Report>
+where
+    R: AsyncBufRead + Unpin,
+    O: Observer + ?Sized,
+{
+    let validation = read_lines(reader, keep_failures, observer);
+    #[cfg(feature = "tracing")]
+    let validation = tracing::Instrument::instrument(
+        validation,
+        tracing::info_span!("async_validate_lines", keep_failures),
+    );
+    validation.await
+}
+
+/// Validate the lines for [`validate_lines_keeping()`].
+async fn read_lines<R, O>(
+    mut reader: R,
+    keep_failures: usize,
+    observer: &mut O,
+) -> io::Result<Report>
+where
+    R: AsyncBufRead + Unpin,
+    O: Observer + ?Sized,
+{
+    let start = Instant::now();
+    let mut report = Report::default();
+    let mut buf = Vec::new();
+    let mut line = 0;
+
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf).await? == 0 {
+            break;
+        }
+        line += 1;
+        report.record(line, strip_line_ending(&buf), keep_failures, observer);
+    }
+
+    report.finish(start);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bulk;
+    use futures::executor::block_on;
+    use proptest::prelude::*;
+
+    #[test]
+    fn reader_errors_are_returned() {
+        struct Failing;
+
+        impl ::tokio::io::AsyncRead for Failing {
+            fn poll_read(
+                self: std::pin::Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+                _: &mut ::tokio::io::ReadBuf<'_>,
+            ) -> std::task::Poll<io::Result<()>> {
+                std::task::Poll::Ready(Err(io::Error::other("broken")))
+            }
+        }
+
+        let reader = ::tokio::io::BufReader::new(Failing);
+        let err = block_on(validate_lines(reader)).unwrap_err();
+        assert_eq!(err.to_string(), "broken");
+    }
+
+    #[test]
+    fn observer_sees_same_outcomes_as_bulk() {
+        let data = "US0378331005\nUS0378331006\n\nus03783310\n";
+        let mut expected = Vec::new();
+        bulk::validate_lines_observed(data.as_bytes(), 0, &mut |o| expected.push(o)).unwrap();
+        let mut outcomes = Vec::new();
+        block_on(validate_lines_observed(data.as_bytes(), 0, &mut |o| {
+            outcomes.push(o)
+        }))
+        .unwrap();
+        assert_eq!(outcomes, expected);
+        assert_eq!(outcomes.len(), 4);
+    }
+
+    proptest! {
+        #[test]
+        fn same_as_bulk(
+            lines in proptest::collection::vec("([A-Z]{2}[0-9A-Z]{9}[0-9])|( ?[a-zA-Z0-9]{0,13}\r?)", 0..50),
+            keep in 0usize..5,
+        ) {
+            let data = lines.join("\n");
+            let mut expected = bulk::validate_lines_keeping(data.as_bytes(), keep).unwrap();
+            let mut report = block_on(validate_lines_keeping(data.as_bytes(), keep)).unwrap();
+            expected.duration = Default::default();
+            report.duration = Default::default();
+            prop_assert_eq!(report, expected);
+        }
+    }
+}
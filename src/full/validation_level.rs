@@ -0,0 +1,109 @@
+//! # isin::validation_level
+//!
+//! Validation to a chosen depth, for pipelines where different stages deliberately check inputs
+//! with different strictness.
+
+use std::fmt;
+
+use crate::Error;
+
+/// How thoroughly to validate an ISIN, from least to most thorough. Each level includes the checks
+/// of the levels before it.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum ValidationLevel {
+    /// The length and the character classes of the _Prefix_, _Basic Code_ and _Check Digit_, as
+    /// checked by `validate_format()`.
+    FormatOnly,
+    /// The format and the value of the _Check Digit_, as checked by `validate()` and `parse()`.
+    Checksum,
+    /// The format, the _Check Digit_, and that the _Prefix_ is one allocated for use in ISINs, as
+    /// checked by [`Prefix::is_allocated()`](crate::Prefix::is_allocated).
+    RegistryAllocated,
+}
+
+impl fmt::Display for ValidationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ValidationLevel::FormatOnly => "format",
+            ValidationLevel::Checksum => "checksum",
+            ValidationLevel::RegistryAllocated => "registry allocation",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The failure of `validate_at()`: the level whose checks failed, and the error found.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ValidationFailure {
+    pub(crate) level: ValidationLevel,
+    pub(crate) error: Error,
+}
+
+impl ValidationFailure {
+    /// The least thorough level whose checks failed. This may be lower than the level asked for,
+    /// such as `FormatOnly` for an input of the wrong length validated at `Checksum` level.
+    pub fn level(&self) -> ValidationLevel {
+        self.level
+    }
+
+    /// The error found.
+    pub fn error(&self) -> Error {
+        self.error
+    }
+}
+
+impl fmt::Display for ValidationFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed {} validation: {}", self.level, self.error)
+    }
+}
+
+impl std::error::Error for ValidationFailure {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate_at;
+
+    #[test]
+    fn levels() {
+        use ValidationLevel::*;
+
+        let cases = [
+            ("US0378331005", None),
+            ("US037833100", Some(FormatOnly)),
+            ("US03783310-5", Some(FormatOnly)),
+            ("US0378331006", Some(Checksum)),
+        ];
+        for (input, failed) in cases {
+            for level in [FormatOnly, Checksum] {
+                let expected = failed.filter(|&failed| failed <= level);
+                assert_eq!(
+                    validate_at(level, input).err().map(|f| f.level()),
+                    expected,
+                    "{input} at {level}"
+                );
+            }
+        }
+
+        assert_eq!(validate_at(RegistryAllocated, "US0378331005"), Ok(()));
+        let qq = crate::build_from_payload("QQ000000000").unwrap();
+        let failure = validate_at(RegistryAllocated, qq.as_ref()).unwrap_err();
+        assert_eq!(failure.level(), RegistryAllocated);
+        assert_eq!(failure.error(), Error::UnallocatedPrefix { was: *b"QQ" });
+        assert_eq!(
+            failure.to_string(),
+            "failed registry allocation validation: prefix \"QQ\" is not an allocated country \
+             code or special prefix"
+        );
+        assert_eq!(validate_at(Checksum, qq.as_ref()), Ok(()));
+
+        let wrong = format!("QQ000000000{}", (qq.check_digit_value() + 1) % 10);
+        let failure = validate_at(RegistryAllocated, &wrong).unwrap_err();
+        assert_eq!(failure.level(), Checksum);
+    }
+}
@@ -0,0 +1,107 @@
+//! # isin::winnow
+//!
+//! Parsers for use with the [`winnow`](https://docs.rs/winnow) parser combinator library, so an
+//! ISIN can be part of a larger grammar.
+//!
+//! As with the [`nom`](https://docs.rs/nom) parsers, each parser takes exactly the next twelve
+//! characters of the input and requires them to be a valid ISIN as `parse()` does. When they are
+//! not, the input is left at the start of the twelve characters, and the [`ParseError`] is kept as
+//! the cause of the `winnow` error:
+//!
+//! ```
+//! use winnow::Parser;
+//!
+//! // The SecurityID field of a FIX message, when the SecurityIDSource is an ISIN.
+//! fn security_id(input: &mut &str) -> winnow::Result<isin::ISIN> {
+//!     ("48=", isin::winnow::isin).map(|(_, isin)| isin).parse_next(input)
+//! }
+//!
+//! let isin = security_id.parse("48=US0378331005").unwrap();
+//! assert_eq!(isin.to_string(), "US0378331005");
+//!
+//! let err = security_id.parse("48=US0378331006").unwrap_err();
+//! assert_eq!(err.offset(), 3);
+//! let cause = err.inner().cause().unwrap();
+//! assert_eq!(cause.to_string(), "incorrect check digit '6' when expecting '5'");
+//! ```
+//!
+//! This module is only available with the `winnow` feature enabled.
+
+use ::winnow::error::{FromExternalError, ParserError};
+use ::winnow::token::take;
+use ::winnow::Parser;
+
+use crate::{parse, parse_bytes, ParseError, ISIN};
+
+/// Parse an ISIN from the next twelve characters of a string.
+pub fn isin(input: &mut &str) -> ::winnow::Result<ISIN> {
+    isin_with(input)
+}
+
+/// Parse an ISIN from the next twelve bytes of a byte slice.
+pub fn isin_bytes(input: &mut &[u8]) -> ::winnow::Result<ISIN> {
+    isin_bytes_with(input)
+}
+
+/// Parse an ISIN from the next twelve characters of a string, with an error type of your choice,
+/// such as a `ModalResult` for use with `cut_err()`. A [`ParseError`] is converted with
+/// `E::from_external_error()`.
+pub fn isin_with<'i, E>(input: &mut &'i str) -> Result<ISIN, E>
+where
+    E: ParserError<&'i str> + FromExternalError<&'i str, ParseError>,
+{
+    take(12usize).try_map(parse).parse_next(input)
+}
+
+/// Parse an ISIN from the next twelve bytes of a byte slice, with an error type of your choice, as
+/// for [`isin_with()`].
+pub fn isin_bytes_with<'i, E>(input: &mut &'i [u8]) -> Result<ISIN, E>
+where
+    E: ParserError<&'i [u8]> + FromExternalError<&'i [u8], ParseError>,
+{
+    take(12usize).try_map(parse_bytes).parse_next(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::winnow::error::{ContextError, ErrMode};
+    use ::winnow::ModalResult;
+
+    #[test]
+    fn parse_and_leave_rest() {
+        let apple = parse("US0378331005").unwrap();
+        let mut input = "US0378331005, etc.";
+        assert_eq!(isin(&mut input), Ok(apple));
+        assert_eq!(input, ", etc.");
+
+        let mut input = &b"US0378331005\x01"[..];
+        assert_eq!(isin_bytes(&mut input), Ok(apple));
+        assert_eq!(input, b"\x01");
+    }
+
+    #[test]
+    fn errors_keep_input_and_cause() {
+        let mut input = "US037833100";
+        assert!(isin(&mut input).is_err());
+        assert_eq!(input, "US037833100");
+
+        let mut input = "US0378331006 etc.";
+        let err = isin(&mut input).unwrap_err();
+        assert_eq!(input, "US0378331006 etc.");
+        let cause = err.cause().unwrap().downcast_ref::<ParseError>();
+        assert_eq!(cause, Some(&parse("US0378331006").unwrap_err()));
+    }
+
+    #[test]
+    fn modal_errors() {
+        let mut input = "US0378331006";
+        let result: ModalResult<ISIN> = isin_with(&mut input);
+        assert!(matches!(result, Err(ErrMode::Backtrack(_))));
+
+        let result: ModalResult<ISIN> =
+            ::winnow::combinator::cut_err(isin_with::<ErrMode<ContextError>>)
+                .parse_next(&mut input);
+        assert!(matches!(result, Err(ErrMode::Cut(_))));
+    }
+}